@@ -7,10 +7,8 @@
 #![windows_subsystem = "windows"]
 #![allow(clippy::single_match)]
 
-use std::sync::Arc;
-
 use accesskit::{DefaultActionVerb, Role};
-use masonry::app_driver::{AppDriver, DriverCtx};
+use masonry::app_driver::{AppDriver, DriverCtx, WindowId};
 use masonry::widget::{Align, CrossAxisAlignment, Flex, Label, RootWidget, SizedBox, WidgetRef};
 use masonry::{
     AccessCtx, AccessEvent, Action, BoxConstraints, Color, EventCtx, LayoutCtx, LifeCycle,
@@ -156,7 +154,7 @@ impl Widget for CalcButton {
             }
             PointerEvent::PointerUp(_, _) => {
                 if ctx.is_active() && !ctx.is_disabled() {
-                    ctx.submit_action(Action::Other(Arc::new(self.action)));
+                    ctx.submit_other_action(self.action);
                     ctx.request_paint();
                     trace!("CalcButton {:?} released", ctx.widget_id());
                 }
@@ -176,7 +174,7 @@ impl Widget for CalcButton {
         if event.target == ctx.widget_id() {
             match event.action {
                 accesskit::Action::Default => {
-                    ctx.submit_action(Action::Other(Arc::new(self.action)));
+                    ctx.submit_other_action(self.action);
                     ctx.request_paint();
                 }
                 _ => {}
@@ -242,13 +240,16 @@ impl Widget for CalcButton {
 }
 
 impl AppDriver for CalcState {
-    fn on_action(&mut self, ctx: &mut DriverCtx<'_>, _widget_id: WidgetId, action: Action) {
-        match action {
-            Action::Other(payload) => match payload.downcast_ref::<CalcAction>().unwrap() {
-                CalcAction::Digit(digit) => self.digit(*digit),
-                CalcAction::Op(op) => self.op(*op),
-            },
-            _ => unreachable!(),
+    fn on_action(
+        &mut self,
+        ctx: &mut DriverCtx<'_>,
+        _window_id: WindowId,
+        _widget_id: WidgetId,
+        action: Action,
+    ) {
+        match action.downcast_other::<CalcAction>().unwrap() {
+            CalcAction::Digit(digit) => self.digit(*digit),
+            CalcAction::Op(op) => self.op(*op),
         }
 
         ctx.get_root::<RootWidget<Flex>>()
@@ -9,7 +9,7 @@
 
 use accesskit::Role;
 use kurbo::Stroke;
-use masonry::app_driver::{AppDriver, DriverCtx};
+use masonry::app_driver::{AppDriver, DriverCtx, WindowId};
 use masonry::kurbo::BezPath;
 use masonry::widget::{FillStrat, RootWidget, WidgetRef};
 use masonry::{
@@ -28,7 +28,14 @@ use winit::window::Window;
 struct Driver;
 
 impl AppDriver for Driver {
-    fn on_action(&mut self, _ctx: &mut DriverCtx<'_>, _widget_id: WidgetId, _action: Action) {}
+    fn on_action(
+        &mut self,
+        _ctx: &mut DriverCtx<'_>,
+        _window_id: WindowId,
+        _widget_id: WidgetId,
+        _action: Action,
+    ) {
+    }
 }
 
 struct CustomWidget(String);
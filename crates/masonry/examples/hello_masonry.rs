@@ -7,7 +7,7 @@
 // On Windows platform, don't show a console when opening the app.
 #![windows_subsystem = "windows"]
 
-use masonry::app_driver::{AppDriver, DriverCtx};
+use masonry::app_driver::{AppDriver, DriverCtx, WindowId};
 use masonry::widget::{prelude::*, RootWidget};
 use masonry::widget::{Button, Flex, Label};
 use masonry::Action;
@@ -19,7 +19,13 @@ const VERTICAL_WIDGET_SPACING: f64 = 20.0;
 struct Driver;
 
 impl AppDriver for Driver {
-    fn on_action(&mut self, _ctx: &mut DriverCtx<'_>, _widget_id: WidgetId, action: Action) {
+    fn on_action(
+        &mut self,
+        _ctx: &mut DriverCtx<'_>,
+        _window_id: WindowId,
+        _widget_id: WidgetId,
+        action: Action,
+    ) {
         match action {
             Action::ButtonPressed => {
                 println!("Hello");
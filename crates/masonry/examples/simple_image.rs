@@ -8,7 +8,7 @@
 // On Windows platform, don't show a console when opening the app.
 #![windows_subsystem = "windows"]
 
-use masonry::app_driver::{AppDriver, DriverCtx};
+use masonry::app_driver::{AppDriver, DriverCtx, WindowId};
 use masonry::widget::{FillStrat, Image, RootWidget};
 use masonry::{Action, WidgetId};
 use vello::peniko::{Format, Image as ImageBuf};
@@ -18,7 +18,14 @@ use winit::window::Window;
 struct Driver;
 
 impl AppDriver for Driver {
-    fn on_action(&mut self, _ctx: &mut DriverCtx<'_>, _widget_id: WidgetId, _action: Action) {}
+    fn on_action(
+        &mut self,
+        _ctx: &mut DriverCtx<'_>,
+        _window_id: WindowId,
+        _widget_id: WidgetId,
+        _action: Action,
+    ) {
+    }
 }
 
 pub fn main() {
@@ -4,6 +4,8 @@
 use std::any::Any;
 use std::sync::Arc;
 
+use crate::event::DragData;
+
 // TODO - Refactor - See issue #1
 
 // TODO - TextCursor changed, ImeChanged, EnterKey, MouseEnter
@@ -17,10 +19,24 @@ pub enum Action {
     TextChanged(String),
     TextEntered(String),
     CheckboxChecked(bool),
+    /// A drag was dropped on a widget that [accepts](crate::Widget::accepts_drop) it,
+    /// the id of the action is the one of that widget.
+    Dropped(DragData),
     // FIXME - This is a huge hack
     Other(Arc<dyn Any>),
 }
 
+impl Action {
+    /// The payload of an [`Action::Other`] if it's a `T`, e.g. one submitted with
+    /// [`EventCtx::submit_other_action`](crate::EventCtx::submit_other_action).
+    pub fn downcast_other<T: Any>(&self) -> Option<&T> {
+        match self {
+            Self::Other(payload) => payload.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
 impl PartialEq for Action {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -28,6 +44,7 @@ impl PartialEq for Action {
             (Self::TextChanged(l0), Self::TextChanged(r0)) => l0 == r0,
             (Self::TextEntered(l0), Self::TextEntered(r0)) => l0 == r0,
             (Self::CheckboxChecked(l0), Self::CheckboxChecked(r0)) => l0 == r0,
+            (Self::Dropped(l0), Self::Dropped(r0)) => l0 == r0,
             #[allow(ambiguous_wide_pointer_comparisons)]
             // FIXME
             (Self::Other(val_l), Self::Other(val_r)) => Arc::ptr_eq(val_l, val_r),
@@ -43,6 +60,7 @@ impl std::fmt::Debug for Action {
             Self::TextChanged(text) => f.debug_tuple("TextChanged").field(text).finish(),
             Self::TextEntered(text) => f.debug_tuple("TextEntered").field(text).finish(),
             Self::CheckboxChecked(b) => f.debug_tuple("CheckboxChecked").field(b).finish(),
+            Self::Dropped(data) => f.debug_tuple("Dropped").field(data).finish(),
             Self::Other(_) => write!(f, "Other(...)"),
         }
     }
@@ -1,23 +1,1443 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::widget::WidgetMut;
-use crate::{Action, Widget, WidgetId};
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use tracing::debug;
+use winit::event::ElementState;
+use winit::keyboard::{Key, ModifiersState};
+use winit::window::WindowAttributes;
+
+use crate::contexts::WidgetCtx;
+use crate::kurbo::Point;
+use crate::render_root::{RenderRoot, WindowSizePolicy};
+use crate::widget::{DowncastError, ScrollAlign, WidgetMut, WidgetRef};
+use crate::{Action, Handled, TextEvent, Widget, WidgetId};
 
 // xilem::App will implement AppDriver
 
 pub struct DriverCtx<'a> {
-    // TODO
+    /// The window of the event that is handled, `main_root_widget` is its root widget.
+    pub(crate) window_id: WindowId,
     pub(crate) main_root_widget: WidgetMut<'a, Box<dyn Widget>>,
+    /// The other windows of the app.
+    pub(crate) windows: &'a mut Windows,
+    pub(crate) clipboard: &'a mut dyn Clipboard,
+    pub(crate) rebuild_requested: &'a mut bool,
+    pub(crate) app_handle: &'a AppHandle,
+}
+
+/// A serialized app state, see [`DriverCtx::snapshot_state`].
+#[cfg(feature = "state-snapshot")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateSnapshot(String);
+
+#[cfg(feature = "state-snapshot")]
+impl StateSnapshot {
+    /// A snapshot from its JSON, e.g. one that [`as_json`](Self::as_json) returned before a restart.
+    pub fn from_json(json: String) -> Self {
+        StateSnapshot(json)
+    }
+
+    /// The JSON of the snapshot.
+    pub fn as_json(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The id of a window of the app, see [`DriverCtx::open_window`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WindowId(NonZeroU64);
+
+impl WindowId {
+    /// Allocate a new, unique `WindowId`.
+    pub(crate) fn next() -> WindowId {
+        static WINDOW_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+        let id = WINDOW_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+        WindowId(id.try_into().unwrap())
+    }
+
+    pub fn to_raw(self) -> u64 {
+        self.0.into()
+    }
+}
+
+/// A window the event loop has to create or destroy, see [`DriverCtx::open_window`].
+pub(crate) enum WindowRequest {
+    Open(WindowId, Box<WindowAttributes>),
+    Close(WindowId),
+}
+
+/// The render roots of the open windows of an app, by window id.
+pub(crate) struct Windows {
+    pub(crate) roots: BTreeMap<WindowId, RenderRoot>,
+    /// The windows that were opened or closed by the driver since the event loop last handled them.
+    pub(crate) requests: Vec<WindowRequest>,
+}
+
+impl Windows {
+    pub(crate) fn new(window_id: WindowId, render_root: RenderRoot) -> Self {
+        Windows {
+            roots: BTreeMap::from([(window_id, render_root)]),
+            requests: Vec::new(),
+        }
+    }
+
+    /// The window whose root widget app-wide callbacks (e.g. [`AppDriver::on_rebuild`]) get,
+    /// which is the oldest open window.
+    pub(crate) fn main_window(&self) -> Option<WindowId> {
+        self.roots.keys().next().copied()
+    }
+
+    /// Call `f` with the render root of `window_id` and the other windows.
+    ///
+    /// Returns `None` if there is no such window, e.g. because it was closed.
+    fn with_window<R>(
+        &mut self,
+        window_id: WindowId,
+        f: impl FnOnce(&mut RenderRoot, &mut Windows) -> R,
+    ) -> Option<R> {
+        let mut render_root = self.roots.remove(&window_id)?;
+        let res = f(&mut render_root, self);
+        let closed = self
+            .requests
+            .iter()
+            .any(|request| matches!(request, WindowRequest::Close(id) if *id == window_id));
+        if !closed {
+            self.roots.insert(window_id, render_root);
+        }
+        Some(res)
+    }
 }
 
 pub trait AppDriver {
-    fn on_action(&mut self, ctx: &mut DriverCtx<'_>, widget_id: WidgetId, action: Action);
+    /// Handle an action submitted by the widget with `widget_id`, in the window `window_id`.
+    ///
+    /// Actions of a window that was closed (e.g. by a previous action) aren't delivered.
+    /// The default does nothing.
+    fn on_action(
+        &mut self,
+        ctx: &mut DriverCtx<'_>,
+        window_id: WindowId,
+        widget_id: WidgetId,
+        action: Action,
+    ) {
+        _ = (ctx, window_id, widget_id, action);
+    }
+
+    /// Handle an action like [`on_action`](Self::on_action), and report whether the
+    /// default follow-up of the action, if any, should be suppressed.
+    ///
+    /// When enabled with [`DriverCtx::set_enter_moves_focus`], an [`Action::TextEntered`]
+    /// that wasn't handled moves the focus to the next widget, as Tab would.
+    /// Return [`Handled::Yes`] to suppress that, e.g. when Enter submits a form,
+    /// or [`Handled::No`] if the action was ignored.
+    ///
+    /// The default calls [`on_action`](Self::on_action) and returns [`Handled::No`],
+    /// so drivers that don't care about follow-ups only implement `on_action`.
+    fn on_action_handled(
+        &mut self,
+        ctx: &mut DriverCtx<'_>,
+        window_id: WindowId,
+        widget_id: WidgetId,
+        action: Action,
+    ) -> Handled {
+        self.on_action(ctx, window_id, widget_id, action);
+        Handled::No
+    }
+
+    /// Handle a keyboard event before it's sent to the focused widget.
+    ///
+    /// This is the place for app-wide shortcuts, e.g. Ctrl+S to save.
+    /// Return `true` to consume the event, or `false` (the default) to
+    /// let the focused widget handle it as usual, which it will also do
+    /// for shortcuts that the app reacted to, but didn't consume.
+    fn on_key(
+        &mut self,
+        ctx: &mut DriverCtx<'_>,
+        key: &Key,
+        state: ElementState,
+        mods: ModifiersState,
+    ) -> bool {
+        _ = (ctx, key, state, mods);
+        false
+    }
+
+    /// Rebuild the widget tree from the app state, after [`DriverCtx::request_rebuild`] was called.
+    ///
+    /// This is called once before the next frame is painted, no matter how often
+    /// a rebuild was requested in the meantime. The default does nothing.
+    ///
+    /// The root widget of `ctx` is the one of the oldest open window, the other windows
+    /// can be rebuilt with [`DriverCtx::edit_window_root`].
+    fn on_rebuild(&mut self, ctx: &mut DriverCtx<'_>) {
+        _ = ctx;
+    }
+
+    /// Handle a command sent with a [`CommandSender`], e.g. the result of work on a background thread.
+    ///
+    /// Commands are delivered on the UI thread, in the order in which they were sent. The default drops them.
+    fn on_command(&mut self, ctx: &mut DriverCtx<'_>, command: Command) {
+        _ = (ctx, command);
+    }
+}
+
+/// Pass `action` to [`AppDriver::on_action_handled`], and apply its default follow-up if the driver didn't handle it.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_action(
+    windows: &mut Windows,
+    app_driver: &mut dyn AppDriver,
+    clipboard: &mut dyn Clipboard,
+    rebuild_requested: &mut bool,
+    app_handle: &AppHandle,
+    window_id: WindowId,
+    widget_id: WidgetId,
+    action: Action,
+) {
+    let delivered = windows.with_window(window_id, |render_root, windows| {
+        let follow_up =
+            render_root.state.enter_moves_focus && matches!(action, Action::TextEntered(_));
+        let handled = render_root.edit_root_widget(|root| {
+            let mut ctx = DriverCtx {
+                window_id,
+                main_root_widget: root,
+                windows,
+                clipboard,
+                rebuild_requested,
+                app_handle,
+            };
+            app_driver.on_action_handled(&mut ctx, window_id, widget_id, action)
+        });
+        if follow_up && !handled.is_handled() {
+            render_root.focus_next_widget();
+        }
+    });
+    if delivered.is_none() {
+        debug!(
+            "Dropped action of widget {:?} in closed window {:?}",
+            widget_id, window_id
+        );
+    }
+}
+
+/// Pass a keyboard event to [`AppDriver::on_key`], and `text_event` to the focused widget
+/// of the window `window_id` if the driver didn't consume it.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_key(
+    windows: &mut Windows,
+    app_driver: &mut dyn AppDriver,
+    clipboard: &mut dyn Clipboard,
+    rebuild_requested: &mut bool,
+    app_handle: &AppHandle,
+    window_id: WindowId,
+    (key, state, mods): (&Key, ElementState, ModifiersState),
+    text_event: TextEvent,
+) {
+    let consumed = with_driver_ctx(
+        windows,
+        clipboard,
+        rebuild_requested,
+        app_handle,
+        window_id,
+        |ctx| app_driver.on_key(ctx, key, state, mods),
+    );
+    if consumed == Some(false) {
+        if let Some(render_root) = windows.roots.get_mut(&window_id) {
+            render_root.handle_text_event(text_event);
+        }
+    }
+}
+
+/// Call `f` with a [`DriverCtx`] for the window `window_id`, if it's still open.
+pub(crate) fn with_driver_ctx<R>(
+    windows: &mut Windows,
+    clipboard: &mut dyn Clipboard,
+    rebuild_requested: &mut bool,
+    app_handle: &AppHandle,
+    window_id: WindowId,
+    f: impl FnOnce(&mut DriverCtx<'_>) -> R,
+) -> Option<R> {
+    windows.with_window(window_id, |render_root, windows| {
+        render_root.edit_root_widget(|root| {
+            let mut ctx = DriverCtx {
+                window_id,
+                main_root_widget: root,
+                windows,
+                clipboard,
+                rebuild_requested,
+                app_handle,
+            };
+            f(&mut ctx)
+        })
+    })
+}
+
+/// Call [`AppDriver::on_rebuild`] if a rebuild was requested since the last frame.
+pub(crate) fn rebuild_if_requested(
+    windows: &mut Windows,
+    app_driver: &mut dyn AppDriver,
+    clipboard: &mut dyn Clipboard,
+    rebuild_requested: &mut bool,
+    app_handle: &AppHandle,
+) {
+    let Some(main_window) = windows.main_window() else {
+        return;
+    };
+    if !std::mem::take(rebuild_requested) {
+        return;
+    }
+    with_driver_ctx(
+        windows,
+        clipboard,
+        rebuild_requested,
+        app_handle,
+        main_window,
+        |ctx| app_driver.on_rebuild(ctx),
+    );
+}
+
+/// Pass the commands that were sent to `app_handle` since the last call to [`AppDriver::on_command`].
+pub(crate) fn deliver_commands(
+    windows: &mut Windows,
+    app_driver: &mut dyn AppDriver,
+    clipboard: &mut dyn Clipboard,
+    rebuild_requested: &mut bool,
+    app_handle: &AppHandle,
+) {
+    // Commands sent by `on_command` itself are delivered in the same call
+    while let Some(main_window) = windows.main_window() {
+        let Some(command) = app_handle.queue.pop() else {
+            break;
+        };
+        with_driver_ctx(
+            windows,
+            clipboard,
+            rebuild_requested,
+            app_handle,
+            main_window,
+            |ctx| app_driver.on_command(ctx, command),
+        );
+    }
+}
+
+/// A command sent to the app with a [`CommandSender`], which can be downcast to the type that was sent.
+pub type Command = Box<dyn Any + Send>;
+
+/// The commands sent to an app, shared between its [`AppHandle`] and the [`CommandSender`]s.
+struct CommandQueue {
+    commands: Mutex<VecDeque<Command>>,
+    /// Wakes up the event loop, to deliver the commands.
+    wake: Box<dyn Fn() + Send + Sync>,
+}
+
+impl CommandQueue {
+    fn push(&self, command: Command) {
+        let mut commands = self.commands.lock().unwrap();
+        let was_empty = commands.is_empty();
+        commands.push_back(command);
+        // Don't hold the lock while waking up the event loop, which might deliver the commands right away
+        drop(commands);
+        // All commands are delivered at once, so the event loop only needs to be woken up for the first
+        if was_empty {
+            (self.wake)();
+        }
+    }
+
+    fn pop(&self) -> Option<Command> {
+        self.commands.lock().unwrap().pop_front()
+    }
+}
+
+/// A handle to the running app, see [`DriverCtx::app_handle`].
+pub struct AppHandle {
+    queue: Arc<CommandQueue>,
+}
+
+impl AppHandle {
+    /// Create a handle whose senders call `wake` when there are new commands to deliver.
+    pub(crate) fn new(wake: impl Fn() + Send + Sync + 'static) -> Self {
+        AppHandle {
+            queue: Arc::new(CommandQueue {
+                commands: Mutex::new(VecDeque::new()),
+                wake: Box::new(wake),
+            }),
+        }
+    }
+
+    /// Return a sender for commands to this app, which can be moved to other threads.
+    pub fn sender(&self) -> CommandSender {
+        CommandSender {
+            queue: Arc::downgrade(&self.queue),
+        }
+    }
+}
+
+/// Sends commands to [`AppDriver::on_command`] from any thread, see [`AppHandle::sender`].
+///
+/// The sender doesn't keep the app alive, once the app has exited, sending is a no-op.
+#[derive(Clone)]
+pub struct CommandSender {
+    queue: Weak<CommandQueue>,
+}
+
+impl CommandSender {
+    /// Send `command` to the app and wake up its event loop to deliver it.
+    pub fn send(&self, command: impl Any + Send) {
+        if let Some(queue) = self.queue.upgrade() {
+            queue.push(Box::new(command));
+        }
+    }
+}
+
+impl fmt::Debug for CommandSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandSender")
+            .field("app_alive", &(self.queue.strong_count() > 0))
+            .finish()
+    }
+}
+
+/// Access to a text clipboard.
+///
+/// The event loop uses [`PlatformClipboard`]; tests and embedders can provide
+/// their own implementation.
+pub trait Clipboard {
+    /// Return the current text contents of the clipboard, if there are any.
+    fn get_text(&mut self) -> Result<Option<String>, ClipboardError>;
+
+    /// Replace the contents of the clipboard with `text`.
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError>;
+}
+
+/// The reason a clipboard operation failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// There is no clipboard we can access synchronously on this platform.
+    ///
+    /// This is the case in browsers, where clipboard access is async and permission-gated.
+    Unsupported,
+    /// The platform refused access to the clipboard.
+    AccessDenied,
+    /// The platform clipboard returned some other error.
+    Platform(String),
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardError::Unsupported => {
+                write!(f, "clipboard access is not supported on this platform")
+            }
+            ClipboardError::AccessDenied => write!(f, "access to the clipboard was denied"),
+            ClipboardError::Platform(msg) => write!(f, "clipboard error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+/// The clipboard of the platform the app is running on.
+///
+/// The underlying handle is created lazily, on first use.
+#[derive(Default)]
+pub struct PlatformClipboard {
+    #[cfg(not(target_arch = "wasm32"))]
+    inner: Option<arboard::Clipboard>,
+}
+
+impl PlatformClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn inner(&mut self) -> Result<&mut arboard::Clipboard, ClipboardError> {
+        if self.inner.is_none() {
+            self.inner = Some(arboard::Clipboard::new().map_err(ClipboardError::from)?);
+        }
+        Ok(self.inner.as_mut().unwrap())
+    }
+}
+
+impl Clipboard for PlatformClipboard {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+        match self.inner()?.get_text() {
+            Ok(text) => Ok(Some(text)),
+            Err(arboard::Error::ContentNotAvailable) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.inner()?.set_text(text).map_err(ClipboardError::from)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn set_text(&mut self, _text: &str) -> Result<(), ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<arboard::Error> for ClipboardError {
+    fn from(err: arboard::Error) -> Self {
+        match err {
+            arboard::Error::ClipboardNotSupported => ClipboardError::Unsupported,
+            arboard::Error::ClipboardOccupied => ClipboardError::AccessDenied,
+            err => ClipboardError::Platform(err.to_string()),
+        }
+    }
 }
 
 impl<'a> DriverCtx<'a> {
-    /// Return a [`WidgetMut`] to the root widget.
+    /// The window of the event that is handled, whose root widget [`get_root`](Self::get_root) returns.
+    pub fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+
+    /// Open a new window with `root_widget`, and return its id.
+    ///
+    /// The window is created once the current callback has returned, but its root widget
+    /// can already be edited with [`edit_window_root`](Self::edit_window_root).
+    pub fn open_window(
+        &mut self,
+        attributes: WindowAttributes,
+        root_widget: impl Widget,
+    ) -> WindowId {
+        let window_id = WindowId::next();
+        let render_root = RenderRoot::new(root_widget, WindowSizePolicy::User, 1.0);
+        self.windows.roots.insert(window_id, render_root);
+        self.windows
+            .requests
+            .push(WindowRequest::Open(window_id, Box::new(attributes)));
+        window_id
+    }
+
+    /// Close the window `window_id`, which may be the window of the current event.
+    ///
+    /// The actions its widgets submitted that weren't handled yet are dropped.
+    /// Once the last window is closed, the app exits.
+    pub fn close_window(&mut self, window_id: WindowId) {
+        self.windows.roots.remove(&window_id);
+        self.windows.requests.push(WindowRequest::Close(window_id));
+    }
+
+    /// Call `f` with a [`WidgetMut`] to the root widget of the window `window_id`.
+    ///
+    /// Returns `None` if there is no such window, e.g. because it was closed.
+    pub fn edit_window_root<R>(
+        &mut self,
+        window_id: WindowId,
+        f: impl FnOnce(WidgetMut<'_, Box<dyn Widget>>) -> R,
+    ) -> Option<R> {
+        if window_id == self.window_id {
+            let root = &mut self.main_root_widget;
+            let root = WidgetMut {
+                ctx: WidgetCtx {
+                    global_state: root.ctx.global_state,
+                    parent_widget_state: root.ctx.parent_widget_state,
+                    widget_state: root.ctx.widget_state,
+                },
+                widget: root.widget,
+            };
+            return Some(f(root));
+        }
+        let render_root = self.windows.roots.get_mut(&window_id)?;
+        Some(render_root.edit_root_widget(f))
+    }
+
+    /// Return a [`WidgetMut`] to the root widget of the window of the current event.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the root widget isn't of type `W`, see [`DriverCtx::try_get_root`].
     pub fn get_root<W: Widget>(&mut self) -> WidgetMut<'_, W> {
         self.main_root_widget.downcast()
     }
+
+    /// Return a [`WidgetMut`] to the root widget, or an error naming the expected
+    /// and actual types if the root widget isn't of type `W`.
+    pub fn try_get_root<W: Widget>(&mut self) -> Result<WidgetMut<'_, W>, DowncastError> {
+        let actual = self.main_root_widget.widget.type_name();
+        self.main_root_widget
+            .try_downcast()
+            .ok_or_else(|| DowncastError::new::<W>(actual))
+    }
+
+    /// Return the id of the innermost widget at `point` (in window coordinates), as of the last layout pass.
+    ///
+    /// Where widgets overlap, the one painted on top is returned, e.g. to show a tooltip for the hovered widget.
+    pub fn widget_at(&self, point: Point) -> Option<WidgetId> {
+        let root = WidgetRef::new(
+            self.main_root_widget.ctx.widget_state,
+            &**self.main_root_widget.widget,
+        );
+        root.find_widget_at_pos(point)
+            .map(|widget| widget.state().id)
+    }
+
+    /// Scroll the portals containing the widget with `widget_id` so that it's visible, placed according to `align`.
+    ///
+    /// Only the innermost enclosing [`Portal`](crate::widget::Portal) is scrolled. This does nothing if the widget
+    /// isn't inside a portal, or doesn't exist. If the widget wasn't laid out yet (e.g. it was just added),
+    /// it's scrolled to after the next layout pass. Only the last request before that pass is applied.
+    pub fn scroll_to(&mut self, widget_id: WidgetId, align: ScrollAlign) {
+        self.main_root_widget.ctx.global_state.scroll_request = Some((widget_id, align));
+    }
+
+    /// Paint the layout bounds of every widget of the current window, with its [`WidgetId`], over the UI.
+    ///
+    /// The bounds are colored by id, to tell nested widgets apart. The overlay is only painted,
+    /// so it affects neither the layout nor which widget is hit by the pointer.
+    pub fn set_debug_layout(&mut self, enabled: bool) {
+        let ctx = &mut self.main_root_widget.ctx;
+        if ctx.global_state.debug_paint == enabled {
+            return;
+        }
+        ctx.global_state.debug_paint = enabled;
+        // TODO - What we'd really like is to request a repaint for every single widget.
+        ctx.request_layout();
+        ctx.request_paint();
+    }
+
+    /// Paint a ring around the focused widget of the current window, so that keyboard users see
+    /// where Tab moved the focus to.
+    ///
+    /// The ring is painted around the layout bounds of the widget, without affecting the layout.
+    /// While it's enabled, Tab (or Shift+Tab) also focuses the first (or last) widget
+    /// of the focus chain when no widget is focused.
+    pub fn set_focus_ring(&mut self, enabled: bool) {
+        let ctx = &mut self.main_root_widget.ctx;
+        if ctx.global_state.focus_ring == enabled {
+            return;
+        }
+        ctx.global_state.focus_ring = enabled;
+        // Only the focused widget needs a repaint, but we can't reach it from here
+        ctx.request_layout();
+        ctx.request_paint();
+    }
+
+    /// Let Enter in a text box move the focus to the next widget of the current window, as Tab does,
+    /// unless [`AppDriver::on_action_handled`] handled the [`Action::TextEntered`].
+    ///
+    /// This is disabled by default.
+    pub fn set_enter_moves_focus(&mut self, enabled: bool) {
+        self.main_root_widget.ctx.global_state.enter_moves_focus = enabled;
+    }
+
+    /// Schedule a call of [`AppDriver::on_rebuild`] before the next frame is painted,
+    /// e.g. when the app state was changed outside of the usual action handling.
+    ///
+    /// Multiple requests before the next frame result in a single rebuild.
+    pub fn request_rebuild(&mut self) {
+        *self.rebuild_requested = true;
+    }
+
+    /// Return the handle of the running app, e.g. to get a [`CommandSender`] for a background thread.
+    pub fn app_handle(&self) -> &AppHandle {
+        self.app_handle
+    }
+
+    /// Serialize the app `state` into a snapshot, to [restore](Self::restore_state) it later,
+    /// e.g. after the view logic was reloaded.
+    ///
+    /// The snapshot is JSON, so a development harness can keep it across restarts of the app.
+    #[cfg(feature = "state-snapshot")]
+    pub fn snapshot_state<S: serde::Serialize>(
+        &self,
+        state: &S,
+    ) -> Result<StateSnapshot, serde_json::Error> {
+        serde_json::to_string(state).map(StateSnapshot)
+    }
+
+    /// Deserialize the app state from `snapshot`, and request a rebuild for it.
+    ///
+    /// When the snapshot doesn't match `S` anymore, e.g. because a field was added since it was taken,
+    /// a warning is logged and the default state is returned.
+    #[cfg(feature = "state-snapshot")]
+    pub fn restore_state<S: serde::de::DeserializeOwned + Default>(
+        &mut self,
+        snapshot: &StateSnapshot,
+    ) -> S {
+        self.request_rebuild();
+        serde_json::from_str(&snapshot.0).unwrap_or_else(|err| {
+            tracing::warn!(
+                "Couldn't restore the app state from the snapshot, using the default: {err}"
+            );
+            S::default()
+        })
+    }
+
+    /// Return the text currently in the clipboard.
+    ///
+    /// Returns `Ok(None)` if the clipboard is empty or doesn't contain text.
+    pub fn get_clipboard_text(&mut self) -> Result<Option<String>, ClipboardError> {
+        self.clipboard.get_text()
+    }
+
+    /// Replace the contents of the clipboard with `text`.
+    pub fn set_clipboard_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.clipboard.set_text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_root::{RenderRoot, WindowSizePolicy};
+    use crate::testing::{widget_ids, ModularWidget};
+    use crate::widget::{Button, Flex, Label};
+    use crate::LifeCycle;
+
+    #[derive(Default)]
+    struct MockClipboard(Option<String>);
+
+    impl Clipboard for MockClipboard {
+        fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+            Ok(self.0.clone())
+        }
+
+        fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+            self.0 = Some(text.to_string());
+            Ok(())
+        }
+    }
+
+    struct UnsupportedClipboard;
+
+    impl Clipboard for UnsupportedClipboard {
+        fn get_text(&mut self) -> Result<Option<String>, ClipboardError> {
+            Err(ClipboardError::Unsupported)
+        }
+
+        fn set_text(&mut self, _text: &str) -> Result<(), ClipboardError> {
+            Err(ClipboardError::Unsupported)
+        }
+    }
+
+    fn single_window(root_widget: impl Widget) -> (WindowId, Windows) {
+        let window_id = WindowId::next();
+        let render_root = RenderRoot::new(root_widget, WindowSizePolicy::User, 1.0);
+        (window_id, Windows::new(window_id, render_root))
+    }
+
+    fn with_label_ctx<R>(
+        clipboard: &mut dyn Clipboard,
+        f: impl FnOnce(&mut DriverCtx<'_>) -> R,
+    ) -> R {
+        let (window_id, mut windows) = single_window(Label::new("Hello"));
+        let app_handle = AppHandle::new(|| {});
+        with_driver_ctx(
+            &mut windows,
+            clipboard,
+            &mut false,
+            &app_handle,
+            window_id,
+            f,
+        )
+        .unwrap()
+    }
+
+    #[derive(Default)]
+    struct ShortcutDriver {
+        saved: usize,
+    }
+
+    impl AppDriver for ShortcutDriver {
+        fn on_action(
+            &mut self,
+            _ctx: &mut DriverCtx<'_>,
+            _window_id: WindowId,
+            _widget_id: WidgetId,
+            _action: Action,
+        ) {
+        }
+
+        fn on_key(
+            &mut self,
+            _ctx: &mut DriverCtx<'_>,
+            key: &Key,
+            state: ElementState,
+            mods: ModifiersState,
+        ) -> bool {
+            if key == &Key::Character("s".into()) && mods.control_key() {
+                if state.is_pressed() {
+                    self.saved += 1;
+                }
+                return true;
+            }
+            false
+        }
+    }
+
+    struct NoShortcuts;
+
+    impl AppDriver for NoShortcuts {
+        fn on_action(
+            &mut self,
+            _ctx: &mut DriverCtx<'_>,
+            _window_id: WindowId,
+            _widget_id: WidgetId,
+            _action: Action,
+        ) {
+        }
+    }
+
+    #[test]
+    fn shortcut_is_consumed() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use winit::event::Ime;
+
+        fn focusable<S>(state: S) -> ModularWidget<S> {
+            ModularWidget::new(state).lifecycle_fn(|_, ctx, event| {
+                if let LifeCycle::BuildFocusChain = event {
+                    ctx.register_for_focus();
+                }
+            })
+        }
+
+        let received = Rc::new(RefCell::new(0));
+        let textbox =
+            focusable(received.clone()).text_event_fn(|received, _, _| *received.borrow_mut() += 1);
+        let [first, textbox_id] = widget_ids();
+        let widget = Flex::row()
+            .with_child_id(focusable(()), first)
+            .with_child_id(textbox, textbox_id);
+        let (window_id, mut windows) = single_window(widget);
+        let render_root = windows.roots.get_mut(&window_id).unwrap();
+        render_root.edit_root_widget(|_| {});
+        render_root.state.focused_widget = Some(first);
+        render_root.focus_next_widget();
+        assert_eq!(render_root.state.focused_widget, Some(textbox_id));
+
+        let mut driver = ShortcutDriver::default();
+        let mut clipboard = MockClipboard::default();
+        let app_handle = AppHandle::new(|| {});
+        let ctrl_s = Key::Character("s".into());
+        // winit's `KeyEvent` can't be created outside of winit,
+        // so the widget receives the typed text in place of the key.
+        let mut press = |windows: &mut Windows, mods| {
+            handle_key(
+                windows,
+                &mut driver,
+                &mut clipboard,
+                &mut false,
+                &app_handle,
+                window_id,
+                (&ctrl_s, ElementState::Pressed, mods),
+                TextEvent::Ime(Ime::Commit("s".into())),
+            );
+        };
+
+        press(&mut windows, ModifiersState::CONTROL);
+        assert_eq!(*received.borrow(), 0);
+        // without the modifier, the key goes to the focused widget
+        press(&mut windows, ModifiersState::empty());
+        assert_eq!(*received.borrow(), 1);
+        assert_eq!(driver.saved, 1);
+    }
+
+    #[test]
+    fn keys_are_not_consumed_by_default() {
+        with_label_ctx(&mut MockClipboard::default(), |ctx| {
+            let key = Key::Character("s".into());
+            let consumed =
+                NoShortcuts.on_key(ctx, &key, ElementState::Pressed, ModifiersState::CONTROL);
+            assert!(!consumed);
+        });
+    }
+
+    #[derive(Default)]
+    struct RebuildDriver {
+        rebuilds: usize,
+    }
+
+    impl AppDriver for RebuildDriver {
+        fn on_action(
+            &mut self,
+            ctx: &mut DriverCtx<'_>,
+            _window_id: WindowId,
+            _widget_id: WidgetId,
+            _action: Action,
+        ) {
+            ctx.request_rebuild();
+        }
+
+        fn on_rebuild(&mut self, _ctx: &mut DriverCtx<'_>) {
+            self.rebuilds += 1;
+        }
+    }
+
+    #[test]
+    fn requested_rebuilds_are_coalesced() {
+        let (window_id, mut windows) = single_window(Label::new("Hello"));
+        let mut clipboard = MockClipboard::default();
+        let mut driver = RebuildDriver::default();
+        let mut rebuild_requested = false;
+        let app_handle = AppHandle::new(|| {});
+
+        rebuild_if_requested(
+            &mut windows,
+            &mut driver,
+            &mut clipboard,
+            &mut rebuild_requested,
+            &app_handle,
+        );
+        assert_eq!(driver.rebuilds, 0);
+
+        for _ in 0..2 {
+            handle_action(
+                &mut windows,
+                &mut driver,
+                &mut clipboard,
+                &mut rebuild_requested,
+                &app_handle,
+                window_id,
+                WidgetId::next(),
+                Action::ButtonPressed,
+            );
+        }
+        for _frame in 0..2 {
+            rebuild_if_requested(
+                &mut windows,
+                &mut driver,
+                &mut clipboard,
+                &mut rebuild_requested,
+                &app_handle,
+            );
+        }
+        assert_eq!(driver.rebuilds, 1);
+    }
+
+    #[derive(Default)]
+    struct CommandDriver {
+        results: Vec<String>,
+    }
+
+    impl AppDriver for CommandDriver {
+        fn on_action(
+            &mut self,
+            _ctx: &mut DriverCtx<'_>,
+            _window_id: WindowId,
+            _widget_id: WidgetId,
+            _action: Action,
+        ) {
+        }
+
+        fn on_command(&mut self, ctx: &mut DriverCtx<'_>, command: Command) {
+            if let Ok(result) = command.downcast::<String>() {
+                self.results.push(*result);
+                ctx.request_rebuild();
+            }
+        }
+    }
+
+    #[test]
+    fn commands_from_other_threads_are_delivered() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let (_, mut windows) = single_window(Label::new("Hello"));
+        let mut clipboard = MockClipboard::default();
+        let mut driver = CommandDriver::default();
+        let mut rebuild_requested = false;
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let app_handle = AppHandle::new({
+            let wakes = wakes.clone();
+            move || {
+                wakes.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let sender = app_handle.sender();
+        std::thread::spawn(move || {
+            sender.send("first".to_string());
+            sender.send("second".to_string());
+        })
+        .join()
+        .unwrap();
+        // the event loop is woken up once for both commands
+        assert_eq!(wakes.load(Ordering::SeqCst), 1);
+
+        deliver_commands(
+            &mut windows,
+            &mut driver,
+            &mut clipboard,
+            &mut rebuild_requested,
+            &app_handle,
+        );
+        assert_eq!(driver.results, ["first", "second"]);
+        assert!(rebuild_requested);
+
+        // once the app is gone, sending does nothing
+        let sender = app_handle.sender();
+        drop(app_handle);
+        std::thread::spawn(move || sender.send("late".to_string()))
+            .join()
+            .unwrap();
+        assert_eq!(wakes.load(Ordering::SeqCst), 1);
+    }
+
+    struct EnterDriver(Handled);
+
+    impl AppDriver for EnterDriver {
+        fn on_action_handled(
+            &mut self,
+            _ctx: &mut DriverCtx<'_>,
+            _window_id: WindowId,
+            _widget_id: WidgetId,
+            _action: Action,
+        ) -> Handled {
+            self.0
+        }
+    }
+
+    #[test]
+    fn handled_action_suppresses_focus_follow_up() {
+        fn focus_taker() -> impl Widget {
+            ModularWidget::new(()).lifecycle_fn(|_, ctx, event| {
+                if let LifeCycle::BuildFocusChain = event {
+                    ctx.register_for_focus();
+                }
+            })
+        }
+
+        let [first, second] = widget_ids();
+        let widget = Flex::row()
+            .with_child_id(focus_taker(), first)
+            .with_child_id(focus_taker(), second);
+        let (window_id, mut windows) = single_window(widget);
+        let mut clipboard = MockClipboard::default();
+        let app_handle = AppHandle::new(|| {});
+        let focused = |windows: &mut Windows| {
+            windows
+                .roots
+                .get_mut(&window_id)
+                .unwrap()
+                .state
+                .focused_widget
+        };
+        let render_root = windows.roots.get_mut(&window_id).unwrap();
+        render_root.edit_root_widget(|_| {});
+        render_root.state.focused_widget = Some(first);
+
+        let mut enter = |windows: &mut Windows, handled| {
+            handle_action(
+                windows,
+                &mut EnterDriver(handled),
+                &mut clipboard,
+                &mut false,
+                &app_handle,
+                window_id,
+                first,
+                Action::TextEntered("text".into()),
+            );
+        };
+        // Without opting in, Enter never moves the focus
+        enter(&mut windows, Handled::No);
+        assert_eq!(focused(&mut windows), Some(first));
+
+        windows
+            .roots
+            .get_mut(&window_id)
+            .unwrap()
+            .state
+            .enter_moves_focus = true;
+        enter(&mut windows, Handled::Yes);
+        assert_eq!(focused(&mut windows), Some(first));
+        enter(&mut windows, Handled::No);
+        assert_eq!(focused(&mut windows), Some(second));
+    }
+
+    #[test]
+    fn topmost_widget_at_point() {
+        use crate::testing::{widget_ids, ModularWidget};
+        use crate::widget::SizedBox;
+        use crate::{Size, WidgetPod};
+
+        let [background, foreground] = widget_ids();
+        let boxed =
+            |id, size| WidgetPod::new_with_id(SizedBox::empty().width(size).height(size), id);
+        // Both children are placed at the origin, the second one is painted on top
+        let stack = ModularWidget::new((boxed(background, 100.), boxed(foreground, 50.)))
+            .lifecycle_fn(|(first, second), ctx, event| {
+                first.lifecycle(ctx, event);
+                second.lifecycle(ctx, event);
+            })
+            .layout_fn(|(first, second), ctx, bc| {
+                first.layout(ctx, bc);
+                second.layout(ctx, bc);
+                ctx.place_child(first, Point::ZERO);
+                ctx.place_child(second, Point::ZERO);
+                Size::new(100., 100.)
+            })
+            .children_fn(|(first, second)| smallvec::smallvec![first.as_dyn(), second.as_dyn()]);
+        let window_id = WindowId::next();
+        let render_root = RenderRoot::new(stack, WindowSizePolicy::Content, 1.0);
+        let mut windows = Windows::new(window_id, render_root);
+
+        let app_handle = AppHandle::new(|| {});
+        let mut clipboard = MockClipboard::default();
+        with_driver_ctx(
+            &mut windows,
+            &mut clipboard,
+            &mut false,
+            &app_handle,
+            window_id,
+            |ctx| {
+                assert_eq!(ctx.widget_at(Point::new(25., 25.)), Some(foreground));
+                assert_eq!(ctx.widget_at(Point::new(75., 75.)), Some(background));
+                assert_eq!(ctx.widget_at(Point::new(150., 150.)), None);
+            },
+        );
+    }
+
+    #[test]
+    fn scroll_to_widget_below_the_fold() {
+        use crate::event::WindowEvent;
+        use crate::widget::{Portal, SizedBox};
+        use winit::dpi::PhysicalSize;
+
+        let [first, last] = widget_ids();
+        let item = || SizedBox::empty().width(100.).height(50.);
+        let mut column = Flex::column().with_child_id(item(), first);
+        for _ in 0..8 {
+            column = column.with_child(item());
+        }
+        let column = column.with_child_id(item(), last);
+        let (window_id, mut windows) = single_window(Portal::new(column));
+        let render_root = windows.roots.get_mut(&window_id).unwrap();
+        render_root.handle_window_event(WindowEvent::Resize(PhysicalSize::new(100, 100)));
+        render_root.root_layout();
+
+        let app_handle = AppHandle::new(|| {});
+        let mut scroll_to = |widget_id, align| {
+            with_driver_ctx(
+                &mut windows,
+                &mut MockClipboard::default(),
+                &mut false,
+                &app_handle,
+                window_id,
+                |ctx| ctx.scroll_to(widget_id, align),
+            );
+            let render_root = windows.roots.get_mut(&window_id).unwrap();
+            render_root.edit_root_widget(|mut root| {
+                root.downcast::<Portal<Flex>>().widget.get_viewport_pos()
+            })
+        };
+        // The last item ends at 500, just enough is scrolled to show it
+        assert_eq!(scroll_to(last, ScrollAlign::Nearest), Point::new(0., 400.));
+        assert_eq!(scroll_to(first, ScrollAlign::Nearest), Point::ZERO);
+        // The portal can't be scrolled past its content
+        assert_eq!(scroll_to(last, ScrollAlign::Start), Point::new(0., 400.));
+    }
+
+    /// Opens a second window on the first action, and records the window of each action.
+    #[derive(Default)]
+    struct MultiWindowDriver {
+        actions: Vec<WindowId>,
+        second_window: Option<WindowId>,
+    }
+
+    impl AppDriver for MultiWindowDriver {
+        fn on_action(
+            &mut self,
+            ctx: &mut DriverCtx<'_>,
+            window_id: WindowId,
+            _widget_id: WidgetId,
+            _action: Action,
+        ) {
+            assert_eq!(ctx.window_id(), window_id);
+            self.actions.push(window_id);
+            match self.second_window {
+                None => {
+                    let second_window =
+                        ctx.open_window(WindowAttributes::default(), Button::new("Click"));
+                    self.second_window = Some(second_window);
+                }
+                Some(second_window) => {
+                    assert_eq!(window_id, second_window);
+                    assert!(ctx.try_get_root::<Button>().is_ok());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn action_is_routed_to_second_window() {
+        let (first_window, mut windows) = single_window(Label::new("Hello"));
+        let mut driver = MultiWindowDriver::default();
+        let app_handle = AppHandle::new(|| {});
+        let widget_id = WidgetId::next();
+        let handle_action = |windows: &mut Windows, driver: &mut MultiWindowDriver, window_id| {
+            handle_action(
+                windows,
+                driver,
+                &mut MockClipboard::default(),
+                &mut false,
+                &app_handle,
+                window_id,
+                widget_id,
+                Action::ButtonPressed,
+            );
+        };
+
+        handle_action(&mut windows, &mut driver, first_window);
+        let second_window = driver.second_window.unwrap();
+        assert!(matches!(
+            windows.requests[..],
+            [WindowRequest::Open(id, _)] if id == second_window
+        ));
+        assert_eq!(windows.main_window(), Some(first_window));
+
+        handle_action(&mut windows, &mut driver, second_window);
+        assert_eq!(driver.actions, [first_window, second_window]);
+
+        // The first window can be edited from the second one
+        with_driver_ctx(
+            &mut windows,
+            &mut MockClipboard::default(),
+            &mut false,
+            &app_handle,
+            second_window,
+            |ctx| {
+                ctx.edit_window_root(first_window, |mut root| {
+                    root.downcast::<Label>().set_text("Renamed");
+                })
+                .unwrap();
+                ctx.close_window(second_window);
+            },
+        )
+        .unwrap();
+        let label_text = windows
+            .roots
+            .get_mut(&first_window)
+            .unwrap()
+            .edit_root_widget(|mut root| root.downcast::<Label>().widget.text().to_string());
+        assert_eq!(label_text, "Renamed");
+
+        // e.g. an action that was queued before the window was closed
+        handle_action(&mut windows, &mut driver, second_window);
+        assert_eq!(driver.actions, [first_window, second_window]);
+        assert_eq!(windows.main_window(), Some(first_window));
+    }
+
+    #[test]
+    fn debug_layout_is_only_painted_when_enabled() {
+        let [label] = widget_ids();
+        let root = Flex::column().with_child_id(Label::new("Hello"), label);
+        let window_id = WindowId::next();
+        let render_root = RenderRoot::new(root, WindowSizePolicy::Content, 1.0);
+        let mut windows = Windows::new(window_id, render_root);
+        let app_handle = AppHandle::new(|| {});
+        let with_ctx = |windows: &mut Windows, f: &dyn Fn(&mut DriverCtx<'_>) -> _| {
+            with_driver_ctx(
+                windows,
+                &mut MockClipboard::default(),
+                &mut false,
+                &app_handle,
+                window_id,
+                f,
+            )
+            .unwrap()
+        };
+        let mut paint = |enabled| {
+            with_ctx(&mut windows, &|ctx| {
+                ctx.set_debug_layout(enabled);
+                None
+            });
+            let (scene, _) = windows.roots.get_mut(&window_id).unwrap().redraw();
+            let hit = with_ctx(&mut windows, &|ctx| ctx.widget_at(Point::new(1., 1.)));
+            (scene.encoding().n_paths, hit)
+        };
+
+        let (plain_paths, plain_hit) = paint(false);
+        let (debug_paths, debug_hit) = paint(true);
+        // The bounds of the flex and the label, and their ids
+        assert!(debug_paths > plain_paths);
+        assert_eq!(debug_hit, plain_hit);
+        assert_eq!(plain_hit, Some(label));
+        assert_eq!(paint(false), (plain_paths, plain_hit));
+    }
+
+    #[test]
+    fn root_of_correct_type() {
+        with_label_ctx(&mut MockClipboard::default(), |ctx| {
+            assert!(ctx.try_get_root::<Label>().is_ok());
+        });
+    }
+
+    #[test]
+    fn root_of_wrong_type_names_both_types() {
+        with_label_ctx(&mut MockClipboard::default(), |ctx| {
+            let Err(err) = ctx.try_get_root::<Button>() else {
+                panic!("the root widget is a `Label`");
+            };
+            assert!(err.expected.ends_with("Button"), "{}", err.expected);
+            assert!(err.actual.ends_with("Label"), "{}", err.actual);
+            let message = err.to_string();
+            assert!(message.contains(err.expected) && message.contains(err.actual));
+        });
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum CustomAction {
+        Clicked(u32),
+        Painted,
+    }
+
+    #[derive(Default)]
+    struct CustomActionDriver {
+        actions: Vec<(WidgetId, CustomAction)>,
+    }
+
+    impl AppDriver for CustomActionDriver {
+        fn on_action(
+            &mut self,
+            _ctx: &mut DriverCtx<'_>,
+            _window_id: WindowId,
+            widget_id: WidgetId,
+            action: Action,
+        ) {
+            let action = *action.downcast_other::<CustomAction>().unwrap();
+            self.actions.push((widget_id, action));
+        }
+    }
+
+    #[test]
+    fn custom_widget_action_reaches_driver() {
+        use winit::dpi::LogicalPosition;
+        use winit::event::MouseButton;
+
+        use crate::event::{PointerEvent, PointerState};
+        use crate::render_root::RenderRootSignal;
+
+        let [clickable] = widget_ids();
+        let widget = ModularWidget::new(3)
+            .pointer_event_fn(|clicks, ctx, event| {
+                if let PointerEvent::PointerDown(_, _) = event {
+                    ctx.submit_other_action(CustomAction::Clicked(*clicks));
+                }
+            })
+            .paint_fn(|_, ctx, _| ctx.submit_other_action(CustomAction::Painted));
+        let root = Flex::column().with_child_id(widget, clickable);
+        let window_id = WindowId::next();
+        let mut render_root = RenderRoot::new(root, WindowSizePolicy::Content, 1.0);
+
+        render_root.redraw();
+        let mut pointer = PointerState::empty();
+        pointer.position = LogicalPosition::new(10., 10.);
+        render_root.handle_pointer_event(PointerEvent::PointerMove(pointer.clone()));
+        render_root.handle_pointer_event(PointerEvent::PointerDown(MouseButton::Left, pointer));
+
+        let mut windows = Windows::new(window_id, render_root);
+        let mut driver = CustomActionDriver::default();
+        let app_handle = AppHandle::new(|| {});
+        while let Some(signal) = windows.roots.get_mut(&window_id).unwrap().pop_signal() {
+            if let RenderRootSignal::Action(action, widget_id) = signal {
+                handle_action(
+                    &mut windows,
+                    &mut driver,
+                    &mut MockClipboard::default(),
+                    &mut false,
+                    &app_handle,
+                    window_id,
+                    widget_id,
+                    action,
+                );
+            }
+        }
+        // The action submitted during paint is delivered after the frame, in order
+        assert_eq!(
+            driver.actions,
+            [
+                (clickable, CustomAction::Painted),
+                (clickable, CustomAction::Clicked(3)),
+            ]
+        );
+    }
+
+    #[cfg(feature = "state-snapshot")]
+    #[test]
+    fn state_is_restored_from_snapshot() {
+        #[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Counter {
+            count: u32,
+            label: String,
+        }
+
+        #[derive(Debug, Default, PartialEq, serde::Deserialize)]
+        struct RenamedCounter {
+            clicks: u32,
+        }
+
+        let (window_id, mut windows) = single_window(Label::new("Hello"));
+        let app_handle = AppHandle::new(|| {});
+        let mut rebuild_requested = false;
+        let (counter, renamed) = with_driver_ctx(
+            &mut windows,
+            &mut MockClipboard::default(),
+            &mut rebuild_requested,
+            &app_handle,
+            window_id,
+            |ctx| {
+                let mut counter = Counter {
+                    count: 3,
+                    label: "Clicks".into(),
+                };
+                let snapshot = ctx.snapshot_state(&counter).unwrap();
+                counter.count += 1;
+                // The snapshot survives e.g. a restart of the app as JSON
+                let snapshot = StateSnapshot::from_json(snapshot.as_json().to_string());
+                let counter: Counter = ctx.restore_state(&snapshot);
+                // A schema change falls back to the default
+                let renamed: RenamedCounter = ctx.restore_state(&snapshot);
+                (counter, renamed)
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            counter,
+            Counter {
+                count: 3,
+                label: "Clicks".into()
+            }
+        );
+        assert_eq!(renamed, RenamedCounter::default());
+        assert!(rebuild_requested);
+    }
+
+    #[test]
+    fn clipboard_round_trip() {
+        let mut clipboard = MockClipboard::default();
+        with_label_ctx(&mut clipboard, |ctx| {
+            assert_eq!(ctx.get_clipboard_text(), Ok(None));
+            ctx.set_clipboard_text("copied text").unwrap();
+            assert_eq!(ctx.get_clipboard_text(), Ok(Some("copied text".into())));
+        });
+        assert_eq!(clipboard.0.as_deref(), Some("copied text"));
+    }
+
+    #[test]
+    fn unsupported_clipboard_returns_error() {
+        with_label_ctx(&mut UnsupportedClipboard, |ctx| {
+            assert_eq!(ctx.get_clipboard_text(), Err(ClipboardError::Unsupported));
+            assert_eq!(
+                ctx.set_clipboard_text("text"),
+                Err(ClipboardError::Unsupported)
+            );
+        });
+    }
 }
@@ -4,17 +4,20 @@
 //! The context types that are passed into various widget methods.
 
 use std::any::Any;
+use std::sync::Arc;
 use std::time::Duration;
 
 use accesskit::{NodeBuilder, TreeUpdate};
 use parley::FontContext;
 use tracing::{trace, warn};
+use vello::peniko::Color;
 use winit::dpi::LogicalPosition;
 use winit::window::CursorIcon;
 
 use crate::action::Action;
+use crate::event::DragData;
 use crate::promise::PromiseToken;
-use crate::render_root::{RenderRootSignal, RenderRootState};
+use crate::render_root::{DragState, RenderRootSignal, RenderRootState};
 use crate::text_helpers::{ImeChangeSignal, TextFieldRegistration};
 use crate::widget::{CursorChange, WidgetMut, WidgetState};
 use crate::{Insets, Point, Rect, Size, Widget, WidgetId, WidgetPod};
@@ -249,6 +252,21 @@ impl_context_method!(
         pub fn is_stashed(&self) -> bool {
             self.widget_state.is_stashed
         }
+
+        /// The data of the drag in progress, if any, see [`EventCtx::start_drag`].
+        pub fn drag_data(&self) -> Option<&DragData> {
+            self.global_state.drag.as_ref().map(|drag| &drag.data)
+        }
+
+        /// Whether the drag in progress would be dropped on this widget, e.g. to highlight it.
+        ///
+        /// See [`Widget::accepts_drop`].
+        pub fn is_drop_target(&self) -> bool {
+            self.global_state
+                .drag
+                .as_ref()
+                .is_some_and(|drag| drag.target == Some(self.widget_state.id))
+        }
     }
 );
 
@@ -294,6 +312,23 @@ impl_context_method!(EventCtx<'_>, {
         trace!("clear_cursor");
         self.widget_state.cursor_change = CursorChange::Default;
     }
+
+    /// Start dragging `payload` from this widget, e.g. on a pointer move while it's active.
+    ///
+    /// During the drag, the innermost hot widget whose [`Widget::accepts_drop`] returns `true`
+    /// is the drop target. When the pointer is released over it, an [`Action::Dropped`] with the
+    /// id of the target is submitted, otherwise the drag ends without an action.
+    /// Pressing Escape cancels the drag. A drag that's already in progress is replaced.
+    pub fn start_drag(&mut self, payload: impl Any) {
+        trace!("start_drag");
+        self.global_state.drag = Some(DragState {
+            data: DragData {
+                source: self.widget_state.id,
+                payload: std::sync::Arc::new(payload),
+            },
+            target: None,
+        });
+    }
 });
 
 impl<'a> WidgetCtx<'a> {
@@ -432,14 +467,19 @@ impl_context_method!(WidgetCtx<'_>, EventCtx<'_>, LifeCycleCtx<'_>, {
     }
 });
 
-// methods on everyone but paintctx
+// methods on everyone but accessctx
 impl_context_method!(
     WidgetCtx<'_>,
     EventCtx<'_>,
     LifeCycleCtx<'_>,
     LayoutCtx<'_>,
+    PaintCtx<'_>,
     {
-        /// Submit an [`Action`].
+        /// Submit an [`Action`], which is passed to [`AppDriver::on_action`](crate::AppDriver::on_action)
+        /// with the id of this widget.
+        ///
+        /// Actions are queued, and only delivered after the current pass, so an action submitted
+        /// during layout or paint reaches the driver once the frame is done.
         ///
         /// Note: Actions are still a WIP feature.
         pub fn submit_action(&mut self, action: Action) {
@@ -449,6 +489,23 @@ impl_context_method!(
                 .push_back(RenderRootSignal::Action(action, self.widget_state.id));
         }
 
+        /// Submit a custom action of a widget type as [`Action::Other`],
+        /// see [`submit_action`](Self::submit_action).
+        ///
+        /// The driver gets the `payload` back with [`Action::downcast_other`].
+        pub fn submit_other_action<T: Any>(&mut self, payload: T) {
+            self.submit_action(Action::Other(Arc::new(payload)));
+        }
+    }
+);
+
+// methods on everyone but paintctx
+impl_context_method!(
+    WidgetCtx<'_>,
+    EventCtx<'_>,
+    LifeCycleCtx<'_>,
+    LayoutCtx<'_>,
+    {
         /// Run the provided function in the background.
         ///
         /// The function takes a [`WorkerCtx`] which it can use to
@@ -582,7 +639,7 @@ impl LifeCycleCtx<'_> {
     /// [`LifeCycle::BuildFocusChain`]: enum.Lifecycle.html#variant.BuildFocusChain
     pub fn register_for_focus(&mut self) {
         trace!("register_for_focus");
-        self.widget_state.focus_chain.push(self.widget_id());
+        self.widget_state.focus_chain.push((self.widget_id(), None));
     }
 
     /// Register this widget as accepting text input.
@@ -678,6 +735,22 @@ impl PaintCtx<'_> {
     pub fn depth(&self) -> u32 {
         self.depth
     }
+
+    /// The background color set with [`WidgetMut::set_background_color`].
+    ///
+    /// The background is painted before the widget's `paint` method is called,
+    /// so this is only needed by widgets that e.g. pick a contrasting color.
+    pub fn background_color(&self) -> Option<Color> {
+        self.widget_state.background_color
+    }
+
+    /// The text color set with [`WidgetMut::set_text_color`].
+    ///
+    /// Widgets that paint text should use it instead of their own text color
+    /// (e.g. [`theme::TEXT_COLOR`](crate::theme::TEXT_COLOR)) if it's set.
+    pub fn text_color(&self) -> Option<Color> {
+        self.widget_state.text_color
+    }
 }
 
 impl AccessCtx<'_> {
@@ -213,6 +213,7 @@ impl DebugLogger {
             StateTree::new("has_active", w_state.has_active),
             StateTree::new("has_focus", w_state.has_focus),
             StateTree::new("request_anim", w_state.request_anim),
+            StateTree::new("is_animating", w_state.is_animating),
             StateTree::new("children_changed", w_state.children_changed),
             StateTree::new(
                 "is_explicitly_disabled_new",
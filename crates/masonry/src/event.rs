@@ -4,9 +4,12 @@
 //! Events.
 
 use crate::kurbo::Rect;
+use crate::widget::ScrollAlign;
 // TODO - See issue #14
 use crate::WidgetId;
 
+use std::any::Any;
+use std::sync::Arc;
 use std::{collections::HashSet, path::PathBuf};
 
 use accesskit::{Action, ActionData};
@@ -30,7 +33,6 @@ pub enum WindowEvent {
     RebuildAccessTree,
 }
 
-// TODO - How can RenderRoot express "I started a drag-and-drop op"?
 // TODO - Touchpad, Touch, AxisMotion
 // TODO - How to handle CursorEntered?
 // Note to self: Events like "pointerenter", "pointerleave" are handled differently at the Widget level. But that's weird because WidgetPod can distribute them. Need to think about this again.
@@ -47,6 +49,13 @@ pub enum PointerEvent {
     HoverFileCancel(PointerState),
 }
 
+/// The data of a drag-and-drop operation within the window, see [`EventCtx::start_drag`](crate::EventCtx::start_drag).
+#[derive(Clone)]
+pub struct DragData {
+    pub(crate) source: WidgetId,
+    pub(crate) payload: Arc<dyn Any>,
+}
+
 // TODO - Clipboard Paste?
 // TODO skip is_synthetic=true events
 #[derive(Debug, Clone)]
@@ -184,6 +193,13 @@ pub enum InternalLifeCycle {
     ParentWindowOrigin {
         mouse_pos: Option<LogicalPosition<f64>>,
     },
+
+    /// Used to route a scroll request to the portals containing the `target` widget.
+    RouteScrollTo {
+        /// the widget to scroll to
+        target: WidgetId,
+        align: ScrollAlign,
+    },
 }
 
 /// Event indicating status changes within the widget hierarchy.
@@ -310,6 +326,34 @@ impl AccessEvent {
     }
 }
 
+impl DragData {
+    /// The widget that started the drag.
+    pub fn source(&self) -> WidgetId {
+        self.source
+    }
+
+    /// The payload passed to [`EventCtx::start_drag`](crate::EventCtx::start_drag), if it's a `T`.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+}
+
+impl PartialEq for DragData {
+    fn eq(&self, other: &Self) -> bool {
+        #[allow(ambiguous_wide_pointer_comparisons)]
+        let same_payload = Arc::ptr_eq(&self.payload, &other.payload);
+        self.source == other.source && same_payload
+    }
+}
+
+impl std::fmt::Debug for DragData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragData")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
 impl PointerState {
     pub fn empty() -> Self {
         #[cfg(FALSE)]
@@ -363,6 +407,7 @@ impl LifeCycle {
                 InternalLifeCycle::RouteFocusChanged { .. } => "RouteFocusChanged",
                 InternalLifeCycle::RouteDisabledChanged => "RouteDisabledChanged",
                 InternalLifeCycle::ParentWindowOrigin { .. } => "ParentWindowOrigin",
+                InternalLifeCycle::RouteScrollTo { .. } => "RouteScrollTo",
             },
             LifeCycle::WidgetAdded => "WidgetAdded",
             LifeCycle::AnimFrame(_) => "AnimFrame",
@@ -387,7 +432,8 @@ impl InternalLifeCycle {
             InternalLifeCycle::RouteWidgetAdded
             | InternalLifeCycle::RouteFocusChanged { .. }
             | InternalLifeCycle::RouteDisabledChanged => true,
-            InternalLifeCycle::ParentWindowOrigin { .. } => false,
+            InternalLifeCycle::ParentWindowOrigin { .. }
+            | InternalLifeCycle::RouteScrollTo { .. } => false,
         }
     }
 }
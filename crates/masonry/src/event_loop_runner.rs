@@ -1,8 +1,9 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use accesskit_winit::Adapter;
 use tracing::subscriber::SetGlobalDefaultError;
@@ -15,25 +16,51 @@ use winit::application::ApplicationHandler;
 use winit::dpi::LogicalPosition;
 use winit::error::EventLoopError;
 use winit::event::WindowEvent as WinitWindowEvent;
-use winit::event_loop::{ActiveEventLoop, EventLoop};
-use winit::window::{Window, WindowAttributes, WindowId};
+use winit::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
+use winit::window::{Window, WindowAttributes, WindowId as WinitWindowId};
 
-use crate::app_driver::{AppDriver, DriverCtx};
+use crate::app_driver::{
+    self, AppDriver, AppHandle, Clipboard, PlatformClipboard, WindowId, WindowRequest, Windows,
+};
 use crate::event::{PointerState, WindowEvent};
 use crate::render_root::{self, RenderRoot, WindowSizePolicy};
 use crate::{PointerEvent, TextEvent, Widget};
 
-struct MainState<'a> {
+/// The platform window of a [`RenderRoot`] in [`MainState::windows`].
+struct WindowState<'a> {
     window: Arc<Window>,
-    render_cx: RenderContext,
     surface: RenderSurface<'a>,
-    render_root: RenderRoot,
-    renderer: Option<Renderer>,
     pointer_state: PointerState,
-    app_driver: Box<dyn AppDriver>,
     accesskit_adapter: Adapter,
 }
 
+struct MainState<'a> {
+    render_cx: RenderContext,
+    renderer: Option<Renderer>,
+    windows: Windows,
+    window_states: HashMap<WindowId, WindowState<'a>>,
+    window_ids: HashMap<WinitWindowId, WindowId>,
+    app_driver: Box<dyn AppDriver>,
+    clipboard: Box<dyn Clipboard>,
+    rebuild_requested: bool,
+    app_handle: AppHandle,
+    event_loop_proxy: EventLoopProxy<MasonryUserEvent>,
+}
+
+/// The user events of the event loop run by Masonry.
+#[derive(Debug)]
+pub enum MasonryUserEvent {
+    AccessKit(accesskit_winit::Event),
+    /// Commands were sent with a [`CommandSender`](crate::app_driver::CommandSender).
+    Command,
+}
+
+impl From<accesskit_winit::Event> for MasonryUserEvent {
+    fn from(event: accesskit_winit::Event) -> Self {
+        MasonryUserEvent::AccessKit(event)
+    }
+}
+
 pub fn run(
     window_attributes: WindowAttributes,
     root_widget: impl Widget,
@@ -55,31 +82,36 @@ pub fn run(
 
 pub fn run_with(
     window: Window,
-    event_loop: EventLoop<accesskit_winit::Event>,
+    event_loop: EventLoop<MasonryUserEvent>,
     accesskit_adapter: Adapter,
     root_widget: impl Widget,
     app_driver: impl AppDriver + 'static,
 ) -> Result<(), EventLoopError> {
     let window = Arc::new(window);
     let mut render_cx = RenderContext::new().unwrap();
-    let size = window.inner_size();
-    let surface = pollster::block_on(render_cx.create_surface(
-        window.clone(),
-        size.width,
-        size.height,
-        PresentMode::AutoVsync,
-    ))
-    .unwrap();
     let scale_factor = window.scale_factor();
+    let window_state = create_window_state(&mut render_cx, window, accesskit_adapter);
+    let command_proxy = Mutex::new(event_loop.create_proxy());
+    let app_handle = AppHandle::new(move || {
+        // Sending fails when the event loop has exited, the commands are dropped with the app then
+        let _ = command_proxy
+            .lock()
+            .unwrap()
+            .send_event(MasonryUserEvent::Command);
+    });
+    let window_id = WindowId::next();
+    let render_root = RenderRoot::new(root_widget, WindowSizePolicy::User, scale_factor);
     let mut main_state = MainState {
-        window,
         render_cx,
-        surface,
-        render_root: RenderRoot::new(root_widget, WindowSizePolicy::User, scale_factor),
         renderer: None,
-        pointer_state: PointerState::empty(),
+        windows: Windows::new(window_id, render_root),
+        window_ids: HashMap::from([(window_state.window.id(), window_id)]),
+        window_states: HashMap::from([(window_id, window_state)]),
         app_driver: Box::new(app_driver),
-        accesskit_adapter,
+        clipboard: Box::new(PlatformClipboard::new()),
+        rebuild_requested: false,
+        app_handle,
+        event_loop_proxy: event_loop.create_proxy(),
     };
 
     // If there is no default tracing subscriber, we set our own. If one has
@@ -91,71 +123,131 @@ pub fn run_with(
     event_loop.run_app(&mut main_state)
 }
 
-impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
+fn create_window_state(
+    render_cx: &mut RenderContext,
+    window: Arc<Window>,
+    accesskit_adapter: Adapter,
+) -> WindowState<'static> {
+    let size = window.inner_size();
+    let surface = pollster::block_on(render_cx.create_surface(
+        window.clone(),
+        size.width,
+        size.height,
+        PresentMode::AutoVsync,
+    ))
+    .unwrap();
+    WindowState {
+        window,
+        surface,
+        pointer_state: PointerState::empty(),
+        accesskit_adapter,
+    }
+}
+
+impl ApplicationHandler<MasonryUserEvent> for MainState<'_> {
     fn resumed(&mut self, _event_loop: &ActiveEventLoop) {
         // FIXME: initialize window in this handler because initializing it before running the event loop is deprecated
     }
 
-    fn window_event(&mut self, event_loop: &ActiveEventLoop, _: WindowId, event: WinitWindowEvent) {
-        self.accesskit_adapter.process_event(&self.window, &event);
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        winit_window_id: WinitWindowId,
+        event: WinitWindowEvent,
+    ) {
+        let Some(&window_id) = self.window_ids.get(&winit_window_id) else {
+            return;
+        };
+        let (Some(window_state), Some(render_root)) = (
+            self.window_states.get_mut(&window_id),
+            self.windows.roots.get_mut(&window_id),
+        ) else {
+            return;
+        };
+        window_state
+            .accesskit_adapter
+            .process_event(&window_state.window, &event);
 
         match event {
             WinitWindowEvent::RedrawRequested => {
-                let (scene, tree_update) = self.render_root.redraw();
-                self.render(scene);
-                self.accesskit_adapter.update_if_active(|| tree_update);
+                // Animations are moved forward once per frame, before it's painted
+                render_root.handle_window_event(WindowEvent::AnimFrame);
+                app_driver::rebuild_if_requested(
+                    &mut self.windows,
+                    &mut *self.app_driver,
+                    &mut *self.clipboard,
+                    &mut self.rebuild_requested,
+                    &self.app_handle,
+                );
+                // The rebuild may have closed the window
+                if let Some(render_root) = self.windows.roots.get_mut(&window_id) {
+                    let (scene, tree_update) = render_root.redraw();
+                    self.render(window_id, scene);
+                    if let Some(window_state) = self.window_states.get_mut(&window_id) {
+                        window_state
+                            .accesskit_adapter
+                            .update_if_active(|| tree_update);
+                    }
+                }
+            }
+            WinitWindowEvent::CloseRequested => {
+                self.windows.roots.remove(&window_id);
+                self.windows.requests.push(WindowRequest::Close(window_id));
             }
-            WinitWindowEvent::CloseRequested => event_loop.exit(),
             WinitWindowEvent::Resized(size) => {
-                self.render_root
-                    .handle_window_event(WindowEvent::Resize(size));
+                render_root.handle_window_event(WindowEvent::Resize(size));
             }
             WinitWindowEvent::ModifiersChanged(modifiers) => {
-                self.pointer_state.mods = modifiers;
-                self.render_root
-                    .handle_text_event(TextEvent::ModifierChange(modifiers.state()));
+                window_state.pointer_state.mods = modifiers;
+                render_root.handle_text_event(TextEvent::ModifierChange(modifiers.state()));
             }
             WinitWindowEvent::KeyboardInput {
                 device_id: _,
                 event,
                 is_synthetic: _,
             } => {
-                self.render_root.handle_text_event(TextEvent::KeyboardKey(
-                    event,
-                    self.pointer_state.mods.state(),
-                ));
+                let mods = window_state.pointer_state.mods.state();
+                let key = event.logical_key.clone();
+                app_driver::handle_key(
+                    &mut self.windows,
+                    &mut *self.app_driver,
+                    &mut *self.clipboard,
+                    &mut self.rebuild_requested,
+                    &self.app_handle,
+                    window_id,
+                    (&key, event.state, mods),
+                    TextEvent::KeyboardKey(event, mods),
+                );
             }
             WinitWindowEvent::Ime(ime) => {
-                self.render_root.handle_text_event(TextEvent::Ime(ime));
+                render_root.handle_text_event(TextEvent::Ime(ime));
             }
             WinitWindowEvent::Focused(new_focus) => {
-                self.render_root
-                    .handle_text_event(TextEvent::FocusChange(new_focus));
+                render_root.handle_text_event(TextEvent::FocusChange(new_focus));
             }
             WinitWindowEvent::CursorMoved { position, .. } => {
-                self.pointer_state.physical_position = position;
-                self.pointer_state.position = position.to_logical(self.window.scale_factor());
-                self.render_root
-                    .handle_pointer_event(PointerEvent::PointerMove(self.pointer_state.clone()));
+                let pointer_state = &mut window_state.pointer_state;
+                pointer_state.physical_position = position;
+                pointer_state.position = position.to_logical(window_state.window.scale_factor());
+                render_root.handle_pointer_event(PointerEvent::PointerMove(pointer_state.clone()));
             }
             WinitWindowEvent::CursorLeft { .. } => {
-                self.render_root
-                    .handle_pointer_event(PointerEvent::PointerLeave(self.pointer_state.clone()));
+                render_root.handle_pointer_event(PointerEvent::PointerLeave(
+                    window_state.pointer_state.clone(),
+                ));
             }
             WinitWindowEvent::MouseInput { state, button, .. } => match state {
                 winit::event::ElementState::Pressed => {
-                    self.render_root
-                        .handle_pointer_event(PointerEvent::PointerDown(
-                            button,
-                            self.pointer_state.clone(),
-                        ));
+                    render_root.handle_pointer_event(PointerEvent::PointerDown(
+                        button,
+                        window_state.pointer_state.clone(),
+                    ));
                 }
                 winit::event::ElementState::Released => {
-                    self.render_root
-                        .handle_pointer_event(PointerEvent::PointerUp(
-                            button,
-                            self.pointer_state.clone(),
-                        ));
+                    render_root.handle_pointer_event(PointerEvent::PointerUp(
+                        button,
+                        window_state.pointer_state.clone(),
+                    ));
                 }
             },
             WinitWindowEvent::MouseWheel { delta, .. } => {
@@ -164,14 +256,13 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
                         LogicalPosition::new(x as f64, y as f64)
                     }
                     winit::event::MouseScrollDelta::PixelDelta(delta) => {
-                        delta.to_logical(self.window.scale_factor())
+                        delta.to_logical(window_state.window.scale_factor())
                     }
                 };
-                self.render_root
-                    .handle_pointer_event(PointerEvent::MouseWheel(
-                        delta,
-                        self.pointer_state.clone(),
-                    ));
+                render_root.handle_pointer_event(PointerEvent::MouseWheel(
+                    delta,
+                    window_state.pointer_state.clone(),
+                ));
             }
             _ => (),
         }
@@ -179,18 +270,37 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
         self.handle_signals(event_loop);
     }
 
-    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: accesskit_winit::Event) {
-        match event.window_event {
-            // Note that this event can be called at any time, even multiple times if
-            // the user restarts their screen reader.
-            accesskit_winit::WindowEvent::InitialTreeRequested => {
-                self.render_root
-                    .handle_window_event(WindowEvent::RebuildAccessTree);
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: MasonryUserEvent) {
+        match event {
+            MasonryUserEvent::AccessKit(event) => {
+                let render_root = self
+                    .window_ids
+                    .get(&event.window_id)
+                    .and_then(|window_id| self.windows.roots.get_mut(window_id));
+                let Some(render_root) = render_root else {
+                    return;
+                };
+                match event.window_event {
+                    // Note that this event can be called at any time, even multiple times if
+                    // the user restarts their screen reader.
+                    accesskit_winit::WindowEvent::InitialTreeRequested => {
+                        render_root.handle_window_event(WindowEvent::RebuildAccessTree);
+                    }
+                    accesskit_winit::WindowEvent::ActionRequested(action_request) => {
+                        render_root.root_on_access_event(action_request);
+                    }
+                    accesskit_winit::WindowEvent::AccessibilityDeactivated => {}
+                }
             }
-            accesskit_winit::WindowEvent::ActionRequested(action_request) => {
-                self.render_root.root_on_access_event(action_request);
+            MasonryUserEvent::Command => {
+                app_driver::deliver_commands(
+                    &mut self.windows,
+                    &mut *self.app_driver,
+                    &mut *self.clipboard,
+                    &mut self.rebuild_requested,
+                    &self.app_handle,
+                );
             }
-            accesskit_winit::WindowEvent::AccessibilityDeactivated => {}
         }
 
         self.handle_signals(event_loop);
@@ -198,15 +308,18 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
 }
 
 impl MainState<'_> {
-    fn render(&mut self, scene: Scene) {
-        let scale = self.window.scale_factor();
-        let size = self.window.inner_size();
+    fn render(&mut self, window_id: WindowId, scene: Scene) {
+        let Some(window_state) = self.window_states.get_mut(&window_id) else {
+            return;
+        };
+        let scale = window_state.window.scale_factor();
+        let size = window_state.window.inner_size();
         let width = size.width;
         let height = size.height;
+        let surface = &mut window_state.surface;
 
-        if self.surface.config.width != width || self.surface.config.height != height {
-            self.render_cx
-                .resize_surface(&mut self.surface, width, height);
+        if surface.config.width != width || surface.config.height != height {
+            self.render_cx.resize_surface(surface, width, height);
         }
 
         let transformed_scene = if scale == 1.0 {
@@ -218,15 +331,15 @@ impl MainState<'_> {
         };
         let scene_ref = transformed_scene.as_ref().unwrap_or(&scene);
 
-        let Ok(surface_texture) = self.surface.surface.get_current_texture() else {
+        let Ok(surface_texture) = surface.surface.get_current_texture() else {
             warn!("failed to acquire next swapchain texture");
             return;
         };
-        let dev_id = self.surface.dev_id;
+        let dev_id = surface.dev_id;
         let device = &self.render_cx.devices[dev_id].device;
         let queue = &self.render_cx.devices[dev_id].queue;
         let renderer_options = RendererOptions {
-            surface_format: Some(self.surface.format),
+            surface_format: Some(surface.format),
             use_cpu: false,
             antialiasing_support: AaSupport {
                 area: true,
@@ -249,19 +362,50 @@ impl MainState<'_> {
         device.poll(wgpu::Maintain::Wait);
     }
 
-    fn handle_signals(&mut self, _event_loop: &ActiveEventLoop) {
-        while let Some(signal) = self.render_root.pop_signal() {
+    fn handle_signals(&mut self, event_loop: &ActiveEventLoop) {
+        let window_ids: Vec<_> = self.windows.roots.keys().copied().collect();
+        for window_id in window_ids {
+            self.handle_window_signals(window_id);
+        }
+        self.handle_window_requests(event_loop);
+        // The rebuild itself happens right before the next frame is painted
+        if self.rebuild_requested {
+            let main_window = self.windows.main_window();
+            if let Some(window_state) = main_window.and_then(|id| self.window_states.get(&id)) {
+                window_state.window.request_redraw();
+            }
+        }
+    }
+
+    fn handle_window_signals(&mut self, window_id: WindowId) {
+        // The window can be closed by an action
+        while let Some(signal) = self
+            .windows
+            .roots
+            .get_mut(&window_id)
+            .and_then(|render_root| render_root.pop_signal())
+        {
+            if let render_root::RenderRootSignal::Action(action, widget_id) = signal {
+                debug!("Action {:?} on widget {:?}", action, widget_id);
+                app_driver::handle_action(
+                    &mut self.windows,
+                    &mut *self.app_driver,
+                    &mut *self.clipboard,
+                    &mut self.rebuild_requested,
+                    &self.app_handle,
+                    window_id,
+                    widget_id,
+                    action,
+                );
+                continue;
+            }
+            let Some(window_state) = self.window_states.get(&window_id) else {
+                // The platform window isn't created yet, it's requested to redraw anyway
+                continue;
+            };
+            let window = &window_state.window;
             match signal {
-                render_root::RenderRootSignal::Action(action, widget_id) => {
-                    self.render_root.edit_root_widget(|root| {
-                        debug!("Action {:?} on widget {:?}", action, widget_id);
-                        let mut driver_ctx = DriverCtx {
-                            main_root_widget: root,
-                        };
-                        self.app_driver
-                            .on_action(&mut driver_ctx, widget_id, action);
-                    });
-                }
+                render_root::RenderRootSignal::Action(..) => unreachable!(),
                 render_root::RenderRootSignal::TextFieldAdded => {
                     // TODO
                 }
@@ -281,30 +425,73 @@ impl MainState<'_> {
                     // TODO
                 }
                 render_root::RenderRootSignal::RequestRedraw => {
-                    self.window.request_redraw();
+                    window.request_redraw();
                 }
                 render_root::RenderRootSignal::RequestAnimFrame => {
-                    // TODO
-                    self.window.request_redraw();
+                    // The animation frame is sent with the redraw
+                    window.request_redraw();
                 }
                 render_root::RenderRootSignal::SpawnWorker(_worker_fn) => {
                     // TODO
                 }
                 render_root::RenderRootSignal::TakeFocus => {
-                    self.window.focus_window();
+                    window.focus_window();
                 }
                 render_root::RenderRootSignal::SetCursor(cursor) => {
-                    self.window.set_cursor(cursor);
+                    window.set_cursor(cursor);
                 }
                 render_root::RenderRootSignal::SetSize(size) => {
                     // TODO - Handle return value?
-                    let _ = self.window.request_inner_size(size);
+                    let _ = window.request_inner_size(size);
                 }
                 render_root::RenderRootSignal::SetTitle(title) => {
-                    self.window.set_title(&title);
+                    window.set_title(&title);
+                }
+            }
+        }
+    }
+
+    /// Create and destroy the windows opened and closed by the app driver (or the user).
+    fn handle_window_requests(&mut self, event_loop: &ActiveEventLoop) {
+        for request in std::mem::take(&mut self.windows.requests) {
+            match request {
+                WindowRequest::Open(window_id, attributes) => {
+                    // The window may have been closed right away
+                    let Some(render_root) = self.windows.roots.get_mut(&window_id) else {
+                        continue;
+                    };
+                    let visible = attributes.visible;
+                    let window = match event_loop.create_window(attributes.with_visible(false)) {
+                        Ok(window) => window,
+                        Err(err) => {
+                            warn!("failed to create window: {err}");
+                            self.windows.roots.remove(&window_id);
+                            continue;
+                        }
+                    };
+                    let adapter =
+                        Adapter::with_event_loop_proxy(&window, self.event_loop_proxy.clone());
+                    window.set_visible(visible);
+                    if window.scale_factor() != 1.0 {
+                        render_root
+                            .handle_window_event(WindowEvent::Rescale(window.scale_factor()));
+                    }
+                    let window_state =
+                        create_window_state(&mut self.render_cx, Arc::new(window), adapter);
+                    window_state.window.request_redraw();
+                    self.window_ids.insert(window_state.window.id(), window_id);
+                    self.window_states.insert(window_id, window_state);
+                }
+                WindowRequest::Close(window_id) => {
+                    if let Some(window_state) = self.window_states.remove(&window_id) {
+                        self.window_ids.remove(&window_state.window.id());
+                    }
                 }
             }
         }
+        if self.windows.roots.is_empty() {
+            event_loop.exit();
+        }
     }
 }
 
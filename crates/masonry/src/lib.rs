@@ -120,7 +120,8 @@ pub use action::Action;
 pub use box_constraints::BoxConstraints;
 pub use contexts::{AccessCtx, EventCtx, LayoutCtx, LifeCycleCtx, PaintCtx, WidgetCtx};
 pub use event::{
-    AccessEvent, InternalLifeCycle, LifeCycle, PointerEvent, StatusChange, TextEvent, WindowTheme,
+    AccessEvent, DragData, InternalLifeCycle, LifeCycle, PointerEvent, StatusChange, TextEvent,
+    WindowTheme,
 };
 pub use kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
 pub use parley::layout::Alignment as TextAlignment;
@@ -17,9 +17,9 @@ use winit::window::CursorIcon;
 
 use crate::contexts::{EventCtx, LayoutCtx, LifeCycleCtx, PaintCtx, WidgetCtx, WorkerFn};
 use crate::debug_logger::DebugLogger;
-use crate::event::{PointerEvent, TextEvent, WindowEvent};
+use crate::event::{DragData, PointerEvent, TextEvent, WindowEvent};
 use crate::kurbo::Point;
-use crate::widget::{WidgetMut, WidgetState};
+use crate::widget::{ScrollAlign, WidgetMut, WidgetState};
 use crate::{
     AccessCtx, AccessEvent, Action, BoxConstraints, Handled, InternalLifeCycle, LifeCycle, Widget,
     WidgetId, WidgetPod,
@@ -48,7 +48,28 @@ pub(crate) struct RenderRootState {
     pub(crate) signal_queue: VecDeque<RenderRootSignal>,
     pub(crate) focused_widget: Option<WidgetId>,
     pub(crate) next_focused_widget: Option<WidgetId>,
+    /// The widget to scroll to, once it's laid out, see [`DriverCtx::scroll_to`](crate::DriverCtx::scroll_to).
+    pub(crate) scroll_request: Option<(WidgetId, ScrollAlign)>,
+    /// The drag in progress, see [`EventCtx::start_drag`].
+    pub(crate) drag: Option<DragState>,
     pub(crate) font_context: FontContext,
+    /// Whether the layout bounds and ids of all widgets are painted over them,
+    /// see [`DriverCtx::set_debug_layout`](crate::app_driver::DriverCtx::set_debug_layout).
+    pub(crate) debug_paint: bool,
+    /// Whether a ring is painted around the focused widget,
+    /// see [`DriverCtx::set_focus_ring`](crate::app_driver::DriverCtx::set_focus_ring).
+    pub(crate) focus_ring: bool,
+    /// Whether an unhandled [`Action::TextEntered`](crate::Action::TextEntered) moves the focus,
+    /// see [`DriverCtx::set_enter_moves_focus`](crate::app_driver::DriverCtx::set_enter_moves_focus).
+    pub(crate) enter_moves_focus: bool,
+    /// The focusable widgets, in the order Tab moves through them.
+    pub(crate) focus_chain: Vec<WidgetId>,
+}
+
+pub(crate) struct DragState {
+    pub(crate) data: DragData,
+    /// The widget the drag would be dropped on, found anew on every pointer event.
+    pub(crate) target: Option<WidgetId>,
 }
 
 /// Defines how a windows size should be determined
@@ -99,7 +120,13 @@ impl RenderRoot {
                 signal_queue: VecDeque::new(),
                 focused_widget: None,
                 next_focused_widget: None,
+                scroll_request: None,
+                drag: None,
                 font_context: FontContext::default(),
+                debug_paint: false,
+                focus_ring: false,
+                enter_moves_focus: false,
+                focus_chain: Vec::new(),
             },
             rebuild_access_tree: true,
         };
@@ -144,8 +171,7 @@ impl RenderRoot {
                 let last = self.last_anim.take();
                 let elapsed_ns = last.map(|t| now.duration_since(t).as_nanos()).unwrap_or(0) as u64;
 
-                if self.wants_animation_frame() {
-                    self.root_lifecycle(LifeCycle::AnimFrame(elapsed_ns));
+                if self.root_anim_frame(elapsed_ns) {
                     self.last_anim = Some(now);
                 }
                 Handled::Yes
@@ -177,6 +203,10 @@ impl RenderRoot {
         if self.root.state().needs_layout {
             self.root_layout();
         }
+        // Scrolling moves the content of a portal, which is laid out again
+        if self.route_scroll_request() && self.root.state().needs_layout {
+            self.root_layout();
+        }
         if self.root.state().needs_layout {
             warn!("Widget requested layout during layout pass");
             self.state
@@ -225,6 +255,8 @@ impl RenderRoot {
             f(root_widget)
         };
         self.post_event_processing(&mut fake_widget_state);
+        // A widget that wasn't laid out yet is scrolled to by `redraw` instead
+        self.route_scroll_request();
 
         res
     }
@@ -234,6 +266,7 @@ impl RenderRoot {
             WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
 
         self.state.next_focused_widget = self.state.focused_widget;
+        let prev_drop_target = self.state.drag.as_mut().and_then(|drag| drag.target.take());
         let mut ctx = EventCtx {
             global_state: &mut self.state,
             widget_state: &mut widget_state,
@@ -260,17 +293,36 @@ impl RenderRoot {
             Handled::from(ctx.is_handled)
         };
 
-        if let Some(cursor) = &ctx.widget_state.cursor {
-            // TODO - Add methods and `into()` impl to make this more concise.
-            ctx.global_state
-                .signal_queue
-                .push_back(RenderRootSignal::SetCursor(*cursor));
-        } else {
-            ctx.global_state
-                .signal_queue
-                .push_back(RenderRootSignal::SetCursor(CursorIcon::Default));
+        if let PointerEvent::PointerUp(_, _) = event {
+            if let Some(drag) = ctx.global_state.drag.take() {
+                if let Some(target) = drag.target {
+                    ctx.global_state
+                        .signal_queue
+                        .push_back(RenderRootSignal::Action(Action::Dropped(drag.data), target));
+                }
+                ctx.global_state
+                    .signal_queue
+                    .push_back(RenderRootSignal::RequestRedraw);
+            }
+        } else if let Some(drag) = &ctx.global_state.drag {
+            // So that the targets can update their highlight, see `EventCtx::is_drop_target`
+            if drag.target != prev_drop_target {
+                ctx.global_state
+                    .signal_queue
+                    .push_back(RenderRootSignal::RequestRedraw);
+            }
         }
 
+        let cursor = match &ctx.global_state.drag {
+            Some(drag) if drag.target.is_some() => CursorIcon::Grabbing,
+            Some(_) => CursorIcon::NoDrop,
+            None => ctx.widget_state.cursor.unwrap_or(CursorIcon::Default),
+        };
+        ctx.global_state
+            .signal_queue
+            .push_back(RenderRootSignal::SetCursor(cursor));
+        self.cursor_icon = cursor;
+
         self.post_event_processing(&mut widget_state);
         self.root.as_dyn().debug_validate(false);
 
@@ -302,6 +354,16 @@ impl RenderRoot {
             Handled::from(ctx.is_handled)
         };
 
+        if let TextEvent::KeyboardKey(key, _) = &event {
+            let is_escape = key.physical_key == PhysicalKey::Code(KeyCode::Escape);
+            if is_escape && key.state.is_pressed() && self.state.drag.take().is_some() {
+                debug!("Drag cancelled");
+                self.state
+                    .signal_queue
+                    .push_back(RenderRootSignal::RequestRedraw);
+            }
+        }
+
         // If event is tab we handle focus
         if let TextEvent::KeyboardKey(key, mods) = event {
             if handled == Handled::No && key.physical_key == PhysicalKey::Code(KeyCode::Tab) {
@@ -422,11 +484,12 @@ impl RenderRoot {
         // TODO - Handle Xilem's VIEW_CONTEXT_CHANGED
 
         let widget_state = WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
+        let debug_paint = self.state.debug_paint;
         let mut ctx = PaintCtx {
             global_state: &mut self.state,
             widget_state: &widget_state,
             depth: 0,
-            debug_paint: false,
+            debug_paint,
             debug_widget: false,
         };
 
@@ -451,8 +514,7 @@ impl RenderRoot {
         scene
     }
 
-    // TODO - Integrate in unit tests?
-    fn root_accessibility(&mut self) -> TreeUpdate {
+    pub(crate) fn root_accessibility(&mut self) -> TreeUpdate {
         let mut tree_update = TreeUpdate {
             nodes: vec![],
             tree: None,
@@ -528,6 +590,11 @@ impl RenderRoot {
         if self.root.state().update_focus_chain {
             let event = LifeCycle::BuildFocusChain;
             self.root_lifecycle(event);
+
+            // Widgets with a tab index come first, the sort is stable to keep the tree order
+            let mut focus_chain = self.root.state().focus_chain.clone();
+            focus_chain.sort_by_key(|(_, tab_index)| (tab_index.is_none(), *tab_index));
+            self.state.focus_chain = focus_chain.into_iter().map(|(id, _)| id).collect();
         }
 
         self.update_focus();
@@ -553,11 +620,45 @@ impl RenderRoot {
         }
     }
 
+    /// Route the pending [`DriverCtx::scroll_to`](crate::DriverCtx::scroll_to) request, if any.
+    ///
+    /// The target can only be located once it's laid out, until then the request stays pending.
+    /// Returns whether a request was routed.
+    fn route_scroll_request(&mut self) -> bool {
+        let Some((target, align)) = self.state.scroll_request else {
+            return false;
+        };
+        match self.root.as_dyn().find_widget_by_id(target) {
+            Some(widget) if !widget.state().is_laid_out => return false,
+            Some(_) => {}
+            // The widget was removed in the meantime
+            None => {
+                self.state.scroll_request = None;
+                return false;
+            }
+        }
+        self.state.scroll_request = None;
+        let event = LifeCycle::Internal(InternalLifeCycle::RouteScrollTo { target, align });
+        self.root_lifecycle(event);
+        true
+    }
+
     /// `true` iff any child requested an animation frame since the last `AnimFrame` event.
-    fn wants_animation_frame(&self) -> bool {
+    pub(crate) fn wants_animation_frame(&self) -> bool {
         self.root.state().request_anim
     }
 
+    /// Send an `AnimFrame` event, `elapsed_ns` after the last one, if any widget wants one.
+    ///
+    /// Returns whether the event was sent.
+    pub(crate) fn root_anim_frame(&mut self, elapsed_ns: u64) -> bool {
+        if !self.wants_animation_frame() {
+            return false;
+        }
+        self.root_lifecycle(LifeCycle::AnimFrame(elapsed_ns));
+        true
+    }
+
     fn update_focus(&mut self) {
         let old = self.state.focused_widget;
         let new = self.state.next_focused_widget;
@@ -576,9 +677,34 @@ impl RenderRoot {
         }
     }
 
+    /// Move the focus to the next widget in the focus chain, as Tab does.
+    pub(crate) fn focus_next_widget(&mut self) {
+        self.move_focus(true);
+    }
+
+    /// Move the focus to the previous widget in the focus chain, as Shift+Tab does.
+    pub(crate) fn focus_previous_widget(&mut self) {
+        self.move_focus(false);
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let mut fake_widget_state =
+            WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
+        self.state.next_focused_widget = self.widget_from_focus_chain(forward);
+        self.post_event_processing(&mut fake_widget_state);
+    }
+
     fn widget_from_focus_chain(&self, forward: bool) -> Option<WidgetId> {
-        self.state.focused_widget.and_then(|focus| {
-            self.focus_chain()
+        let first_or_last = || {
+            if forward {
+                self.focus_chain().first().copied()
+            } else {
+                self.focus_chain().last().copied()
+            }
+        };
+        match self.state.focused_widget {
+            Some(focus) => self
+                .focus_chain()
                 .iter()
                 // Find where the focused widget is in the focus chain
                 .position(|id| id == &focus)
@@ -592,21 +718,17 @@ impl RenderRoot {
                     };
                     self.focus_chain()[new_idx]
                 })
-                .or_else(|| {
-                    // If the currently focused widget isn't in the focus chain,
-                    // then we'll just return the first/last entry of the chain, if any.
-                    if forward {
-                        self.focus_chain().first().copied()
-                    } else {
-                        self.focus_chain().last().copied()
-                    }
-                })
-        })
+                // If the currently focused widget isn't in the focus chain,
+                // then we'll just return the first/last entry of the chain, if any.
+                .or_else(first_or_last),
+            // When keyboard users see the focus ring, Tab also enters the focus chain
+            None if self.state.focus_ring => first_or_last(),
+            None => None,
+        }
     }
 
-    // TODO - Store in RenderRootState
     pub(crate) fn focus_chain(&self) -> &[WidgetId] {
-        &self.root.state().focus_chain
+        &self.state.focus_chain
     }
 }
 
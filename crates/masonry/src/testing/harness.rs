@@ -4,7 +4,9 @@
 //! Tools and infrastructure for testing widgets.
 
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
+use accesskit::TreeUpdate;
 use image::io::Reader as ImageReader;
 use image::{Rgba, RgbaImage};
 use vello::util::RenderContext;
@@ -15,6 +17,7 @@ use wgpu::{
 };
 use winit::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
 use winit::event::{Ime, MouseButton};
+use winit::window::CursorIcon;
 
 use super::screenshots::get_image_diff;
 use super::snapshot_utils::get_cargo_workspace;
@@ -62,8 +65,8 @@ pub const HARNESS_DEFAULT_BACKGROUND_COLOR: Color = Color::rgb8(0x29, 0x29, 0x29
 ///
 /// `TestHarness` tries to act like the normal masonry environment. For instance, it will dispatch every `Command` sent during event handling, handle lifecycle methods, etc.
 ///
-/// The passage of time is simulated with the [`move_timers_forward`](Self::move_timers_forward) methods. **(TODO -
-/// Doesn't move animations forward.)**
+/// The passage of time is simulated with the [`move_timers_forward`](Self::move_timers_forward) methods,
+/// and animations are moved forward with [`animation_frame`](Self::animation_frame).
 ///
 /// **(TODO - ExtEvents aren't handled.)**
 ///
@@ -233,6 +236,12 @@ impl TestHarness {
         }
     }
 
+    /// Run the accessibility pass on the whole widget tree and return the resulting nodes.
+    pub fn access_tree(&mut self) -> TreeUpdate {
+        self.render_root.rebuild_access_tree = true;
+        self.render_root.root_accessibility()
+    }
+
     // TODO - We add way too many dependencies in this code
     // TODO - Should be async?
     /// Create a bitmap (an array of pixels), paint the window and return the bitmap as an 8-bits-per-channel RGB image.
@@ -402,6 +411,29 @@ impl TestHarness {
         self.process_state_after_event();
     }
 
+    /// Paint a ring around the focused widget, see
+    /// [`DriverCtx::set_focus_ring`](crate::app_driver::DriverCtx::set_focus_ring).
+    pub fn set_focus_ring(&mut self, enabled: bool) {
+        self.edit_root_widget(|mut root| {
+            let ctx = &mut root.ctx;
+            ctx.global_state.focus_ring = enabled;
+            ctx.request_layout();
+            ctx.request_paint();
+        });
+    }
+
+    /// Move the focus to the next widget in the keyboard navigation order, as Tab does.
+    pub fn focus_next(&mut self) {
+        self.render_root.focus_next_widget();
+        self.process_state_after_event();
+    }
+
+    /// Move the focus to the previous widget in the keyboard navigation order, as Shift+Tab does.
+    pub fn focus_previous(&mut self) {
+        self.render_root.focus_previous_widget();
+        self.process_state_after_event();
+    }
+
     #[cfg(FALSE)]
     /// Simulate the passage of time.
     ///
@@ -424,6 +456,20 @@ impl TestHarness {
         }
     }
 
+    /// Send an animation frame, `elapsed` after the last one, to the animating widgets.
+    ///
+    /// Like timers, animations only move forward when this is called, see [`Widget::animate`].
+    /// Nothing happens if no widget is animating.
+    pub fn animation_frame(&mut self, elapsed: Duration) {
+        self.render_root.root_anim_frame(elapsed.as_nanos() as u64);
+        self.process_state_after_event();
+    }
+
+    /// Whether a widget wants another animation frame, see [`animation_frame`](Self::animation_frame).
+    pub fn is_animating(&self) -> bool {
+        self.render_root.wants_animation_frame()
+    }
+
     // --- Getters ---
 
     /// Return the root widget.
@@ -454,6 +500,16 @@ impl TestHarness {
             .find_widget_by_id(self.render_root.state.focused_widget?)
     }
 
+    /// Return the focusable widgets, in the order Tab moves through them.
+    pub fn focus_chain(&self) -> &[WidgetId] {
+        self.render_root.focus_chain()
+    }
+
+    /// The cursor the platform was told to show after the last pointer event.
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.render_root.cursor_icon()
+    }
+
     /// Call the provided visitor on every widget in the widget tree.
     pub fn inspect_widgets(&mut self, f: impl Fn(WidgetRef<'_, dyn Widget>) + 'static) {
         fn inspect(
@@ -14,11 +14,13 @@
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::rc::Rc;
+use std::time::Duration;
 
 use accesskit::Role;
 use accesskit_winit::Event;
 use smallvec::SmallVec;
 use vello::Scene;
+use winit::window::CursorIcon;
 
 use crate::event::{PointerEvent, TextEvent};
 use crate::widget::{SizedBox, WidgetRef};
@@ -33,6 +35,9 @@ pub type LayoutFn<S> = dyn FnMut(&mut S, &mut LayoutCtx, &BoxConstraints) -> Siz
 pub type PaintFn<S> = dyn FnMut(&mut S, &mut PaintCtx, &mut Scene);
 pub type RoleFn<S> = dyn Fn(&S) -> Role;
 pub type AccessFn<S> = dyn FnMut(&mut S, &mut AccessCtx);
+pub type CursorFn<S> = dyn Fn(&S, Point) -> Option<CursorIcon>;
+pub type AcceptsDropFn<S> = dyn Fn(&S, &DragData) -> bool;
+pub type AnimateFn<S> = dyn FnMut(&mut S, Duration) -> bool;
 pub type ChildrenFn<S> = dyn Fn(&S) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]>;
 
 #[cfg(FALSE)]
@@ -52,6 +57,9 @@ pub struct ModularWidget<S> {
     paint: Option<Box<PaintFn<S>>>,
     role: Option<Box<RoleFn<S>>>,
     access: Option<Box<AccessFn<S>>>,
+    cursor: Option<Box<CursorFn<S>>>,
+    accepts_drop: Option<Box<AcceptsDropFn<S>>>,
+    animate: Option<Box<AnimateFn<S>>>,
     children: Option<Box<ChildrenFn<S>>>,
 }
 
@@ -129,6 +137,9 @@ impl<S> ModularWidget<S> {
             paint: None,
             role: None,
             access: None,
+            cursor: None,
+            accepts_drop: None,
+            animate: None,
             children: None,
         }
     }
@@ -196,6 +207,21 @@ impl<S> ModularWidget<S> {
         self
     }
 
+    pub fn cursor_fn(mut self, f: impl Fn(&S, Point) -> Option<CursorIcon> + 'static) -> Self {
+        self.cursor = Some(Box::new(f));
+        self
+    }
+
+    pub fn accepts_drop_fn(mut self, f: impl Fn(&S, &DragData) -> bool + 'static) -> Self {
+        self.accepts_drop = Some(Box::new(f));
+        self
+    }
+
+    pub fn animate_fn(mut self, f: impl FnMut(&mut S, Duration) -> bool + 'static) -> Self {
+        self.animate = Some(Box::new(f));
+        self
+    }
+
     pub fn children_fn(
         mut self,
         children: impl Fn(&S) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> + 'static,
@@ -268,6 +294,22 @@ impl<S: 'static> Widget for ModularWidget<S> {
         }
     }
 
+    fn get_cursor(&self, pos: Point) -> Option<CursorIcon> {
+        self.cursor.as_ref().and_then(|f| f(&self.state, pos))
+    }
+
+    fn accepts_drop(&self, data: &DragData) -> bool {
+        self.accepts_drop
+            .as_ref()
+            .is_some_and(|f| f(&self.state, data))
+    }
+
+    fn animate(&mut self, elapsed: Duration) -> bool {
+        self.animate
+            .as_mut()
+            .is_some_and(|f| f(&mut self.state, elapsed))
+    }
+
     fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
         if let Some(f) = self.children.as_ref() {
             f(&self.state)
@@ -417,6 +459,18 @@ impl<W: Widget> Widget for Recorder<W> {
         self.child.accessibility(ctx);
     }
 
+    fn get_cursor(&self, pos: Point) -> Option<CursorIcon> {
+        self.child.get_cursor(pos)
+    }
+
+    fn accepts_drop(&self, data: &DragData) -> bool {
+        self.child.accepts_drop(data)
+    }
+
+    fn animate(&mut self, elapsed: Duration) -> bool {
+        self.child.animate(elapsed)
+    }
+
     fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
         self.child.children()
     }
@@ -53,6 +53,24 @@ impl<T: Selectable> TextWithSelection<T> {
         self.layout.set_text(text);
     }
 
+    /// Select the text from `anchor` to `active`, which are UTF-8 byte indices.
+    ///
+    /// Indices past the end of the text are clamped to its length, and indices within
+    /// a character are moved to its end. Equal indices place a caret.
+    pub fn set_selection(&mut self, anchor: usize, active: usize) {
+        let selection = Selection::new(anchor, active, Affinity::Downstream)
+            .constrained(self.layout.text().as_str());
+        if self.selection != Some(selection) {
+            self.selection = Some(selection);
+            self.needs_selection_update = true;
+        }
+    }
+
+    /// Select the whole text.
+    pub fn select_all(&mut self) {
+        self.set_selection(0, self.layout.text().as_str().len());
+    }
+
     pub fn needs_rebuild(&self) -> bool {
         self.layout.needs_rebuild() || self.needs_selection_update
     }
@@ -499,7 +517,10 @@ pub trait EditableTextCursor {
 }
 
 impl<Str: Deref<Target = str> + TextStorage> Selectable for Str {
-    type Cursor<'a> = StringCursor<'a> where Self: 'a;
+    type Cursor<'a>
+        = StringCursor<'a>
+    where
+        Self: 'a;
 
     fn cursor<'a>(&self, position: usize) -> Option<StringCursor> {
         let new_cursor = StringCursor {
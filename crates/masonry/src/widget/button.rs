@@ -192,9 +192,8 @@ impl Widget for Button {
     }
 
     fn accessibility(&mut self, ctx: &mut AccessCtx) {
-        let _name = self.label.widget().text().as_str().to_string();
-        // We may want to add a name if it doesn't interfere with the child label
-        // ctx.current_node().set_name(name);
+        let name = self.label.widget().text().as_str().to_string();
+        ctx.current_node().set_name(name);
         ctx.current_node()
             .set_default_action_verb(DefaultActionVerb::Click);
 
@@ -242,6 +241,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn button_has_role_and_name() {
+        let [button_id] = widget_ids();
+        let mut harness = TestHarness::create(Button::new("Hello").with_id(button_id));
+
+        let tree = harness.access_tree();
+        let (_, node) = tree
+            .nodes
+            .iter()
+            .find(|(id, _)| *id == button_id.into())
+            .unwrap();
+        assert_eq!(node.role(), accesskit::Role::Button);
+        assert_eq!(node.name(), Some("Hello"));
+    }
+
     #[test]
     fn edit_button() {
         let image_1 = {
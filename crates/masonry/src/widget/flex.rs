@@ -170,22 +170,7 @@ impl Flex {
 
     /// Builder-style method to add a flexible child to the container.
     pub fn with_flex_child(mut self, child: impl Widget, params: impl Into<FlexParams>) -> Self {
-        // TODO - dedup?
-        let params = params.into();
-        let child = if params.flex > 0.0 {
-            Child::Flex {
-                widget: WidgetPod::new(Box::new(child)),
-                alignment: params.alignment,
-                flex: params.flex,
-            }
-        } else {
-            // TODO
-            tracing::warn!("Flex value should be > 0.0. To add a non-flex child use the add_child or with_child methods.\nSee the docs for masonry::widget::Flex for more information");
-            Child::Fixed {
-                widget: WidgetPod::new(Box::new(child)),
-                alignment: None,
-            }
-        };
+        let child = Child::new_flex(child, params.into());
         self.children.push(child);
         self
     }
@@ -280,15 +265,20 @@ impl<'a> WidgetMut<'a, Flex> {
     /// See also [`with_child`].
     ///
     /// [`with_child`]: Flex::with_child
-    pub fn add_child(&mut self, child: impl Widget) {
+    ///
+    /// Returns the id of the new child, e.g. to get it with [`child_mut_by_id`](Self::child_mut_by_id) later.
+    pub fn add_child(&mut self, child: impl Widget) -> WidgetId {
+        let widget: WidgetPod<Box<dyn Widget>> = WidgetPod::new(Box::new(child));
+        let id = widget.id();
         let child = Child::Fixed {
-            widget: WidgetPod::new(Box::new(child)),
+            widget,
             alignment: None,
         };
         self.widget.children.push(child);
         // TODO
         self.ctx.widget_state.children_changed = true;
         self.ctx.widget_state.needs_layout = true;
+        id
     }
 
     pub fn add_child_id(&mut self, child: impl Widget, id: WidgetId) {
@@ -303,24 +293,18 @@ impl<'a> WidgetMut<'a, Flex> {
     }
 
     /// Add a flexible child widget.
-    pub fn add_flex_child(&mut self, child: impl Widget, params: impl Into<FlexParams>) {
-        let params = params.into();
-        let child = if params.flex > 0.0 {
-            Child::Flex {
-                widget: WidgetPod::new(Box::new(child)),
-                alignment: params.alignment,
-                flex: params.flex,
-            }
-        } else {
-            // TODO
-            tracing::warn!("Flex value should be > 0.0. To add a non-flex child use the add_child or with_child methods.\nSee the docs for masonry::widget::Flex for more information");
-            Child::Fixed {
-                widget: WidgetPod::new(Box::new(child)),
-                alignment: None,
-            }
-        };
+    ///
+    /// Returns the id of the new child.
+    pub fn add_flex_child(
+        &mut self,
+        child: impl Widget,
+        params: impl Into<FlexParams>,
+    ) -> WidgetId {
+        let child = Child::new_flex(child, params.into());
+        let id = child.widget().unwrap().id();
         self.widget.children.push(child);
         self.ctx.children_changed();
+        id
     }
 
     /// Add a spacer widget with a standard size.
@@ -374,12 +358,17 @@ impl<'a> WidgetMut<'a, Flex> {
     /// See also [`with_child`].
     ///
     /// [`with_child`]: Flex::with_child
-    pub fn insert_child(&mut self, idx: usize, child: impl Widget) {
-        self.insert_child_pod(idx, WidgetPod::new(Box::new(child)));
+    ///
+    /// Returns the id of the new child.
+    pub fn insert_child(&mut self, idx: usize, child: impl Widget) -> WidgetId {
+        self.insert_child_pod(idx, WidgetPod::new(Box::new(child)))
     }
 
     /// Add a non-flex child widget.
-    pub fn insert_child_pod(&mut self, idx: usize, widget: WidgetPod<Box<dyn Widget>>) {
+    ///
+    /// Returns the id of the new child.
+    pub fn insert_child_pod(&mut self, idx: usize, widget: WidgetPod<Box<dyn Widget>>) -> WidgetId {
+        let id = widget.id();
         let child = Child::Fixed {
             widget,
             alignment: None,
@@ -388,33 +377,23 @@ impl<'a> WidgetMut<'a, Flex> {
         // TODO
         self.ctx.widget_state.children_changed = true;
         self.ctx.widget_state.needs_layout = true;
+        id
     }
 
+    /// Returns the id of the new child.
     pub fn insert_flex_child(
         &mut self,
         idx: usize,
         child: impl Widget,
         params: impl Into<FlexParams>,
-    ) {
-        let params = params.into();
-        let child = if params.flex > 0.0 {
-            Child::Flex {
-                widget: WidgetPod::new(Box::new(child)),
-                alignment: params.alignment,
-                flex: params.flex,
-            }
-        } else {
-            // TODO
-            tracing::warn!("Flex value should be > 0.0. To add a non-flex child use the add_child or with_child methods.\nSee the docs for masonry::widget::Flex for more information");
-            Child::Fixed {
-                widget: WidgetPod::new(Box::new(child)),
-                alignment: None,
-            }
-        };
+    ) -> WidgetId {
+        let child = Child::new_flex(child, params.into());
+        let id = child.widget().unwrap().id();
         self.widget.children.insert(idx, child);
         // TODO
         self.ctx.widget_state.children_changed = true;
         self.ctx.widget_state.needs_layout = true;
+        id
     }
 
     // TODO - remove
@@ -480,6 +459,17 @@ impl<'a> WidgetMut<'a, Flex> {
         Some(self.ctx.get_mut(child))
     }
 
+    /// The child with the id `id`, e.g. as returned by [`add_child`](Self::add_child).
+    pub fn child_mut_by_id(&mut self, id: WidgetId) -> Option<WidgetMut<'_, Box<dyn Widget>>> {
+        let child = self
+            .widget
+            .children
+            .iter_mut()
+            .filter_map(Child::widget_mut)
+            .find(|widget| widget.id() == id)?;
+        Some(self.ctx.get_mut(child))
+    }
+
     pub fn clear(&mut self) {
         self.widget.children.clear();
         self.ctx.widget_state.needs_layout = true;
@@ -996,6 +986,24 @@ enum Child {
 }
 
 impl Child {
+    /// A flexible child, or a non-flex child if `params.flex` isn't positive.
+    fn new_flex(child: impl Widget, params: FlexParams) -> Self {
+        if params.flex > 0.0 {
+            Child::Flex {
+                widget: WidgetPod::new(Box::new(child)),
+                alignment: params.alignment,
+                flex: params.flex,
+            }
+        } else {
+            // TODO
+            tracing::warn!("Flex value should be > 0.0. To add a non-flex child use the add_child or with_child methods.\nSee the docs for masonry::widget::Flex for more information");
+            Child::Fixed {
+                widget: WidgetPod::new(Box::new(child)),
+                alignment: None,
+            }
+        }
+    }
+
     fn widget_mut(&mut self) -> Option<&mut WidgetPod<Box<dyn Widget>>> {
         match self {
             Child::Fixed { widget, .. } | Child::Flex { widget, .. } => Some(widget),
@@ -1406,4 +1414,29 @@ mod tests {
 
         // TODO - test out-of-bounds access?
     }
+
+    #[test]
+    fn mutate_inserted_child_by_id() {
+        let widget = Flex::column()
+            .with_child(Label::new("a"))
+            .with_spacer(1.0)
+            .with_child(Label::new("c"));
+
+        let mut harness = TestHarness::create(widget);
+        let id = harness.edit_root_widget(|mut flex| {
+            let mut flex = flex.downcast::<Flex>();
+            let id = flex.insert_flex_child(1, Label::new("b"), 1.0);
+
+            let mut child = flex.child_mut_by_id(id).unwrap();
+            child.downcast::<Label>().set_text("b, edited");
+            id
+        });
+
+        assert_eq!(harness.root_widget().children()[1].id(), id);
+        let label = harness.get_widget(id);
+        assert_eq!(
+            label.downcast::<Label>().unwrap().text().to_string(),
+            "b, edited"
+        );
+    }
 }
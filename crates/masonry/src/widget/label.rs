@@ -233,6 +233,18 @@ impl Widget for Label {
         if self.text_layout.needs_rebuild() {
             debug_panic!("Called Label paint before layout");
         }
+        let brush = match ctx.text_color() {
+            Some(color) => color.into(),
+            None if self.show_disabled && ctx.is_disabled() => {
+                crate::theme::DISABLED_TEXT_COLOR.into()
+            }
+            None => self.brush.clone(),
+        };
+        self.text_layout.set_brush(brush);
+        // Parley needs a rebuild when the colour changes, this doesn't change the size
+        if self.text_layout.needs_rebuild() {
+            self.text_layout.rebuild(ctx.font_ctx());
+        }
         if self.line_break_mode == LineBreaking::Clip {
             let clip_rect = ctx.size().to_rect();
             scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
@@ -15,8 +15,8 @@ use vello::Scene;
 use crate::kurbo::{Point, Rect, Size, Vec2};
 use crate::widget::{Axis, ScrollBar, WidgetMut, WidgetRef};
 use crate::{
-    AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
-    PointerEvent, StatusChange, TextEvent, Widget, WidgetPod,
+    AccessCtx, AccessEvent, BoxConstraints, EventCtx, InternalLifeCycle, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget, WidgetId, WidgetPod,
 };
 
 // TODO - refactor - see issue #15
@@ -103,6 +103,51 @@ impl<W: Widget> Portal<W> {
     }
 }
 
+/// Where a widget ends up in the viewport of its [`Portal`] when scrolled to,
+/// see [`DriverCtx::scroll_to`](crate::DriverCtx::scroll_to).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollAlign {
+    /// Scroll as little as possible to make the widget visible. A visible widget isn't scrolled at all.
+    #[default]
+    Nearest,
+    /// Align the start (top or left) of the widget with the start of the viewport.
+    Start,
+    /// Center the widget in the viewport.
+    Center,
+    /// Align the end (bottom or right) of the widget with the end of the viewport.
+    End,
+}
+
+impl ScrollAlign {
+    /// The new start of `viewport` to show `target` with this alignment, before clamping to the content.
+    fn viewport_start(self, viewport: Range<f64>, target: Range<f64>) -> f64 {
+        let viewport_len = viewport.end - viewport.start;
+        match self {
+            ScrollAlign::Nearest => compute_pan_range(viewport, target).start,
+            ScrollAlign::Start => target.start,
+            ScrollAlign::Center => (target.start + target.end - viewport_len) / 2.0,
+            ScrollAlign::End => target.end - viewport_len,
+        }
+    }
+}
+
+/// The layout rect of the widget `target` in the coordinates of `content`.
+///
+/// Returns `None` if `target` isn't a descendant of `content`, or if it's inside another portal,
+/// which is the one that has to be scrolled.
+fn rect_in_content(content: WidgetRef<'_, dyn Widget>, target: WidgetId) -> Option<Rect> {
+    content.children().into_iter().find_map(|child| {
+        let state = child.state();
+        if state.id == target {
+            Some(state.layout_rect())
+        } else if state.is_portal || !state.children.may_contain(&target) {
+            None
+        } else {
+            rect_in_content(child, target).map(|rect| rect + state.layout_rect().origin().to_vec2())
+        }
+    })
+}
+
 fn compute_pan_range(mut viewport: Range<f64>, target: Range<f64>) -> Range<f64> {
     // if either range contains the other, the viewport doesn't move
     if target.start <= viewport.start && viewport.end <= target.end {
@@ -148,6 +193,43 @@ impl<W: Widget> Portal<W> {
     }
 }
 
+impl<W: Widget> Portal<W> {
+    /// Scroll to the descendant `target` with `align`, if it's not inside a nested portal.
+    fn scroll_to_descendant(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        target: WidgetId,
+        align: ScrollAlign,
+    ) {
+        let Some(target_rect) = rect_in_content(self.child.as_dyn(), target) else {
+            return;
+        };
+        let portal_size = ctx.size();
+        let content_size = self.child.layout_rect().size();
+        let viewport = Rect::from_origin_size(self.viewport_pos, portal_size);
+
+        let pos = Point::new(
+            align.viewport_start(
+                viewport.min_x()..viewport.max_x(),
+                target_rect.min_x()..target_rect.max_x(),
+            ),
+            align.viewport_start(
+                viewport.min_y()..viewport.max_y(),
+                target_rect.min_y()..target_rect.max_y(),
+            ),
+        );
+        if self.set_viewport_pos_raw(portal_size, content_size, pos) {
+            let progress_x = self.viewport_pos.x / (content_size - portal_size).width;
+            ctx.get_mut(&mut self.scrollbar_horizontal)
+                .set_cursor_progress(progress_x);
+            let progress_y = self.viewport_pos.y / (content_size - portal_size).height;
+            ctx.get_mut(&mut self.scrollbar_vertical)
+                .set_cursor_progress(progress_y);
+            ctx.request_layout();
+        }
+    }
+}
+
 impl<W: Widget> WidgetMut<'_, Portal<W>> {
     pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
         self.ctx.get_mut(&mut self.widget.child)
@@ -201,6 +283,7 @@ impl<W: Widget> WidgetMut<'_, Portal<W>> {
             .widget
             .set_viewport_pos_raw(portal_size, content_size, position);
         if pos_changed {
+            // TODO - share with `scroll_to_descendant`
             let progress_x = self.widget.viewport_pos.x / (content_size - portal_size).width;
             self.horizontal_scrollbar_mut()
                 .set_cursor_progress(progress_x);
@@ -305,6 +388,9 @@ impl<W: Widget> Widget for Portal<W> {
             LifeCycle::WidgetAdded => {
                 ctx.register_as_portal();
             }
+            LifeCycle::Internal(InternalLifeCycle::RouteScrollTo { target, align }) => {
+                self.scroll_to_descendant(ctx, *target, *align);
+            }
             //TODO
             //LifeCycle::RequestPanToChild(target_rect) => {}
             _ => {}
@@ -0,0 +1,39 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::testing::{ModularWidget, TestHarness};
+use crate::widget::Flex;
+
+#[test]
+fn animation_stops_after_its_duration() {
+    let animated_for = Rc::new(Cell::new(Duration::ZERO));
+    let animated = ModularWidget::new(animated_for.clone()).animate_fn(|animated_for, elapsed| {
+        animated_for.set(animated_for.get() + elapsed);
+        animated_for.get() < Duration::from_millis(100)
+    });
+    let widget = Flex::row().with_child(animated);
+    let mut harness = TestHarness::create(widget);
+    assert!(!harness.is_animating());
+
+    harness.edit_root_widget(|mut flex| {
+        let mut flex = flex.downcast::<Flex>();
+        flex.child_mut(0).unwrap().start_animation();
+    });
+    assert!(harness.is_animating());
+
+    for _ in 0..3 {
+        harness.animation_frame(Duration::from_millis(30));
+        assert!(harness.is_animating());
+    }
+    harness.animation_frame(Duration::from_millis(30));
+    assert_eq!(animated_for.get(), Duration::from_millis(120));
+    assert!(!harness.is_animating());
+
+    // The widget isn't animated anymore
+    harness.animation_frame(Duration::from_millis(30));
+    assert_eq!(animated_for.get(), Duration::from_millis(120));
+}
@@ -0,0 +1,54 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use winit::event::MouseButton;
+use winit::window::CursorIcon;
+
+use crate::event::PointerEvent;
+use crate::testing::{widget_ids, ModularWidget, TestHarness};
+use crate::widget::Flex;
+use crate::*;
+
+#[test]
+fn drag_is_dropped_on_accepting_widget() {
+    let [source, target, other] = widget_ids();
+
+    let widget = Flex::row()
+        .with_child_id(
+            ModularWidget::new(()).pointer_event_fn(|_, ctx, event| {
+                if let PointerEvent::PointerDown(_, _) = event {
+                    ctx.start_drag("item");
+                }
+            }),
+            source,
+        )
+        .with_child_id(
+            ModularWidget::new(()).accepts_drop_fn(|_, data| data.payload::<&str>().is_some()),
+            target,
+        )
+        .with_child_id(ModularWidget::new(()), other);
+    let mut harness = TestHarness::create(widget);
+
+    harness.mouse_move_to(source);
+    harness.mouse_button_press(MouseButton::Left);
+    harness.mouse_move_to(target);
+    assert_eq!(harness.cursor_icon(), CursorIcon::Grabbing);
+    harness.mouse_button_release(MouseButton::Left);
+
+    let (action, action_target) = harness.pop_action().unwrap();
+    assert_eq!(action_target, target);
+    let Action::Dropped(data) = action else {
+        panic!("expected a drop, got {action:?}");
+    };
+    assert_eq!(data.source(), source);
+    assert_eq!(data.payload::<&str>(), Some(&"item"));
+
+    // Released over a widget that doesn't accept it, the drag ends without a drop
+    harness.mouse_move_to(source);
+    harness.mouse_button_press(MouseButton::Left);
+    harness.mouse_move_to(other);
+    assert_eq!(harness.cursor_icon(), CursorIcon::NoDrop);
+    harness.mouse_button_release(MouseButton::Left);
+    assert_eq!(harness.pop_action(), None);
+    assert_eq!(harness.cursor_icon(), CursorIcon::Default);
+}
@@ -3,7 +3,7 @@
 
 //! Tests related to layout.
 
-use crate::kurbo::{Insets, Size};
+use crate::kurbo::{Insets, Rect, Size};
 use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt};
 use crate::widget::{Flex, SizedBox};
 
@@ -68,6 +68,39 @@ fn layout_insets() {
     assert_eq!(parent_paint_rect.y1, BOX_WIDTH + 20.0);
 }
 
+#[test]
+fn read_layout_rect() {
+    const BOX_WIDTH: f64 = 50.;
+
+    let [box_id, new_box_id] = widget_ids();
+
+    let widget = Flex::row()
+        .with_child(SizedBox::empty().width(BOX_WIDTH).height(BOX_WIDTH))
+        .with_child_id(SizedBox::empty().width(BOX_WIDTH).height(BOX_WIDTH), box_id)
+        .with_flex_spacer(1.0);
+
+    let mut harness = TestHarness::create(widget);
+
+    let box_rect = harness.get_widget(box_id).layout_rect();
+    assert_eq!(box_rect.x0, BOX_WIDTH);
+    assert_eq!(box_rect.size(), Size::new(BOX_WIDTH, BOX_WIDTH));
+
+    harness.edit_root_widget(|mut root| {
+        let mut flex = root.downcast::<Flex>();
+        assert_eq!(flex.child_mut(1).unwrap().layout_rect(), box_rect);
+
+        // the new child hasn't been laid out yet
+        flex.add_child_id(
+            SizedBox::empty().width(BOX_WIDTH).height(BOX_WIDTH),
+            new_box_id,
+        );
+        assert_eq!(flex.child_mut(3).unwrap().layout_rect(), Rect::ZERO);
+    });
+
+    let new_box_rect = harness.get_widget(new_box_id).layout_rect();
+    assert_eq!(new_box_rect.size(), Size::new(BOX_WIDTH, BOX_WIDTH));
+}
+
 // TODO - insets + flex
 // TODO - viewport
 // TODO - insets + viewport
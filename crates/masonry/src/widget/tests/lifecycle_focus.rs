@@ -238,3 +238,56 @@ fn resign_focus_on_disable() {
     assert_eq!(harness.window().focus_chain(), &[focus_2]);
     assert_eq!(harness.window().focus, None);
 }
+
+fn make_focusable_widget() -> impl Widget {
+    ModularWidget::new(()).lifecycle_fn(|_, ctx, event| {
+        if let LifeCycle::BuildFocusChain = event {
+            ctx.register_for_focus();
+        }
+    })
+}
+
+/// Check that Tab follows the tab indices and skips disabled widgets.
+#[test]
+fn tab_index_order() {
+    let [id_1, id_2, id_3, id_4] = widget_ids();
+
+    let widget = Flex::column()
+        .with_child_id(make_focusable_widget(), id_1)
+        .with_child_id(make_focusable_widget(), id_2)
+        .with_child_id(make_focusable_widget(), id_3)
+        .with_child_id(make_focusable_widget(), id_4);
+
+    let mut harness = TestHarness::create(widget);
+    assert_eq!(harness.focus_chain(), &[id_1, id_2, id_3, id_4]);
+
+    // id_3 goes first, then the widgets without a tab index in tree order; id_2 is disabled
+    harness.edit_root_widget(|mut root| {
+        let mut flex = root.downcast::<Flex>();
+        flex.child_mut(2).unwrap().set_tab_index(0);
+        flex.child_mut(1).unwrap().ctx.set_disabled(true);
+    });
+    assert_eq!(harness.focus_chain(), &[id_3, id_1, id_4]);
+
+    // Without the focus ring, Tab doesn't focus anything when nothing is focused
+    harness.focus_next();
+    assert!(harness.focused_widget().is_none());
+
+    harness.set_focus_ring(true);
+    let mut focus_order = Vec::new();
+    for _ in 0..4 {
+        harness.focus_next();
+        focus_order.push(harness.focused_widget().unwrap().id());
+    }
+    assert_eq!(focus_order, [id_3, id_1, id_4, id_3]);
+
+    harness.focus_previous();
+    assert_eq!(harness.focused_widget().unwrap().id(), id_4);
+
+    // Clearing the tab index restores the tree order
+    harness.edit_root_widget(|mut root| {
+        let mut flex = root.downcast::<Flex>();
+        flex.child_mut(2).unwrap().clear_tab_index();
+    });
+    assert_eq!(harness.focus_chain(), &[id_1, id_3, id_4]);
+}
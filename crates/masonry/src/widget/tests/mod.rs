@@ -3,9 +3,12 @@
 
 // TODO - See https://github.com/PoignardAzur/masonry-rs/issues/58
 
+mod animation;
+mod drag_and_drop;
 mod layout;
 mod lifecycle_basic;
 mod lifecycle_disable;
 mod lifecycle_focus;
 mod safety_rails;
 mod status_change;
+mod style_override;
@@ -5,7 +5,9 @@ use assert_matches::assert_matches;
 use winit::event::MouseButton;
 
 use crate::event::{PointerEvent, PointerState};
-use crate::testing::{widget_ids, Record, Recording, TestHarness, TestWidgetExt as _};
+use crate::testing::{
+    widget_ids, ModularWidget, Record, Recording, TestHarness, TestWidgetExt as _,
+};
 use crate::widget::{Button, Flex, Label, SizedBox};
 use crate::*;
 
@@ -293,3 +295,33 @@ fn get_pointer_events_while_active() {
     harness.mouse_move_to(empty_2);
     assert_matches!(next_pointer_event(&button_rec), None);
 }
+
+#[test]
+fn innermost_cursor_wins() {
+    use winit::window::CursorIcon;
+
+    let [link] = widget_ids();
+
+    // Only the left half of the widget is a link
+    let widget = Flex::row().with_spacer(50.0).with_child_id(
+        ModularWidget::new(()).cursor_fn(|_, pos| (pos.x < 50.0).then_some(CursorIcon::Pointer)),
+        link,
+    );
+
+    let mut harness = TestHarness::create(widget);
+    harness.edit_root_widget(|mut root| root.set_cursor(CursorIcon::Text));
+    let rect = harness.get_widget(link).state().window_layout_rect();
+
+    harness.mouse_move((rect.x0 + 10.0, rect.center().y));
+    assert_eq!(harness.cursor_icon(), CursorIcon::Pointer);
+
+    harness.mouse_move((rect.x0 + 60.0, rect.center().y));
+    assert_eq!(harness.cursor_icon(), CursorIcon::Text);
+
+    harness.mouse_move((10.0, rect.center().y));
+    assert_eq!(harness.cursor_icon(), CursorIcon::Text);
+
+    harness.edit_root_widget(|mut root| root.clear_cursor());
+    harness.mouse_move((rect.x0 + 60.0, rect.center().y));
+    assert_eq!(harness.cursor_icon(), CursorIcon::Default);
+}
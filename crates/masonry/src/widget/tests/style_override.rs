@@ -0,0 +1,39 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::testing::{ModularWidget, Record, Recording, TestHarness, TestWidgetExt as _};
+use crate::*;
+
+#[test]
+fn style_overrides_only_request_paint() {
+    let record = Recording::default();
+    let widget = ModularWidget::new(()).record(&record);
+    let mut harness = TestHarness::create(widget);
+    let state = harness.root_widget().state();
+    assert_eq!(state.background_color, None);
+    assert_eq!(state.text_color, None);
+    record.clear();
+
+    harness.edit_root_widget(|mut root| {
+        // as if the widget had just been painted
+        root.ctx.widget_state.needs_paint = false;
+        root.set_background_color(Color::RED);
+        root.set_text_color(Color::BLUE);
+    });
+    let state = harness.root_widget().state();
+    assert_eq!(state.background_color, Some(Color::RED));
+    assert_eq!(state.text_color, Some(Color::BLUE));
+    assert!(state.needs_paint);
+    assert!(!state.needs_layout);
+    let records = record.drain();
+    assert!(!records.iter().any(|r| matches!(r, Record::Layout(_))));
+
+    // back to the theme default, i.e. no background and the widget's own text color
+    harness.edit_root_widget(|mut root| {
+        root.clear_background_color();
+        root.clear_text_color();
+    });
+    let state = harness.root_widget().state();
+    assert_eq!(state.background_color, None);
+    assert_eq!(state.text_color, None);
+}
@@ -1,6 +1,8 @@
 // Copyright 2018 the Xilem Authors and the Druid Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::Range;
+
 use accesskit::Role;
 use kurbo::{Affine, Point, Size, Stroke};
 use parley::{
@@ -54,6 +56,13 @@ impl Textbox {
         self.editor.text()
     }
 
+    /// The selected range of the text in UTF-8 bytes, which is empty for a caret.
+    ///
+    /// This is `None` when nothing is selected, e.g. while the textbox isn't focused.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.editor.selection.map(|selection| selection.range())
+    }
+
     #[doc(alias = "with_text_color")]
     pub fn with_text_brush(mut self, brush: impl Into<TextBrush>) -> Self {
         self.brush = brush.into();
@@ -90,6 +99,29 @@ impl WidgetMut<'_, Textbox> {
         self.widget.editor.text()
     }
 
+    /// See [`Textbox::selection`].
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.widget.selection()
+    }
+
+    /// Select the text from `start` to `end`, which are UTF-8 byte indices, e.g. for find-and-replace.
+    ///
+    /// The caret is placed at `end`, so `end` may be smaller than `start` to select backwards.
+    /// Indices past the end of the text are clamped to its length, and indices within
+    /// a character are moved to its end.
+    ///
+    /// The selection is cleared when the textbox loses focus.
+    pub fn set_selection(&mut self, start: usize, end: usize) {
+        self.set_text_properties(|layout| layout.set_selection(start, end));
+        self.ctx.request_paint();
+    }
+
+    /// Select the whole text, e.g. when the textbox gains focus.
+    pub fn select_all(&mut self) {
+        self.set_text_properties(|layout| layout.select_all());
+        self.ctx.request_paint();
+    }
+
     pub fn set_text_properties<R>(
         &mut self,
         f: impl FnOnce(&mut TextWithSelection<String>) -> R,
@@ -318,3 +350,47 @@ impl Widget for Textbox {
         Some(self.editor.text().as_str().chars().take(100).collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    #[test]
+    fn selection_is_clamped_to_text() {
+        let mut harness = TestHarness::create(Textbox::new("Héllo world"));
+        assert_eq!(
+            harness
+                .root_widget()
+                .downcast::<Textbox>()
+                .unwrap()
+                .selection(),
+            None
+        );
+
+        harness.edit_root_widget(|mut textbox| {
+            let mut textbox = textbox.downcast::<Textbox>();
+            textbox.set_selection(7, 3);
+            assert_eq!(textbox.selection(), Some(3..7));
+
+            // past the end of the text
+            textbox.set_selection(7, 100);
+            assert_eq!(textbox.selection(), Some(7..12));
+
+            // within the two bytes of 'é'
+            textbox.set_selection(2, 2);
+            assert_eq!(textbox.selection(), Some(3..3));
+
+            textbox.select_all();
+            assert_eq!(textbox.selection(), Some(0..12));
+        });
+        assert_eq!(
+            harness
+                .root_widget()
+                .downcast::<Textbox>()
+                .unwrap()
+                .selection(),
+            Some(0..12)
+        );
+    }
+}
@@ -5,13 +5,15 @@ use std::any::Any;
 use std::num::NonZeroU64;
 use std::ops::{Deref, DerefMut};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 use accesskit::Role;
 use smallvec::SmallVec;
 use tracing::{trace_span, Span};
 use vello::Scene;
+use winit::window::CursorIcon;
 
-use crate::event::{AccessEvent, PointerEvent, StatusChange, TextEvent};
+use crate::event::{AccessEvent, DragData, PointerEvent, StatusChange, TextEvent};
 use crate::widget::WidgetRef;
 use crate::{
     AccessCtx, AsAny, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
@@ -114,9 +116,75 @@ pub trait Widget: AsAny {
     /// the render context, which is especially useful for scrolling.
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene);
 
+    /// The role of the widget in the accessibility tree, e.g. [`Role::Button`].
+    ///
+    /// Purely decorative widgets (and containers that only lay out their children)
+    /// use [`Role::GenericContainer`], which screen readers don't announce.
     fn accessibility_role(&self) -> Role;
 
-    fn accessibility(&mut self, ctx: &mut AccessCtx);
+    /// Describe the widget for assistive technology, e.g. screen readers.
+    ///
+    /// The node of the widget ([`AccessCtx::current_node`]) already has its role,
+    /// bounds and children, this adds e.g. its name and state.
+    /// Container widgets must call `accessibility` on their children, so that
+    /// the nodes of the children are added to the tree as well.
+    ///
+    /// The default does nothing, which fits leaf widgets without a name or state.
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        _ = ctx;
+    }
+
+    /// The cursor to show when the pointer is at `pos` over this widget, if any.
+    ///
+    /// The position is in local coordinates, like the ones of [`get_child_at_pos`](Widget::get_child_at_pos).
+    /// This is asked on every pointer event while the widget is hot, so it can report different
+    /// cursors for parts of the widget (e.g. a link in a text). A cursor of a hot child widget
+    /// takes precedence, and one set with [`EventCtx::set_cursor`] takes precedence over this.
+    ///
+    /// The default reports none.
+    fn get_cursor(&self, pos: Point) -> Option<CursorIcon> {
+        _ = pos;
+        None
+    }
+
+    /// Whether `data` can be dropped on this widget, see [`EventCtx::start_drag`].
+    ///
+    /// This is asked on every pointer event during a drag while the widget is hot, the innermost
+    /// hot widget that accepts the drag is the drop target. When the drag is released over it,
+    /// an [`Action::Dropped`](crate::Action::Dropped) with its id is submitted.
+    ///
+    /// The default accepts nothing.
+    fn accepts_drop(&self, data: &DragData) -> bool {
+        _ = data;
+        false
+    }
+
+    /// Move the animation of the widget forward by `elapsed`, the time since the last frame.
+    ///
+    /// This is called on every animation frame after the widget started animating with
+    /// [`WidgetMut::start_animation`](crate::widget::WidgetMut::start_animation), and the widget
+    /// is repainted after each call. Return `true` to keep animating. Once no widget is animating,
+    /// no more animation frames are requested.
+    ///
+    /// The first frame of an animation has an `elapsed` of zero.
+    ///
+    /// The default stops right away.
+    fn animate(&mut self, elapsed: Duration) -> bool {
+        _ = elapsed;
+        false
+    }
+
+    /// The position of the widget in the keyboard navigation order, if it registers for focus.
+    ///
+    /// Like the `tabindex` of HTML, Tab first moves through the widgets with a tab index,
+    /// from the lowest to the highest, and then through the others in tree order. Widgets
+    /// with the same tab index are also visited in tree order. Disabled widgets are skipped.
+    ///
+    /// This can be overridden with [`WidgetMut::set_tab_index`](crate::widget::WidgetMut::set_tab_index).
+    /// The default has no tab index.
+    fn tab_index(&self) -> Option<u32> {
+        None
+    }
 
     /// Return references to this widget's children.
     ///
@@ -155,6 +223,7 @@ pub trait Widget: AsAny {
     ///
     /// The child return is a direct child, not eg a grand-child. The position is in
     /// relative coordinates. (Eg `(0,0)` is the top-left corner of `self`).
+    /// When children overlap, the last one is returned, as it's painted on top.
     ///
     /// Has a default implementation, that can be overridden to search children more
     /// efficiently.
@@ -162,6 +231,7 @@ pub trait Widget: AsAny {
         // layout_rect() is in parent coordinate space
         self.children()
             .into_iter()
+            .rev()
             .find(|child| child.state().layout_rect().contains(pos))
     }
 
@@ -285,6 +355,22 @@ impl Widget for Box<dyn Widget> {
         self.deref_mut().accessibility(ctx);
     }
 
+    fn get_cursor(&self, pos: Point) -> Option<CursorIcon> {
+        self.deref().get_cursor(pos)
+    }
+
+    fn accepts_drop(&self, data: &DragData) -> bool {
+        self.deref().accepts_drop(data)
+    }
+
+    fn animate(&mut self, elapsed: Duration) -> bool {
+        self.deref_mut().animate(elapsed)
+    }
+
+    fn tab_index(&self) -> Option<u32> {
+        self.deref().tab_index()
+    }
+
     fn type_name(&self) -> &'static str {
         self.deref().type_name()
     }
@@ -1,7 +1,14 @@
 // Copyright 2018 the Xilem Authors and the Druid Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt;
+
+use vello::peniko::Color;
+use winit::window::CursorIcon;
+
 use crate::contexts::WidgetCtx;
+use crate::kurbo::Rect;
+use crate::widget::CursorChange;
 use crate::Widget;
 
 // TODO - Document extension trait workaround.
@@ -34,6 +41,87 @@ impl<W: Widget> Drop for WidgetMut<'_, W> {
     }
 }
 
+impl<W: Widget> WidgetMut<'_, W> {
+    /// The widget's layout rect computed by the last layout pass, in its parent's coordinate space.
+    ///
+    /// This can be used e.g. to position a popup relative to the widget.
+    /// Before the first layout pass, this returns [`Rect::ZERO`] (with a warning in debug builds).
+    ///
+    /// See [`WidgetPod::layout_rect`](crate::WidgetPod::layout_rect) for details.
+    pub fn layout_rect(&self) -> Rect {
+        self.ctx.widget_state.computed_layout_rect()
+    }
+
+    /// Set the cursor shown while the pointer is over the widget.
+    ///
+    /// Like [`EventCtx::set_cursor`](crate::EventCtx::set_cursor), a cursor of a child widget under
+    /// the pointer takes precedence. The cursor of the platform is updated on the next pointer event.
+    pub fn set_cursor(&mut self, cursor: CursorIcon) {
+        self.ctx.widget_state.cursor_change = CursorChange::Set(cursor);
+    }
+
+    /// Undo [`set_cursor`](Self::set_cursor), and any cursor set by the widget itself.
+    pub fn clear_cursor(&mut self) {
+        self.ctx.widget_state.cursor_change = CursorChange::Default;
+    }
+
+    /// Paint the layout rect of the widget with `color`, under what the widget paints itself.
+    ///
+    /// This only requests a paint, the layout of the widget isn't affected.
+    #[doc(alias = "set_background")]
+    pub fn set_background_color(&mut self, color: impl Into<Color>) {
+        self.ctx.widget_state.background_color = Some(color.into());
+        self.ctx.request_paint();
+    }
+
+    /// Undo [`set_background_color`](Self::set_background_color), so that only the widget
+    /// paints its background again.
+    pub fn clear_background_color(&mut self) {
+        if self.ctx.widget_state.background_color.take().is_some() {
+            self.ctx.request_paint();
+        }
+    }
+
+    /// Override the color of the text the widget paints, see [`PaintCtx::text_color`](crate::PaintCtx::text_color).
+    ///
+    /// This only requests a paint, the layout of the widget isn't affected.
+    pub fn set_text_color(&mut self, color: impl Into<Color>) {
+        self.ctx.widget_state.text_color = Some(color.into());
+        self.ctx.request_paint();
+    }
+
+    /// Undo [`set_text_color`](Self::set_text_color), so that the widget paints its text
+    /// with its own (usually the theme's) color again.
+    pub fn clear_text_color(&mut self) {
+        if self.ctx.widget_state.text_color.take().is_some() {
+            self.ctx.request_paint();
+        }
+    }
+
+    /// Call [`Widget::animate`] on every animation frame, until it returns `false`.
+    ///
+    /// Calling this while the widget is already animating does nothing.
+    pub fn start_animation(&mut self) {
+        self.ctx.widget_state.is_animating = true;
+        self.ctx.request_anim_frame();
+    }
+
+    /// Set the position of the widget in the keyboard navigation order, overriding [`Widget::tab_index`].
+    ///
+    /// This only has an effect if the widget registers for focus.
+    pub fn set_tab_index(&mut self, tab_index: u32) {
+        self.ctx.widget_state.tab_index = Some(tab_index);
+        self.ctx.widget_state.update_focus_chain = true;
+    }
+
+    /// Undo [`set_tab_index`](Self::set_tab_index), so that the widget's own tab index is used again.
+    pub fn clear_tab_index(&mut self) {
+        if self.ctx.widget_state.tab_index.take().is_some() {
+            self.ctx.widget_state.update_focus_chain = true;
+        }
+    }
+}
+
 impl<'a> WidgetMut<'a, Box<dyn Widget>> {
     /// Attempt to downcast to `WidgetMut` of concrete Widget type.
     pub fn try_downcast<W2: Widget>(&mut self) -> Option<WidgetMut<'_, W2>> {
@@ -63,15 +151,40 @@ impl<'a> WidgetMut<'a, Box<dyn Widget>> {
         let w1_name = self.widget.type_name();
         match self.widget.as_mut_any().downcast_mut() {
             Some(widget) => WidgetMut { ctx, widget },
-            None => {
-                panic!(
-                    "failed to downcast widget: expected widget of type `{}`, found `{}`",
-                    std::any::type_name::<W2>(),
-                    w1_name,
-                );
-            }
+            None => panic!("{}", DowncastError::new::<W2>(w1_name)),
         }
     }
 }
 
+/// The error returned when a widget isn't of the requested type,
+/// see [`DriverCtx::try_get_root`](crate::app_driver::DriverCtx::try_get_root).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DowncastError {
+    /// The type name of the requested widget type.
+    pub expected: &'static str,
+    /// The type name of the widget.
+    pub actual: &'static str,
+}
+
+impl DowncastError {
+    pub(crate) fn new<W: Widget>(actual: &'static str) -> Self {
+        DowncastError {
+            expected: std::any::type_name::<W>(),
+            actual,
+        }
+    }
+}
+
+impl fmt::Display for DowncastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to downcast widget: expected widget of type `{}`, found `{}`",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for DowncastError {}
+
 // TODO - unit tests
@@ -1,16 +1,20 @@
 // Copyright 2018 the Xilem Authors and the Druid Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 use accesskit::{NodeBuilder, NodeId};
+use parley::FontContext;
 use tracing::{info_span, trace, warn};
 use vello::Scene;
 use winit::dpi::LogicalPosition;
 
 use crate::event::{AccessEvent, PointerEvent, TextEvent};
 use crate::kurbo::{Affine, Insets, Point, Rect, Shape, Size};
-use crate::paint_scene_helpers::stroke;
+use crate::paint_scene_helpers::{fill_color, stroke};
 use crate::render_root::RenderRootState;
-use crate::theme::get_debug_color;
+use crate::text2::TextLayout;
+use crate::theme::{self, get_debug_color};
 use crate::widget::{WidgetRef, WidgetState};
 use crate::{
     AccessCtx, BoxConstraints, EventCtx, InternalLifeCycle, LayoutCtx, LifeCycle, LifeCycleCtx,
@@ -387,6 +391,22 @@ impl<W: Widget> WidgetPod<W> {
         let call_inner = (had_active || self.state.is_hot || hot_changed) && !self.state.is_stashed;
         //let call_inner = true;
 
+        // This is updated before the children are visited, so it's merged with their cursors.
+        self.state.hover_cursor = match hot_pos {
+            Some(pos) if self.state.is_hot && !self.state.is_stashed => {
+                let local_pos = Point::new(pos.x, pos.y) - self.state.window_origin().to_vec2();
+                self.inner.get_cursor(local_pos)
+            }
+            _ => None,
+        };
+
+        if let Some(drag) = &mut parent_ctx.global_state.drag {
+            // Ancestors are visited first, so the innermost accepting widget ends up as target
+            if self.state.is_hot && !self.state.is_stashed && self.inner.accepts_drop(&drag.data) {
+                drag.target = Some(self.state.id);
+            }
+        }
+
         if call_inner {
             self.call_widget_method_with_checks("on_pointer_event", |widget_pod| {
                 // widget_pod is a reborrow of `self`
@@ -641,6 +661,9 @@ impl<W: Widget> WidgetPod<W> {
 
                     if let Some(change) = this_changed {
                         self.state.has_focus = change;
+                        if parent_ctx.global_state.focus_ring {
+                            self.state.needs_paint = true;
+                        }
                         extra_event = Some(StatusChange::FocusChanged(change));
                     } else {
                         self.state.has_focus = false;
@@ -667,6 +690,10 @@ impl<W: Widget> WidgetPod<W> {
                     // TODO - self.state.is_hidden
                     true
                 }
+                InternalLifeCycle::RouteScrollTo { target, .. } => {
+                    // The bloom filter we're checking can return false positives.
+                    self.state.children.may_contain(target)
+                }
             },
             LifeCycle::WidgetAdded => {
                 if !self.state.is_new {
@@ -697,7 +724,17 @@ impl<W: Widget> WidgetPod<W> {
                 );
                 return;
             }
-            LifeCycle::AnimFrame(_) => true,
+            LifeCycle::AnimFrame(elapsed_ns) => {
+                // The widgets that keep animating request the next frame again
+                self.state.request_anim = false;
+                if self.state.is_animating {
+                    let elapsed = Duration::from_nanos(*elapsed_ns);
+                    self.state.is_animating = self.inner.animate(elapsed);
+                    self.state.request_anim = self.state.is_animating;
+                    self.state.needs_paint = true;
+                }
+                true
+            }
             LifeCycle::DisabledChanged(ancestors_disabled) => {
                 self.state.update_focus_chain = true;
 
@@ -789,6 +826,13 @@ impl<W: Widget> WidgetPod<W> {
                 }
                 self.state.has_focus = had_focus;
 
+                // The widget registered itself without knowing its tab index
+                let id = self.state.id;
+                let tab_index = self.state.tab_index.or_else(|| self.inner.tab_index());
+                if let Some(entry) = self.state.focus_chain.iter_mut().find(|(w, _)| *w == id) {
+                    entry.1 = tab_index;
+                }
+
                 if !self.state.is_disabled() {
                     parent_ctx
                         .widget_state
@@ -913,6 +957,7 @@ impl<W: Widget> WidgetPod<W> {
 
         parent_ctx.widget_state.merge_up(&mut self.state);
         self.state.size = new_size;
+        self.state.is_laid_out = true;
         self.log_layout_issues(new_size);
 
         parent_ctx
@@ -981,12 +1026,23 @@ impl<W: Widget> WidgetPod<W> {
                 };
 
                 widget_pod.fragment.reset();
+                if let Some(color) = widget_pod.state.background_color {
+                    let rect = widget_pod.state.size.to_rect();
+                    fill_color(&mut widget_pod.fragment, &rect, color);
+                }
                 widget_pod
                     .inner
                     .paint(&mut inner_ctx, &mut widget_pod.fragment);
 
+                if parent_ctx.global_state.focus_ring
+                    && parent_ctx.global_state.focused_widget == Some(widget_pod.state.id)
+                {
+                    widget_pod.paint_focus_ring(widget_pod.state.size);
+                }
+
                 if parent_ctx.debug_paint {
-                    widget_pod.debug_paint_layout_bounds(widget_pod.state.size);
+                    let font_ctx = &mut parent_ctx.global_state.font_context;
+                    widget_pod.debug_paint_layout_bounds(widget_pod.state.size, font_ctx);
                 }
             });
         }
@@ -995,13 +1051,30 @@ impl<W: Widget> WidgetPod<W> {
         scene.append(&self.fragment, Some(transform));
     }
 
-    fn debug_paint_layout_bounds(&mut self, size: Size) {
+    /// Paint a ring around the layout bounds of this widget, as it has the focus.
+    fn paint_focus_ring(&mut self, size: Size) {
+        const RING_WIDTH: f64 = 2.0;
+        let rect = size.to_rect().inset(RING_WIDTH / 2.0);
+        stroke(&mut self.fragment, &rect, theme::PRIMARY_LIGHT, RING_WIDTH);
+    }
+
+    /// Paint the layout bounds and the id of this widget over it.
+    ///
+    /// This only paints into the fragment of the widget, the size and hit-testing
+    /// of the widget are unchanged.
+    fn debug_paint_layout_bounds(&mut self, size: Size, font_ctx: &mut FontContext) {
         const BORDER_WIDTH: f64 = 1.0;
+        const ID_TEXT_SIZE: f32 = 10.0;
         let rect = size.to_rect().inset(BORDER_WIDTH / -2.0);
         let id = self.id().to_raw();
         let color = get_debug_color(id);
         let scene = &mut self.fragment;
         stroke(scene, &rect, color, BORDER_WIDTH);
+
+        let mut id_text = TextLayout::new(format!("#{id}"), ID_TEXT_SIZE);
+        id_text.set_brush(color);
+        id_text.rebuild(font_ctx);
+        id_text.draw(scene, (BORDER_WIDTH, BORDER_WIDTH));
     }
 
     pub fn accessibility(&mut self, parent_ctx: &mut AccessCtx) {
@@ -5,7 +5,7 @@ use std::ops::Deref;
 
 use smallvec::SmallVec;
 
-use crate::kurbo::Point;
+use crate::kurbo::{Point, Rect};
 use crate::{Widget, WidgetId, WidgetState};
 
 /// A rich reference to a [`Widget`].
@@ -96,6 +96,15 @@ impl<'w, W: Widget + ?Sized> WidgetRef<'w, W> {
         self.widget_state.id
     }
 
+    /// The widget's layout rect computed by the last layout pass, in its parent's coordinate space.
+    ///
+    /// Before the first layout pass, this returns [`Rect::ZERO`] (with a warning in debug builds).
+    ///
+    /// See [`WidgetMut::layout_rect`](crate::widget::WidgetMut::layout_rect).
+    pub fn layout_rect(&self) -> Rect {
+        self.widget_state.computed_layout_rect()
+    }
+
     /// Attempt to downcast to `WidgetRef` of concrete Widget type.
     pub fn downcast<W2: Widget>(&self) -> Option<WidgetRef<'w, W2>> {
         Some(WidgetRef {
@@ -145,8 +154,10 @@ impl<'w> WidgetRef<'w, dyn Widget> {
         }
 
         loop {
-            if let Some(child) = innermost_widget.deref().get_child_at_pos(pos) {
-                pos -= innermost_widget.state().layout_rect().origin().to_vec2();
+            // the layout rects of the children are relative to their parent
+            let local_pos = pos - innermost_widget.state().layout_rect().origin().to_vec2();
+            if let Some(child) = innermost_widget.deref().get_child_at_pos(local_pos) {
+                pos = local_pos;
                 innermost_widget = child;
             } else {
                 return Some(innermost_widget);
@@ -232,4 +243,38 @@ mod tests {
         assert_matches!(harness.get_widget(label_id).downcast::<Label>(), Some(_));
         assert_matches!(harness.get_widget(label_id).downcast::<Button>(), None);
     }
+
+    #[test]
+    fn overlapping_siblings_hit_the_last_one() {
+        use crate::testing::ModularWidget;
+        use crate::widget::SizedBox;
+        use crate::{Point, Size};
+
+        let [below, above] = widget_ids();
+        let boxed =
+            |id, size| WidgetPod::new_with_id(SizedBox::empty().width(size).height(size), id);
+        // Both children are placed at the origin, the second one is painted on top of the first
+        let stack = ModularWidget::new((boxed(below, 100.), boxed(above, 50.)))
+            .lifecycle_fn(|(first, second), ctx, event| {
+                first.lifecycle(ctx, event);
+                second.lifecycle(ctx, event);
+            })
+            .layout_fn(|(first, second), ctx, bc| {
+                first.layout(ctx, &bc.loosen());
+                second.layout(ctx, &bc.loosen());
+                ctx.place_child(first, Point::ZERO);
+                ctx.place_child(second, Point::ZERO);
+                Size::new(100., 100.)
+            })
+            .children_fn(|(first, second)| smallvec::smallvec![first.as_dyn(), second.as_dyn()]);
+        let harness = TestHarness::create(stack);
+        let root = harness.root_widget();
+
+        let hit = |pos| root.get_child_at_pos(pos).map(|child| child.id());
+        assert_eq!(hit(Point::new(25., 25.)), Some(above));
+        assert_eq!(hit(Point::new(75., 75.)), Some(below));
+
+        let found = root.find_widget_at_pos(Point::new(25., 25.)).unwrap();
+        assert_eq!(found.id(), above);
+    }
 }
@@ -5,6 +5,7 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use vello::peniko::Color;
 use winit::window::CursorIcon;
 
 use crate::bloom::Bloom;
@@ -46,6 +47,9 @@ pub struct WidgetState {
     /// The origin of the child in the parent's coordinate space; together with
     /// `size` these constitute the child's layout rect.
     pub(crate) origin: Point,
+    /// `true` once the widget has gone through a layout pass, so that `size` and
+    /// `origin` are meaningful.
+    pub(crate) is_laid_out: bool,
     /// The origin of the parent in the window coordinate space;
     pub(crate) parent_window_origin: Point,
     /// The insets applied to the layout rect to generate the paint rect.
@@ -91,12 +95,20 @@ pub struct WidgetState {
     /// Any descendant has requested an animation frame.
     pub(crate) request_anim: bool,
 
+    /// The widget started animating with `WidgetMut::start_animation`, and its
+    /// `Widget::animate` hasn't returned `false` yet.
+    pub(crate) is_animating: bool,
+
     /// Any descendant has requested an accessibility update.
     pub(crate) request_accessibility_update: bool,
 
     pub(crate) update_focus_chain: bool,
 
-    pub(crate) focus_chain: Vec<WidgetId>,
+    /// The widgets registered for focus in this subtree, in tree order, with their tab index.
+    pub(crate) focus_chain: Vec<(WidgetId, Option<u32>)>,
+    /// The tab index set with [`WidgetMut::set_tab_index`](crate::widget::WidgetMut::set_tab_index),
+    /// overriding [`Widget::tab_index`](crate::Widget::tab_index).
+    pub(crate) tab_index: Option<u32>,
 
     pub(crate) children: Bloom<WidgetId>,
     pub(crate) children_changed: bool,
@@ -106,6 +118,15 @@ pub struct WidgetState {
     /// `cursor_change`, which is persistent).
     // TODO - Remove and handle in WidgetRoot instead
     pub(crate) cursor: Option<CursorIcon>,
+    /// The cursor reported by [`Widget::get_cursor`](crate::Widget::get_cursor) for the
+    /// position of the pointer, updated on every pointer event while the widget is hot.
+    pub(crate) hover_cursor: Option<CursorIcon>,
+    /// The background color set with [`WidgetMut::set_background_color`](crate::widget::WidgetMut::set_background_color),
+    /// painted under the widget. `None` if the widget paints its own (or no) background.
+    pub(crate) background_color: Option<Color>,
+    /// The text color set with [`WidgetMut::set_text_color`](crate::widget::WidgetMut::set_text_color),
+    /// `None` for the widget's own (usually the theme's) text color.
+    pub(crate) text_color: Option<Color>,
 
     pub(crate) text_registrations: Vec<TextFieldRegistration>,
 
@@ -151,6 +172,7 @@ impl WidgetState {
         WidgetState {
             id,
             origin: Point::ORIGIN,
+            is_laid_out: false,
             parent_window_origin: Point::ORIGIN,
             size: size.unwrap_or_default(),
             is_expecting_place_child_call: false,
@@ -171,12 +193,17 @@ impl WidgetState {
             has_active: false,
             has_focus: false,
             request_anim: false,
+            is_animating: false,
             request_accessibility_update: false,
             focus_chain: Vec::new(),
+            tab_index: None,
             children: Bloom::new(),
             children_changed: false,
             cursor_change: CursorChange::Default,
             cursor: None,
+            hover_cursor: None,
+            background_color: None,
+            text_color: None,
             is_explicitly_disabled_new: false,
             text_registrations: Vec::new(),
             update_focus_chain: false,
@@ -243,6 +270,8 @@ impl WidgetState {
         if self.cursor.is_none() {
             if let CursorChange::Set(cursor) = &self.cursor_change {
                 self.cursor = Some(*cursor);
+            } else {
+                self.cursor = self.hover_cursor;
             }
         }
     }
@@ -251,7 +280,10 @@ impl WidgetState {
     /// in that case there will be nothing in the `cursor` field (as `merge_up`
     /// is never called) and so we need to also check the `cursor_change` field.
     fn take_cursor(&mut self) -> Option<CursorIcon> {
-        self.cursor.take().or_else(|| self.cursor_change.cursor())
+        self.cursor
+            .take()
+            .or_else(|| self.cursor_change.cursor())
+            .or(self.hover_cursor)
     }
 
     #[inline]
@@ -273,6 +305,23 @@ impl WidgetState {
         Rect::from_origin_size(self.origin, self.size)
     }
 
+    /// The [`layout_rect`](Self::layout_rect) computed by the last layout pass.
+    ///
+    /// Before the first layout pass, the widget doesn't have a meaningful layout rect yet,
+    /// so this returns [`Rect::ZERO`] and logs a warning.
+    pub(crate) fn computed_layout_rect(&self) -> Rect {
+        if !self.is_laid_out {
+            #[cfg(debug_assertions)]
+            tracing::warn!(
+                "Widget '{}' #{}: reading layout rect before the first layout pass.",
+                self.widget_name,
+                self.id.to_raw(),
+            );
+            return Rect::ZERO;
+        }
+        self.layout_rect()
+    }
+
     /// The [`layout_rect`](crate::WidgetPod::layout_rect) in window coordinates.
     ///
     /// This might not map to a visible area of the screen, eg if the widget is scrolled
@@ -24,4 +24,5 @@ mod view;
 
 pub use id::{Id, IdPath};
 pub use message::{AsyncWake, MessageResult};
+pub use sequence::{for_each_map, indexed, separated};
 pub use vec_splice::VecSplice;
@@ -53,6 +53,92 @@ macro_rules! impl_view_tuple {
         }
     }
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_view_sequence_for_map {
+    ($viewseq:ident, $elements_splice: ident, $cx:ty, $changeflags:ty; $( : $bound:path )?) => {
+        /// The keys are the identities of the child sequences, i.e. a child sequence is rebuilt
+        /// when its key is in both maps, even when its value changed, and deleted (or built)
+        /// when its key was removed (or inserted), see also [`for_each_map`]($crate::for_each_map).
+        ///
+        /// As the children are ordered by key, no elements have to be moved.
+        impl<T, A, K: Ord + Clone $( + $bound )?, VT: $viewseq<T, A>> $viewseq<T, A>
+            for std::collections::BTreeMap<K, VT>
+        {
+            type State = std::collections::BTreeMap<K, VT::State>;
+
+            fn build(&self, cx: &mut $cx, elements: &mut dyn $elements_splice) -> Self::State {
+                self.iter()
+                    .map(|(key, child)| (key.clone(), child.build(cx, elements)))
+                    .collect()
+            }
+
+            fn rebuild(
+                &self,
+                cx: &mut $cx,
+                prev: &Self,
+                state: &mut Self::State,
+                elements: &mut dyn $elements_splice,
+            ) -> $changeflags {
+                let mut changed = <$changeflags>::default();
+                let mut next = self.iter().peekable();
+                let mut prev = prev.iter().peekable();
+                // Walk over both maps in key order, like a merge
+                loop {
+                    match (next.peek().copied(), prev.peek().copied()) {
+                        (Some((key, child)), Some((prev_key, child_prev))) if key == prev_key => {
+                            let child_state = state.get_mut(key).unwrap();
+                            changed |= child.rebuild(cx, child_prev, child_state, elements);
+                            next.next();
+                            prev.next();
+                        }
+                        (next_entry, Some((prev_key, child_prev)))
+                            if next_entry.map_or(true, |(key, _)| prev_key < key) =>
+                        {
+                            let child_state = state.remove(prev_key).unwrap();
+                            elements.delete(child_prev.count(&child_state), cx);
+                            changed |= <$changeflags>::tree_structure();
+                            prev.next();
+                        }
+                        (Some((key, child)), _) => {
+                            state.insert(key.clone(), child.build(cx, elements));
+                            changed |= <$changeflags>::tree_structure();
+                            next.next();
+                        }
+                        (None, _) => break,
+                    }
+                }
+                changed
+            }
+
+            fn count(&self, state: &Self::State) -> usize {
+                self.values().zip(state.values()).map(|(child, child_state)|
+                    child.count(child_state))
+                    .sum()
+            }
+
+            fn message(
+                &self,
+                id_path: &[$crate::Id],
+                state: &mut Self::State,
+                message: Box<dyn std::any::Any>,
+                app_state: &mut T,
+            ) -> $crate::MessageResult<A> {
+                let mut result = $crate::MessageResult::Stale(message);
+                for (child, child_state) in self.values().zip(state.values_mut()) {
+                    if let $crate::MessageResult::Stale(message) = result {
+                        result = child.message(id_path, child_state, message, app_state);
+                    } else {
+                        break;
+                    }
+                }
+                result
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! generate_viewsequence_trait {
     ($viewseq:ident, $view:ident, $viewmarker: ident, $elements_splice: ident, $bound:ident, $cx:ty, $changeflags:ty, $pod:ty; $( $ss:tt )* ) => {
@@ -71,6 +157,9 @@ macro_rules! generate_viewsequence_trait {
             fn mutate(&mut self, cx: &mut $cx) -> &mut $pod;
             // TODO(#160) this could also track view id changes (old_id, new_id)
             /// Mark any changes done by `mutate` on the current element (this doesn't change the index)
+            ///
+            /// When `changeflags` is empty, the element was rebuilt without any changes,
+            /// and implementations shouldn't touch it (e.g. by replacing it in the DOM).
             fn mark(&mut self, changeflags: $changeflags, cx: &mut $cx) -> $changeflags;
             /// Delete the next n existing elements (this doesn't change the index)
             fn delete(&mut self, n: usize, cx: &mut $cx);
@@ -140,6 +229,10 @@ macro_rules! generate_viewsequence_trait {
             ) -> $crate::MessageResult<A>;
 
             /// Returns the current amount of widgets built by this sequence.
+            ///
+            /// This has to be the number of elements that `build` pushed, or `rebuild` pushed or mutated
+            /// (where every previous element has to be either mutated or deleted), including zero,
+            /// otherwise the elements of the parent get out of sync. Parents may check this in debug builds.
             fn count(&self, state: &Self::State) -> usize;
         }
 
@@ -338,6 +431,8 @@ macro_rules! generate_viewsequence_trait {
         #[doc = concat!("`", stringify!($viewmarker), "`.")]
         pub trait $viewmarker {}
 
+        $crate::impl_view_sequence_for_map!($viewseq, $elements_splice, $cx, $changeflags; $( $ss )*);
+
         $crate::impl_view_tuple!($viewseq, $elements_splice, $pod, $cx, $changeflags, ;);
         $crate::impl_view_tuple!($viewseq, $elements_splice, $pod, $cx, $changeflags,
             V0; 0);
@@ -361,3 +456,61 @@ macro_rules! generate_viewsequence_trait {
             V0, V1, V2, V3, V4, V5, V6, V7, V8, V9; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
     };
 }
+
+/// Create a view sequence from the entries of a map (e.g. a `HashMap` or a `BTreeMap`),
+/// with the view sequence returned by `view_fn` for each entry.
+///
+/// The keys are used as identities of the children, so when (only) the value of an entry changes,
+/// its elements are rebuilt and not recreated. The children are ordered by key.
+pub fn for_each_map<'a, K, V, VT>(
+    map: impl IntoIterator<Item = (&'a K, &'a V)>,
+    view_fn: impl Fn(&K, &V) -> VT,
+) -> std::collections::BTreeMap<K, VT>
+where
+    K: Ord + Clone + 'a,
+    V: 'a,
+{
+    map.into_iter()
+        .map(|(key, value)| (key.clone(), view_fn(key, value)))
+        .collect()
+}
+
+/// Create a view sequence from `items`, with the view sequence returned by `view_fn` for each
+/// item and its index, e.g. for a "remove item N" button.
+///
+/// The children are identified by their position, like the ones of a `Vec`. So when an item is
+/// removed, the children of the following items are rebuilt with the next item and a decremented
+/// index. Handlers should thus only use indices that were captured in the current view, and not
+/// keep them across changes of `items`. When items need a stable identity, use [`for_each_map`]
+/// with their keys instead.
+pub fn indexed<'a, V, VT>(
+    items: impl IntoIterator<Item = &'a V>,
+    view_fn: impl Fn(usize, &V) -> VT,
+) -> Vec<VT>
+where
+    V: 'a,
+{
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| view_fn(index, item))
+        .collect()
+}
+
+/// Create a view sequence from `items`, with a separator view returned by `separator_fn`
+/// between each pair of adjacent items (e.g. commas or dividers), but not before the first
+/// or after the last item. So there are no separators for a single or no item.
+///
+/// Each item is paired with the separator in front of it, the children are identified by their
+/// position like the ones of a `Vec`. So when the number of items changes, only separators
+/// at the end are built or deleted.
+pub fn separated<VT, S>(
+    items: impl IntoIterator<Item = VT>,
+    separator_fn: impl Fn() -> S,
+) -> Vec<(Option<S>, VT)> {
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| ((index > 0).then(&separator_fn), item))
+        .collect()
+}
@@ -48,12 +48,13 @@ where
     Logic: FnMut(&mut State) -> View,
     View: MasonryView<State>,
 {
-    fn on_action(
+    fn on_action_handled(
         &mut self,
         ctx: &mut masonry::app_driver::DriverCtx<'_>,
+        _window_id: masonry::app_driver::WindowId,
         widget_id: masonry::WidgetId,
         action: masonry::Action,
-    ) {
+    ) -> masonry::Handled {
         if let Some(id_path) = self.view_cx.widget_map.get(&widget_id) {
             let message_result = self.current_view.message(
                 &mut self.view_state,
@@ -61,38 +62,55 @@ where
                 Box::new(action),
                 &mut self.state,
             );
-            let rebuild = match message_result {
+            let (rebuild, handled) = match message_result {
                 MessageResult::Action(()) => {
                     // It's not entirely clear what to do here
-                    true
+                    (true, masonry::Handled::Yes)
                 }
-                MessageResult::RequestRebuild => true,
-                MessageResult::Nop => false,
+                MessageResult::RequestRebuild => (true, masonry::Handled::Yes),
+                MessageResult::Nop => (false, masonry::Handled::Yes),
+                // e.g. Enter in a textbox without `on_enter`, which keeps its default follow-up
                 MessageResult::Stale(_) => {
                     tracing::info!("Discarding message");
-                    false
+                    (false, masonry::Handled::No)
                 }
             };
             if rebuild {
-                let next_view = (self.logic)(&mut self.state);
-                let mut root = ctx.get_root::<RootWidget<View::Element>>();
-
-                self.view_cx.view_tree_changed = false;
-                next_view.rebuild(
-                    &mut self.view_state,
-                    &mut self.view_cx,
-                    &self.current_view,
-                    root.get_element(),
-                );
+                self.rebuild(ctx);
                 if cfg!(debug_assertions) && !self.view_cx.view_tree_changed {
                     tracing::debug!("Nothing changed as result of action");
                 }
-                self.current_view = next_view;
             }
+            handled
         } else {
             eprintln!("Got action {action:?} for unknown widget. Did you forget to use `with_action_widget`?");
+            masonry::Handled::No
         }
     }
+
+    fn on_rebuild(&mut self, ctx: &mut masonry::app_driver::DriverCtx<'_>) {
+        self.rebuild(ctx);
+    }
+}
+
+impl<State, Logic, View> MasonryDriver<State, Logic, View, View::ViewState>
+where
+    Logic: FnMut(&mut State) -> View,
+    View: MasonryView<State>,
+{
+    fn rebuild(&mut self, ctx: &mut masonry::app_driver::DriverCtx<'_>) {
+        let next_view = (self.logic)(&mut self.state);
+        let mut root = ctx.get_root::<RootWidget<View::Element>>();
+
+        self.view_cx.view_tree_changed = false;
+        next_view.rebuild(
+            &mut self.view_state,
+            &mut self.view_cx,
+            &self.current_view,
+            root.get_element(),
+        );
+        self.current_view = next_view;
+    }
 }
 
 impl<State, Logic, View> Xilem<State, Logic, View>
@@ -0,0 +1,252 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A group of `<details>` elements of which at most one is open, see [`accordion`].
+
+use std::{any::Any, marker::PhantomData};
+
+use gloo::events::{EventListener, EventListenerOptions};
+use wasm_bindgen::JsCast;
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    elements::html,
+    interfaces::{sealed::Sealed, HtmlDetailsElement, HtmlDivElement},
+    view::DomNode,
+    ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Keeps at most one of the `<details>` children of an element open, see [`accordion`].
+pub struct Accordion<E, T, A, F> {
+    element: E,
+    open: Option<usize>,
+    on_change: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct AccordionState<S> {
+    child_id: Id,
+    child_state: S,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// A `<div>` with the `<details>` elements `items`, of which only the one at the index `open` is open.
+///
+/// When the user opens an item, `on_change` is called with its index, the previously open item
+/// is closed when the app is rebuilt with that index. When the user closes the open item,
+/// `on_change` is called with `None`, i.e. no item has to be open.
+///
+/// The `open` property of the items is set to match `open` on every rebuild, so the items
+/// stay as they are when `on_change` doesn't update the app state.
+pub fn accordion<V, F, T, A, OA>(
+    open: Option<usize>,
+    items: impl IntoIterator<Item = V>,
+    on_change: F,
+) -> Accordion<html::Div<T, A, Vec<V>>, T, A, F>
+where
+    V: HtmlDetailsElement<T, A>,
+    F: Fn(&mut T, Option<usize>) -> OA,
+    OA: OptionalAction<A>,
+{
+    Accordion {
+        element: html::div(items.into_iter().collect::<Vec<_>>()),
+        open,
+        on_change,
+        phantom: PhantomData,
+    }
+}
+
+/// Sent when the item at `index` was opened or closed.
+struct Toggled {
+    index: usize,
+    open: bool,
+}
+
+fn items(container: &web_sys::Node) -> impl Iterator<Item = web_sys::HtmlDetailsElement> {
+    let children = container.child_nodes();
+    (0..children.length()).filter_map(move |i| {
+        children
+            .get(i)?
+            .dyn_into::<web_sys::HtmlDetailsElement>()
+            .ok()
+    })
+}
+
+/// Set the `open` property of the items of `container`, to whether it's the item at `open`.
+fn sync_open(container: &web_sys::Node, open: Option<usize>) {
+    for (index, item) in items(container).enumerate() {
+        let is_open = open == Some(index);
+        if item.open() != is_open {
+            item.set_open(is_open);
+        }
+    }
+}
+
+/// `toggle` doesn't bubble, so it's captured, the items of nested accordions are ignored.
+fn create_toggle_listener(container: &web_sys::Node, cx: &Cx) -> EventListener {
+    let thunk = cx.message_thunk();
+    let items_of = container.clone();
+    EventListener::new_with_options(
+        container,
+        "toggle",
+        EventListenerOptions::run_in_capture_phase(),
+        move |event| {
+            let Some(target) = event.target() else {
+                return;
+            };
+            if let Some(index) = items(&items_of)
+                .position(|item| AsRef::<web_sys::EventTarget>::as_ref(&item) == &target)
+            {
+                let open = target
+                    .unchecked_into::<web_sys::HtmlDetailsElement>()
+                    .open();
+                thunk.push_message(Toggled { index, open });
+            }
+        },
+    )
+}
+
+impl<E, T, A, F> ViewMarker for Accordion<E, T, A, F> {}
+impl<E, T, A, F> Sealed for Accordion<E, T, A, F> {}
+
+impl<E, T, A, F, OA> View<T, A> for Accordion<E, T, A, F>
+where
+    E: HtmlDivElement<T, A>,
+    F: Fn(&mut T, Option<usize>) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = AccordionState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            let (child_id, child_state, element) = self.element.build(cx);
+            sync_open(element.as_node_ref(), self.open);
+            let listener = create_toggle_listener(element.as_node_ref(), cx);
+            let state = AccordionState {
+                child_id,
+                child_state,
+                listener,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            let node = element.as_node_ref();
+            if changed.contains(ChangeFlags::STRUCTURE) {
+                state.listener = create_toggle_listener(node, cx);
+            }
+            // The user may have toggled an item without the app state following
+            sync_open(node, self.open);
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] => match message.downcast::<Toggled>() {
+                Ok(toggled) => {
+                    let open = match (toggled.open, self.open) {
+                        (true, open) if open != Some(toggled.index) => Some(toggled.index),
+                        (false, Some(open)) if open == toggled.index => None,
+                        // e.g. the events of the items that were synced on rebuild
+                        _ => return MessageResult::Nop,
+                    };
+                    match (self.on_change)(app_state, open).action() {
+                        Some(action) => MessageResult::Action(action),
+                        None => MessageResult::Nop,
+                    }
+                }
+                Err(message) => MessageResult::Stale(message),
+            },
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlDivElement,
+    Accordion,
+    vars: <F, OA,>,
+    vars_on_ty: <F,>,
+    bounds: {
+        F: Fn(&mut T, Option<usize>) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn opening_an_item_closes_the_open_one() {
+        use wasm_bindgen::JsCast;
+
+        use super::accordion;
+        use crate::{elements::html, test_util::mount};
+
+        let view = |open: Option<usize>| {
+            let items = ["First", "Second"]
+                .map(|title| html::details::<_, (), _>((html::summary(title), html::p("Content"))));
+            accordion(open, items, |open: &mut Option<usize>, index| {
+                *open = index;
+            })
+        };
+        let mut harness = mount(Some(0), view(Some(0)));
+        let items: Vec<web_sys::HtmlDetailsElement> = (1..=2)
+            .map(|n| {
+                harness
+                    .root_element()
+                    .query_selector(&format!("details:nth-child({n})"))
+                    .unwrap()
+                    .unwrap()
+                    .unchecked_into()
+            })
+            .collect();
+        assert!(items[0].open() && !items[1].open());
+
+        let toggle = || web_sys::Event::new("toggle").unwrap();
+        items[1].set_open(true);
+        harness.dispatch_event("details:nth-child(2)", &toggle());
+        assert_eq!(*harness.app_state(), Some(1));
+        harness.rebuild(view(Some(1)));
+        assert!(!items[0].open() && items[1].open());
+        // the `toggle` of the item that was closed by the rebuild is ignored
+        harness.dispatch_event("details:nth-child(1)", &toggle());
+        assert_eq!(*harness.app_state(), Some(1));
+
+        // closing the open item closes all of them
+        items[1].set_open(false);
+        harness.dispatch_event("details:nth-child(2)", &toggle());
+        assert_eq!(*harness.app_state(), None);
+        harness.rebuild(view(None));
+        assert!(!items[0].open() && !items[1].open());
+    }
+}
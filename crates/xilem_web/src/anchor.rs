@@ -0,0 +1,140 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opening links in a new browsing context, see [`HtmlAnchorElement::target_blank`].
+
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, HtmlAnchorElement},
+    AttributeValue, ChangeFlags, Cx, View, ViewMarker,
+};
+
+/// Keeps the opened page from accessing `window.opener` and from receiving the `Referer` header.
+const BLANK_REL: [&str; 2] = ["noopener", "noreferrer"];
+
+/// Sets `target="_blank"` and `rel="noopener noreferrer"`, see [`HtmlAnchorElement::target_blank`].
+pub struct TargetBlank<E, T, A> {
+    element: E,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A> TargetBlank<E, T, A> {
+    pub(crate) fn new(element: E) -> Self {
+        TargetBlank {
+            element,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Add the tokens of [`BLANK_REL`] that are missing in the (space-separated) `rel`.
+fn merge_rel(rel: Option<&str>) -> String {
+    let mut merged = rel.unwrap_or_default().trim().to_owned();
+    for token in BLANK_REL {
+        if !merged
+            .split_ascii_whitespace()
+            .any(|t| t.eq_ignore_ascii_case(token))
+        {
+            if !merged.is_empty() {
+                merged.push(' ');
+            }
+            merged.push_str(token);
+        }
+    }
+    merged
+}
+
+/// Merge [`BLANK_REL`] into a `rel` attribute that was set by an outer view, and set `target`,
+/// this has to be done before the child is built, as outer attributes take precedence.
+fn add_blank_attrs(cx: &mut Cx) {
+    let attributes = &mut cx.current_element_props.attributes;
+    let rel = attributes.get("rel").map(AttributeValue::serialize);
+    let rel = merge_rel(rel.as_deref());
+    attributes.insert("rel".into(), AttributeValue::String(rel.into()));
+    cx.add_attr_to_element(
+        &"target".into(),
+        &Some(AttributeValue::String("_blank".into())),
+    );
+}
+
+impl<E, T, A> ViewMarker for TargetBlank<E, T, A> {}
+impl<E, T, A> Sealed for TargetBlank<E, T, A> {}
+
+impl<E: HtmlAnchorElement<T, A>, T, A> View<T, A> for TargetBlank<E, T, A> {
+    type State = E::State;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        add_blank_attrs(cx);
+        self.element.build(cx)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        add_blank_attrs(cx);
+        self.element.rebuild(cx, &prev.element, id, state, element)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.element.message(id_path, state, message, app_state)
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(HtmlAnchorElement, TargetBlank);
+
+#[cfg(test)]
+mod tests {
+    use super::merge_rel;
+
+    #[test]
+    fn user_rel_is_merged() {
+        assert_eq!(merge_rel(None), "noopener noreferrer");
+        assert_eq!(merge_rel(Some("external")), "external noopener noreferrer");
+        assert_eq!(merge_rel(Some("NoOpener help")), "NoOpener help noreferrer");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn target_blank_adds_noopener() {
+        use crate::{
+            elements::html,
+            interfaces::{Element as _, HtmlAnchorElement as _},
+            Cx, View,
+        };
+
+        let mut cx = Cx::new();
+        let link = html::a::<(), (), _>("docs")
+            .attr("href", "https://example.com")
+            .target_blank();
+        let (_, _, element) = View::build(&link, &mut cx);
+        assert_eq!(element.get_attribute("target").as_deref(), Some("_blank"));
+        assert_eq!(
+            element.get_attribute("rel").as_deref(),
+            Some("noopener noreferrer")
+        );
+
+        let link = html::a::<(), (), _>("docs")
+            .target_blank()
+            .attr("rel", "external");
+        let (_, _, element) = View::build(&link, &mut cx);
+        assert_eq!(
+            element.get_attribute("rel").as_deref(),
+            Some("external noopener noreferrer")
+        );
+    }
+}
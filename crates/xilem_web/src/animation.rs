@@ -0,0 +1,251 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Driving an animation of the app state with `requestAnimationFrame`.
+
+use std::{any::Any, marker::PhantomData, rc::Rc, time::Duration};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::MessageThunk, interfaces::sealed::Sealed, ChangeFlags, Cx, OptionalAction, View,
+    ViewMarker,
+};
+
+pub struct Animation<E, F, T, A = ()> {
+    duration: Duration,
+    easing: E,
+    on_frame: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct AnimationState {
+    clock: AnimationClock,
+    thunk: Rc<MessageThunk>,
+    /// The requested frame, which is `None` when the animation is complete.
+    frame: Option<AnimationFrame>,
+    #[allow(unused)]
+    visibility_listener: EventListener,
+}
+
+/// Call `on_frame` with the eased progress of an animation that runs for `duration`, on every animation frame.
+///
+/// The animation starts when the view is built, the linear progress from `0.0` to `1.0` is passed
+/// through `easing` (e.g. `|t| t` for a linear animation), and `on_frame` is called with the result,
+/// the last call is with `easing(1.0)`, after which no more frames are requested.
+///
+/// The progress is based on the timestamps of the frames, so dropped frames don't slow down the animation.
+/// While the document is hidden (e.g. the tab is in the background), the browser doesn't run animation frames,
+/// and the time in the background doesn't count towards the progress, i.e. the animation is paused.
+///
+/// When the view is removed, the requested frame is canceled.
+///
+/// The view itself is rendered as an empty text node.
+pub fn animation<E, F, T, A, OA>(
+    duration: Duration,
+    easing: E,
+    on_frame: F,
+) -> Animation<E, F, T, A>
+where
+    E: Fn(f64) -> f64,
+    F: Fn(&mut T, f64) -> OA,
+    OA: OptionalAction<A>,
+{
+    Animation {
+        duration,
+        easing,
+        on_frame,
+        phantom: PhantomData,
+    }
+}
+
+/// Sent by an animation frame, with its timestamp in milliseconds.
+struct Frame(f64);
+
+/// Sent when the visibility of the document changes.
+struct VisibilityChanged;
+
+/// Tracks the time an [`Animation`] has been running, via the timestamps of the animation frames.
+#[derive(Debug, Default)]
+struct AnimationClock {
+    /// In milliseconds.
+    elapsed: f64,
+    /// The timestamp of the previous frame, which is `None` before the first frame and after a pause.
+    last_timestamp: Option<f64>,
+}
+
+impl AnimationClock {
+    /// Advance the clock to the frame at `timestamp`, returns the linear progress (between `0.0` and `1.0`).
+    fn frame(&mut self, timestamp: f64, duration: Duration) -> f64 {
+        if let Some(last) = self.last_timestamp {
+            self.elapsed += (timestamp - last).max(0.0);
+        }
+        self.last_timestamp = Some(timestamp);
+        let duration = duration.as_secs_f64() * 1000.0;
+        if duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / duration).min(1.0)
+    }
+
+    /// Don't count the time until the next frame, e.g. while the document is hidden.
+    fn pause(&mut self) {
+        self.last_timestamp = None;
+    }
+}
+
+/// A requested animation frame, which is canceled when it's dropped.
+struct AnimationFrame {
+    handle: i32,
+    #[allow(unused)]
+    callback: Closure<dyn FnMut(f64)>,
+}
+
+impl AnimationFrame {
+    fn request(thunk: &Rc<MessageThunk>) -> Self {
+        let thunk = thunk.clone();
+        let callback =
+            Closure::<dyn FnMut(f64)>::new(move |timestamp| thunk.push_message(Frame(timestamp)));
+        let handle = web_sys::window()
+            .unwrap_throw()
+            .request_animation_frame(callback.as_ref().unchecked_ref())
+            .unwrap_throw();
+        AnimationFrame { handle, callback }
+    }
+}
+
+impl Drop for AnimationFrame {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            // Canceling a frame that already ran is a no-op
+            window.cancel_animation_frame(self.handle).unwrap_throw();
+        }
+    }
+}
+
+impl<E, F, T, A> ViewMarker for Animation<E, F, T, A> {}
+impl<E, F, T, A> Sealed for Animation<E, F, T, A> {}
+
+impl<E, F, T, A, OA> View<T, A> for Animation<E, F, T, A>
+where
+    E: Fn(f64) -> f64,
+    F: Fn(&mut T, f64) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = AnimationState;
+    type Element = web_sys::Text;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, thunk) = cx.with_new_id(|cx| Rc::new(cx.message_thunk()));
+        let visibility_listener = {
+            let thunk = thunk.clone();
+            EventListener::new(cx.document(), "visibilitychange", move |_| {
+                thunk.push_message(VisibilityChanged);
+            })
+        };
+        let state = AnimationState {
+            clock: AnimationClock::default(),
+            frame: Some(AnimationFrame::request(&thunk)),
+            thunk,
+            visibility_listener,
+        };
+        let element = web_sys::Text::new().unwrap_throw();
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        _element: &mut Self::Element,
+    ) -> ChangeFlags {
+        // A changed duration is taken into account with the next frame
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        if !id_path.is_empty() {
+            return MessageResult::Stale(message);
+        }
+        if message.is::<VisibilityChanged>() {
+            if crate::document().hidden() {
+                state.clock.pause();
+            }
+            return MessageResult::Nop;
+        }
+        match message.downcast::<Frame>() {
+            // Frames of a completed animation may still be in the message queue
+            Ok(_) if state.frame.is_none() => MessageResult::Nop,
+            Ok(frame) => {
+                let progress = state.clock.frame(frame.0, self.duration);
+                state.frame = (progress < 1.0).then(|| AnimationFrame::request(&state.thunk));
+                match (self.on_frame)(app_state, (self.easing)(progress)).action() {
+                    Some(action) => MessageResult::Action(action),
+                    None => MessageResult::Nop,
+                }
+            }
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::AnimationClock;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    /// Runs the clock with the frames at `timestamps`, as a mocked `requestAnimationFrame` would.
+    fn progress(clock: &mut AnimationClock, timestamps: &[f64]) -> Vec<f64> {
+        timestamps
+            .iter()
+            .map(|timestamp| clock.frame(*timestamp, SECOND))
+            .collect()
+    }
+
+    #[test]
+    fn progress_advances_and_completes() {
+        let mut clock = AnimationClock::default();
+        let progress = progress(&mut clock, &[100.0, 350.0, 600.0, 1100.0, 1300.0]);
+        assert_eq!(progress, [0.0, 0.25, 0.5, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn dropped_frames_dont_slow_down() {
+        let mut clock = AnimationClock::default();
+        // frames at a steady 60Hz and frames with gaps arrive at the same progress
+        let steady: Vec<f64> = (0..=30).map(|frame| frame as f64 * 1000.0 / 60.0).collect();
+        let last = progress(&mut clock, &steady).pop().unwrap();
+        assert!((last - 0.5).abs() < 1e-9);
+
+        let mut clock = AnimationClock::default();
+        assert_eq!(progress(&mut clock, &[0.0, 100.0, 500.0]), [0.0, 0.1, 0.5]);
+    }
+
+    #[test]
+    fn time_in_background_is_not_counted() {
+        let mut clock = AnimationClock::default();
+        progress(&mut clock, &[0.0, 250.0]);
+        clock.pause();
+        // the first frame after the tab is visible again only resumes the clock
+        assert_eq!(progress(&mut clock, &[60_000.0, 60_250.0]), [0.25, 0.5]);
+    }
+
+    #[test]
+    fn zero_duration_completes_immediately() {
+        let mut clock = AnimationClock::default();
+        assert_eq!(clock.frame(0.0, Duration::ZERO), 1.0);
+    }
+}
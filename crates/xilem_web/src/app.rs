@@ -50,43 +50,36 @@ impl<T: 'static, V: View<T> + 'static, F: FnMut(&mut T) -> V + 'static> App<T, V
     /// forgotten, and will continue to respond to events in the background.
     pub fn run(self, root: &web_sys::HtmlElement) {
         self.0.borrow_mut().ensure_app(root);
+        self.deliver_pending_messages();
         // Latter may not be necessary, we have an rc loop.
         std::mem::forget(self);
     }
-}
 
-impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppInner<T, V, F> {
-    pub fn new(data: T, app_logic: F) -> Self {
-        let cx = Cx::new();
-        AppInner {
-            data,
-            app_logic,
-            view: None,
-            id: None,
-            state: None,
-            element: None,
-            cx,
+    /// Deliver the messages views posted to themselves (via [`Cx::message_self`]) during the last pass.
+    ///
+    /// Delivering them rebuilds the app, which may post new messages, so this is repeated until
+    /// no messages are posted anymore, but at most [`MAX_DELIVERY_PASSES`] times.
+    fn deliver_pending_messages(&self) {
+        for _ in 0..MAX_DELIVERY_PASSES {
+            let messages = self.0.borrow_mut().cx.take_pending_messages();
+            if messages.is_empty() {
+                return;
+            }
+            for message in messages {
+                self.update(message);
+            }
         }
-    }
-
-    fn ensure_app(&mut self, root: &web_sys::HtmlElement) {
-        if self.view.is_none() {
-            let view = (self.app_logic)(&mut self.data);
-            let (id, state, element) = view.build(&mut self.cx);
-            self.view = Some(view);
-            self.id = Some(id);
-            self.state = Some(state);
-
-            root.append_child(element.as_node_ref()).unwrap();
-            self.element = Some(element);
+        let dropped = self.0.borrow_mut().cx.take_pending_messages().len();
+        if dropped > 0 {
+            tracing::warn!(
+                "views kept posting messages to themselves for {MAX_DELIVERY_PASSES} rebuilds, \
+                 dropped {dropped} messages, does a view call `Cx::message_self` on every rebuild?"
+            );
         }
     }
-}
 
-impl<T: 'static, V: View<T> + 'static, F: FnMut(&mut T) -> V + 'static> AppRunner for App<T, V, F> {
-    // For now we handle the message synchronously, but it would also
-    // make sense to to batch them (for example with requestAnimFrame).
-    fn handle_message(&self, message: Message) {
+    /// Pass `message` to the view it's addressed to and rebuild the app.
+    fn update(&self, message: Message) {
         let mut inner_guard = self.0.borrow_mut();
         let inner = &mut *inner_guard;
         if let Some(view) = &mut inner.view {
@@ -96,6 +89,11 @@ impl<T: 'static, V: View<T> + 'static, F: FnMut(&mut T) -> V + 'static> AppRunne
                 message.body,
                 &mut inner.data,
             );
+            #[cfg(debug_assertions)]
+            inner
+                .cx
+                .message_trace
+                .record(&message.id_path, &message_result);
             match message_result {
                 MessageResult::Nop | MessageResult::Action(_) => {
                     // Nothing to do.
@@ -104,11 +102,17 @@ impl<T: 'static, V: View<T> + 'static, F: FnMut(&mut T) -> V + 'static> AppRunne
                     // TODO force a rebuild?
                 }
                 MessageResult::Stale(_) => {
-                    // TODO perhaps inform the user that a stale request bubbled to the top?
+                    // In debug builds, the message trace warns about it
                 }
             }
 
             let new_view = (inner.app_logic)(&mut inner.data);
+            #[cfg(debug_assertions)]
+            {
+                inner.cx.id_registry.clear();
+                inner.cx.message_trace.start_pass();
+            }
+            inner.cx.perf.start_frame();
             let _changed = new_view.rebuild(
                 &mut inner.cx,
                 view,
@@ -116,13 +120,172 @@ impl<T: 'static, V: View<T> + 'static, F: FnMut(&mut T) -> V + 'static> AppRunne
                 inner.state.as_mut().unwrap(),
                 inner.element.as_mut().unwrap(),
             );
+            inner.cx.perf.end_frame();
             // Not sure we have to do anything on changed, the rebuild
             // traversal should cause the DOM to update.
             *view = new_view;
         }
     }
+}
+
+/// How often the messages posted via [`Cx::message_self`] are delivered in a row, before the
+/// remaining ones are dropped, so that a view posting on every rebuild doesn't hang the app.
+const MAX_DELIVERY_PASSES: usize = 100;
+
+impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppInner<T, V, F> {
+    pub fn new(data: T, app_logic: F) -> Self {
+        let cx = Cx::new();
+        AppInner {
+            data,
+            app_logic,
+            view: None,
+            id: None,
+            state: None,
+            element: None,
+            cx,
+        }
+    }
+
+    fn ensure_app(&mut self, root: &web_sys::HtmlElement) {
+        if self.view.is_none() {
+            let view = (self.app_logic)(&mut self.data);
+            #[cfg(debug_assertions)]
+            {
+                self.cx.id_registry.clear();
+                self.cx.message_trace.start_pass();
+            }
+            self.cx.perf.start_frame();
+            let (id, state, element) = view.build(&mut self.cx);
+            self.cx.perf.end_frame();
+            self.view = Some(view);
+            self.id = Some(id);
+            self.state = Some(state);
+
+            root.append_child(element.as_node_ref()).unwrap();
+            self.element = Some(element);
+        }
+    }
+}
+
+impl<T: 'static, V: View<T> + 'static, F: FnMut(&mut T) -> V + 'static> AppRunner for App<T, V, F> {
+    // For now we handle the message synchronously, but it would also
+    // make sense to to batch them (for example with requestAnimFrame).
+    fn handle_message(&self, message: Message) {
+        self.update(message);
+        self.deliver_pending_messages();
+    }
 
     fn clone_box(&self) -> Box<dyn AppRunner> {
         Box::new(self.clone())
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::any::Any;
+
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use xilem_core::{Id, MessageResult};
+
+    use super::{App, MAX_DELIVERY_PASSES};
+    use crate::{elements::html, ChangeFlags, Cx, View, ViewMarker};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Posts its name to itself when it's built (and rebuilt, if `repost`),
+    /// which is added to the app state when it's delivered.
+    struct PostToSelf {
+        name: &'static str,
+        repost: bool,
+    }
+
+    impl ViewMarker for PostToSelf {}
+    impl View<Vec<&'static str>> for PostToSelf {
+        type State = ();
+        type Element = web_sys::Text;
+
+        fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+            let (id, ()) = cx.with_new_id(|cx| cx.message_self(Box::new(self.name)));
+            (id, (), web_sys::Text::new().unwrap())
+        }
+
+        fn rebuild(
+            &self,
+            cx: &mut Cx,
+            _prev: &Self,
+            id: &mut Id,
+            _state: &mut Self::State,
+            _element: &mut Self::Element,
+        ) -> ChangeFlags {
+            if self.repost {
+                cx.with_id(*id, |cx| cx.message_self(Box::new(self.name)));
+            }
+            ChangeFlags::empty()
+        }
+
+        fn message(
+            &self,
+            id_path: &[Id],
+            _state: &mut Self::State,
+            message: Box<dyn Any>,
+            app_state: &mut Vec<&'static str>,
+        ) -> MessageResult<()> {
+            assert!(id_path.is_empty());
+            app_state.push(*message.downcast().unwrap());
+            MessageResult::Nop
+        }
+    }
+
+    fn run<V: View<Vec<&'static str>> + 'static>(
+        app_logic: impl FnMut(&mut Vec<&'static str>) -> V + 'static,
+    ) -> Vec<&'static str> {
+        let app = App::new(Vec::new(), app_logic);
+        let root = web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("div")
+            .unwrap();
+        app.clone().run(root.unchecked_ref());
+        let data = app.0.borrow().data.clone();
+        data
+    }
+
+    #[wasm_bindgen_test]
+    fn posted_message_is_delivered() {
+        let received = run(|_| PostToSelf {
+            name: "a",
+            repost: false,
+        });
+        assert_eq!(received, ["a"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn posting_on_every_rebuild_is_capped() {
+        let received = run(|_| PostToSelf {
+            name: "a",
+            repost: true,
+        });
+        assert_eq!(received.len(), MAX_DELIVERY_PASSES);
+    }
+
+    #[wasm_bindgen_test]
+    fn message_of_torn_down_view_is_dropped() {
+        // "a" is delivered first, the rebuild afterwards removes "b" before its message is delivered
+        let received = run(|received: &mut Vec<&'static str>| {
+            let b = received.is_empty().then_some(PostToSelf {
+                name: "b",
+                repost: false,
+            });
+            html::div((
+                PostToSelf {
+                    name: "a",
+                    repost: false,
+                },
+                b,
+            ))
+        });
+        assert_eq!(received, ["a"]);
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright 2023 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{cell::RefCell, collections::HashSet, rc::Rc, sync::Arc};
+
 type CowStr = std::borrow::Cow<'static, str>;
 
 #[derive(PartialEq, Clone, Debug, PartialOrd)]
@@ -11,9 +13,57 @@ pub enum AttributeValue {
     F32(f32),
     F64(f64),
     String(CowStr),
+    /// A string that is shared by all equal values, see [`AttributeValue::interned`].
+    ///
+    /// Equal interned strings are the same allocation, which is compared first
+    /// (`Rc<str>` compares pointers before contents), so diffing them is cheap.
+    Interned(Rc<str>),
+    /// A string that is shared with the app, e.g. a large translated text used by many elements.
+    ///
+    /// Like [`AttributeValue::Interned`], values of the same allocation are compared by pointer.
+    Shared(Arc<str>),
+}
+
+thread_local! {
+    static INTERNED: RefCell<Interner> = RefCell::default();
+}
+
+/// The strings of [`AttributeValue::interned`].
+#[derive(Default)]
+struct Interner {
+    strings: HashSet<Rc<str>>,
+    /// The number of strings after which the strings that aren't used anymore are dropped.
+    purge_at: usize,
+}
+
+impl Interner {
+    const MIN_PURGE_AT: usize = 64;
+
+    fn intern(&mut self, value: &str) -> Rc<str> {
+        if let Some(interned) = self.strings.get(value) {
+            return interned.clone();
+        }
+        if self.strings.len() >= self.purge_at {
+            // Only the interner refers to these, so they're not used by any value anymore
+            self.strings.retain(|string| Rc::strong_count(string) > 1);
+            self.purge_at = (self.strings.len() * 2).max(Self::MIN_PURGE_AT);
+        }
+        let interned: Rc<str> = value.into();
+        self.strings.insert(interned.clone());
+        interned
+    }
 }
 
 impl AttributeValue {
+    /// A string value that shares its allocation with all other interned values equal to `value`.
+    ///
+    /// This is useful for values that are repeated in large lists, e.g. the same class of
+    /// thousands of rows, which otherwise each hold their own copy of the string.
+    /// Looking up the value has a cost, so unique values (e.g. ids) shouldn't be interned.
+    pub fn interned(value: &str) -> Self {
+        AttributeValue::Interned(INTERNED.with(|interner| interner.borrow_mut().intern(value)))
+    }
+
     pub fn serialize(&self) -> CowStr {
         match self {
             AttributeValue::True => "".into(), // empty string is equivalent to a true set attribute
@@ -22,6 +72,8 @@ impl AttributeValue {
             AttributeValue::F32(n) => n.to_string().into(),
             AttributeValue::F64(n) => n.to_string().into(),
             AttributeValue::String(s) => s.clone(),
+            AttributeValue::Interned(s) => s.to_string().into(),
+            AttributeValue::Shared(s) => s.to_string().into(),
         }
     }
 }
@@ -93,3 +145,64 @@ impl IntoAttributeValue for &'static str {
         Some(AttributeValue::String(self.into()))
     }
 }
+
+impl IntoAttributeValue for Rc<str> {
+    fn into_attr_value(self) -> Option<AttributeValue> {
+        Some(AttributeValue::Interned(self))
+    }
+}
+
+impl IntoAttributeValue for Arc<str> {
+    fn into_attr_value(self) -> Option<AttributeValue> {
+        Some(AttributeValue::Shared(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{AttributeValue, INTERNED};
+
+    fn interned_str(value: &AttributeValue) -> &Rc<str> {
+        match value {
+            AttributeValue::Interned(string) => string,
+            _ => panic!("{value:?} isn't interned"),
+        }
+    }
+
+    #[test]
+    fn equal_values_share_one_string() {
+        // e.g. the class of the rows of a large list
+        let values: Vec<_> = (0..10_000)
+            .map(|_| AttributeValue::interned(&format!("row-{}", "even")))
+            .collect();
+        let first = interned_str(&values[0]);
+        assert!(values
+            .iter()
+            .all(|value| Rc::ptr_eq(interned_str(value), first)));
+        // one allocation of the string for all values, instead of one per value
+        assert_eq!(Rc::strong_count(first), values.len() + 1);
+        assert_eq!(values[0].serialize(), "row-even");
+
+        let other = AttributeValue::interned("row-odd");
+        assert_ne!(values[0], other);
+    }
+
+    #[test]
+    fn unused_strings_are_dropped() {
+        for n in 0..1000 {
+            AttributeValue::interned(&format!("unique-{n}"));
+        }
+        let kept = AttributeValue::interned("kept");
+        for n in 1000..2000 {
+            AttributeValue::interned(&format!("unique-{n}"));
+        }
+        let len = INTERNED.with(|interner| interner.borrow().strings.len());
+        assert!(len < 200, "{len} strings are still interned");
+        assert!(Rc::ptr_eq(
+            interned_str(&kept),
+            interned_str(&AttributeValue::interned("kept"))
+        ));
+    }
+}
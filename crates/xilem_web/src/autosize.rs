@@ -0,0 +1,186 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Growing a `<textarea>` with its content.
+
+use std::{any::Any, marker::PhantomData};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, HtmlTextAreaElement},
+    view::DomNode,
+    ChangeFlags, Cx, View, ViewMarker,
+};
+
+/// Resizes a `<textarea>` to its content, see [`HtmlTextAreaElement::autosize`].
+pub struct Autosize<E, T, A = ()> {
+    element: E,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A> Autosize<E, T, A> {
+    pub(crate) fn new(element: E) -> Self {
+        Autosize {
+            element,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct AutosizeState<S> {
+    child_state: S,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// The height (in px) of a textarea with the vertical `borders` that shows `scroll_height` of content,
+/// capped at `max_height`, and whether the content has to scroll, as it doesn't fit.
+fn autosize_height(scroll_height: f64, borders: f64, max_height: Option<f64>) -> (f64, bool) {
+    let height = scroll_height + borders;
+    match max_height {
+        Some(max_height) if height > max_height => (max_height, true),
+        _ => (height, false),
+    }
+}
+
+/// Set the height of `textarea` to the height of its content, unless it isn't in the document (yet).
+fn resize(textarea: &web_sys::HtmlTextAreaElement) {
+    if !textarea.is_connected() {
+        return;
+    }
+    let style = textarea.style();
+    // Measure the content without the previous height, so that the textarea can also shrink
+    style.set_property("height", "auto").unwrap_throw();
+    let computed = web_sys::window()
+        .unwrap_throw()
+        .get_computed_style(textarea)
+        .unwrap_throw();
+    // `max-height` is "none" (which doesn't parse) when there's no cap
+    let max_height = computed
+        .and_then(|computed| computed.get_property_value("max-height").ok())
+        .and_then(|max_height| max_height.strip_suffix("px")?.parse().ok());
+    let borders = (textarea.offset_height() - textarea.client_height()) as f64;
+    let (height, scroll) = autosize_height(textarea.scroll_height() as f64, borders, max_height);
+    style
+        .set_property("height", &format!("{height}px"))
+        .unwrap_throw();
+    style
+        .set_property("overflow-y", if scroll { "auto" } else { "hidden" })
+        .unwrap_throw();
+}
+
+/// Resize `node` once the current build or rebuild is done, i.e. when its content is applied,
+/// and it has been inserted into the document.
+fn schedule_resize(node: &web_sys::Node) {
+    let textarea: web_sys::HtmlTextAreaElement = node.clone().unchecked_into();
+    wasm_bindgen_futures::spawn_local(async move { resize(&textarea) });
+}
+
+fn create_input_listener(node: &web_sys::Node) -> EventListener {
+    EventListener::new(node, "input", |event| {
+        if let Some(textarea) = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+        {
+            resize(&textarea);
+        }
+    })
+}
+
+impl<E, T, A> ViewMarker for Autosize<E, T, A> {}
+impl<E, T, A> Sealed for Autosize<E, T, A> {}
+
+impl<E: HtmlTextAreaElement<T, A>, T, A> View<T, A> for Autosize<E, T, A> {
+    type State = AutosizeState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, child_state, element) = self.element.build(cx);
+        let node = element.as_node_ref();
+        schedule_resize(node);
+        let state = AutosizeState {
+            child_state,
+            listener: create_input_listener(node),
+        };
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changed = self
+            .element
+            .rebuild(cx, &prev.element, id, &mut state.child_state, element);
+        if changed.contains(ChangeFlags::STRUCTURE) {
+            state.listener = create_input_listener(element.as_node_ref());
+        }
+        if !changed.is_empty() {
+            schedule_resize(element.as_node_ref());
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.element
+            .message(id_path, &mut state.child_state, message, app_state)
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(HtmlTextAreaElement, Autosize);
+
+#[cfg(test)]
+mod tests {
+    use super::autosize_height;
+
+    #[test]
+    fn height_is_capped_by_max_height() {
+        assert_eq!(autosize_height(40.0, 2.0, None), (42.0, false));
+        assert_eq!(autosize_height(40.0, 2.0, Some(100.0)), (42.0, false));
+        // the content scrolls when it's higher than the cap
+        assert_eq!(autosize_height(140.0, 2.0, Some(100.0)), (100.0, true));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn height_grows_with_content() {
+        use wasm_bindgen::{JsCast, JsValue};
+
+        use crate::{elements::html, interfaces::HtmlTextAreaElement as _, Cx, View};
+
+        async fn after_rebuild() {
+            let resolved = js_sys::Promise::resolve(&JsValue::UNDEFINED);
+            wasm_bindgen_futures::JsFuture::from(resolved)
+                .await
+                .unwrap();
+        }
+
+        let mut cx = Cx::new();
+        let short = html::textarea::<(), (), _>("one line".to_string()).autosize();
+        let (mut id, mut state, mut element) = View::build(&short, &mut cx);
+        crate::document_body().append_child(&element).unwrap();
+        after_rebuild().await;
+        let textarea: &web_sys::HtmlTextAreaElement = element.unchecked_ref();
+        let one_line = textarea.offset_height();
+
+        let long = html::textarea::<(), (), _>("one\ntwo\nthree\nfour".to_string()).autosize();
+        View::rebuild(&long, &mut cx, &short, &mut id, &mut state, &mut element);
+        after_rebuild().await;
+        let textarea: &web_sys::HtmlTextAreaElement = element.unchecked_ref();
+        assert!(textarea.offset_height() > one_line);
+        element.remove();
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Asking the user to confirm leaving the page, e.g. when there are unsaved changes, see [`before_unload`].
+
+use std::{any::Any, marker::PhantomData};
+
+use gloo::events::{EventListener, EventListenerOptions};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{interfaces::sealed::Sealed, ChangeFlags, Cx, View, ViewMarker};
+
+pub struct BeforeUnload<F, T, A = ()> {
+    should_warn: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct BeforeUnloadState {
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// Show the native prompt of the browser, which asks the user to confirm leaving (or reloading) the page,
+/// when `should_warn` returns `true` for the app state at that moment, e.g. when there are unsaved changes.
+///
+/// The prompt is requested by cancelling the `beforeunload` event and setting its `returnValue`,
+/// which older browsers need. Browsers show their own message, a custom one can't be set.
+/// They may also skip the prompt, e.g. when the user hasn't interacted with the page.
+///
+/// The listener is removed when the view is removed. As pages with a `beforeunload` listener aren't kept
+/// in the back/forward cache of some browsers, the view should only be used while a warning may be needed:
+///
+/// ```ignore
+/// state.has_unsaved_changes.then(|| before_unload(|state: &AppState| state.has_unsaved_changes))
+/// ```
+///
+/// The view itself is rendered as an empty text node.
+pub fn before_unload<F, T, A>(should_warn: F) -> BeforeUnload<F, T, A>
+where
+    F: Fn(&T) -> bool,
+{
+    BeforeUnload {
+        should_warn,
+        phantom: PhantomData,
+    }
+}
+
+/// Sent by the listener, `should_warn` is called with the app state when the message is handled,
+/// which happens while the event is dispatched.
+struct BeforeUnloadRequested(web_sys::BeforeUnloadEvent);
+
+impl<F, T, A> ViewMarker for BeforeUnload<F, T, A> {}
+impl<F, T, A> Sealed for BeforeUnload<F, T, A> {}
+
+impl<F, T, A> View<T, A> for BeforeUnload<F, T, A>
+where
+    F: Fn(&T) -> bool,
+{
+    type State = BeforeUnloadState;
+    type Element = web_sys::Text;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, thunk) = cx.with_new_id(|cx| cx.message_thunk());
+        let window = web_sys::window().unwrap_throw();
+        // Not passive, so that the event can be cancelled
+        let listener = EventListener::new_with_options(
+            &window,
+            "beforeunload",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                let event = event.clone().unchecked_into();
+                thunk.push_message(BeforeUnloadRequested(event));
+            },
+        );
+        let element = web_sys::Text::new().unwrap_throw();
+        (id, BeforeUnloadState { listener }, element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        _element: &mut Self::Element,
+    ) -> ChangeFlags {
+        // `should_warn` is only called when the event is dispatched
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        if !id_path.is_empty() {
+            return MessageResult::Stale(message);
+        }
+        match message.downcast::<BeforeUnloadRequested>() {
+            Ok(requested) => {
+                if (self.should_warn)(app_state) {
+                    requested.0.prevent_default();
+                    requested.0.set_return_value("");
+                }
+                MessageResult::Nop
+            }
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn unload_is_cancelled_when_warning() {
+        use super::before_unload;
+        use crate::test_util::mount;
+
+        let mut harness = mount(
+            false,
+            before_unload::<_, _, ()>(|has_unsaved_changes: &bool| *has_unsaved_changes),
+        );
+        let window = web_sys::window().unwrap();
+        let unload = || {
+            let mut init = web_sys::EventInit::new();
+            init.cancelable(true);
+            web_sys::Event::new_with_event_init_dict("beforeunload", &init).unwrap()
+        };
+
+        let event = unload();
+        window.dispatch_event(&event).unwrap();
+        harness.handle_messages();
+        assert!(!event.default_prevented());
+
+        // The harness handles the message after the dispatch, but the event can still be cancelled
+        *harness.app_state_mut() = true;
+        let event = unload();
+        window.dispatch_event(&event).unwrap();
+        harness.handle_messages();
+        assert!(event.default_prevented());
+    }
+}
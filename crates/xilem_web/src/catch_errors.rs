@@ -0,0 +1,484 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Isolating failures in a subtree, by rendering a fallback view instead of it.
+
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::MessageThunk, interfaces::sealed::Sealed, view::DomNode, ChangeFlags, Cx, View,
+    ViewMarker,
+};
+
+/// A panic or a reported error that was caught by [`catch_errors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaughtError {
+    message: Option<String>,
+}
+
+impl CaughtError {
+    fn from_panic(payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned());
+        CaughtError { message }
+    }
+
+    fn reported(message: String) -> Self {
+        CaughtError {
+            message: Some(message),
+        }
+    }
+
+    /// The message passed to `panic!` (if it's a string) or to [`Cx::report_error`].
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+/// Reports errors to a [`catch_errors`] view after its child was built, see [`Cx::error_reporter`].
+pub struct ErrorReporter(MessageThunk);
+
+/// The message sent by an [`ErrorReporter`].
+struct ReportedError(String);
+
+impl ErrorReporter {
+    pub(crate) fn new(thunk: MessageThunk) -> Self {
+        ErrorReporter(thunk)
+    }
+
+    /// Render the fallback of the `catch_errors` view instead of its child, from the next rebuild on.
+    pub fn report(&self, message: impl Into<String>) {
+        self.0.push_message(ReportedError(message.into()));
+    }
+}
+
+pub struct CatchErrors<V, F, T, A = (), K = ()> {
+    child: V,
+    fallback: F,
+    retry_key: K,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct CatchErrorsState<S, E, FV, FS, FE> {
+    content: CatchErrorsContent<S, E, FV, FS, FE>,
+    /// An error sent by an [`ErrorReporter`], the fallback is rendered on the next rebuild.
+    reported: Option<CaughtError>,
+}
+
+enum CatchErrorsContent<S, E, FV, FS, FE> {
+    Child {
+        id: Id,
+        state: S,
+        element: E,
+    },
+    /// The fallback view is kept, as it's not part of [`CatchErrors`], but needed to rebuild it.
+    Fallback {
+        error: CaughtError,
+        view: FV,
+        id: Id,
+        state: FS,
+        element: FE,
+    },
+}
+
+impl<S, E: DomNode, FV, FS, FE: DomNode> CatchErrorsContent<S, E, FV, FS, FE> {
+    fn node(&self) -> &web_sys::Node {
+        match self {
+            CatchErrorsContent::Child { element, .. } => element.as_node_ref(),
+            CatchErrorsContent::Fallback { element, .. } => element.as_node_ref(),
+        }
+    }
+}
+
+/// Render `child`, and when it fails, render the view that `fallback` returns for the
+/// [`CaughtError`] instead.
+///
+/// The child fails when its `build` or `rebuild`
+/// * calls [`Cx::report_error`],
+/// * or panics, which can only be caught when the app is compiled with `panic = "unwind"`.
+///   That isn't the default for `wasm32-unknown-unknown`, otherwise a panic aborts the app as before.
+///
+/// Errors that occur later, e.g. in an event handler, can be reported with the
+/// [`ErrorReporter`] returned by [`Cx::error_reporter`], the fallback is then rendered
+/// on the next rebuild.
+///
+/// The state of a child that failed is dropped (e.g. its event listeners are removed),
+/// and the ids and element props it added to the [`Cx`] are removed again.
+///
+/// While the fallback is shown, the child is only built again when the key passed to
+/// [`CatchErrors::retry_on`] changed, otherwise the fallback is shown until the
+/// `catch_errors` view itself is built again.
+pub fn catch_errors<V, F, FV, T, A>(child: V, fallback: F) -> CatchErrors<V, F, T, A>
+where
+    V: View<T, A>,
+    F: Fn(&CaughtError) -> FV,
+    FV: View<T, A>,
+{
+    CatchErrors {
+        child,
+        fallback,
+        retry_key: (),
+        phantom: PhantomData,
+    }
+}
+
+impl<V, F, T, A, K> CatchErrors<V, F, T, A, K> {
+    /// Build the child again while the fallback is shown, when `key` differs from the previous
+    /// rebuild, e.g. the part of the app state that the child is built from.
+    ///
+    /// Every retry builds the child from scratch, so the key should only change when
+    /// the child may succeed again.
+    pub fn retry_on<RK: PartialEq>(self, key: RK) -> CatchErrors<V, F, T, A, RK> {
+        CatchErrors {
+            child: self.child,
+            fallback: self.fallback,
+            retry_key: key,
+            phantom: PhantomData,
+        }
+    }
+}
+
+type Content<V, FV, T, A> = CatchErrorsContent<
+    <V as View<T, A>>::State,
+    <V as View<T, A>>::Element,
+    FV,
+    <FV as View<T, A>>::State,
+    <FV as View<T, A>>::Element,
+>;
+
+impl<V, F, FV, T, A, K> CatchErrors<V, F, T, A, K>
+where
+    V: View<T, A>,
+    F: Fn(&CaughtError) -> FV,
+    FV: View<T, A>,
+{
+    /// Run `f`, which builds or rebuilds the child, returns the error when it panicked or
+    /// reported one. In the latter case the result of `f` (e.g. the state of the child) is dropped.
+    fn try_child<R>(cx: &mut Cx, f: impl FnOnce(&mut Cx) -> R) -> Result<R, CaughtError> {
+        match cx.with_error_boundary(|cx| cx.catch_panic(f)) {
+            (Ok(result), None) => Ok(result),
+            (Ok(_), Some(message)) => Err(CaughtError::reported(message)),
+            (Err(payload), _) => Err(CaughtError::from_panic(&*payload)),
+        }
+    }
+
+    fn build_content(&self, cx: &mut Cx) -> Content<V, FV, T, A> {
+        match Self::try_child(cx, |cx| self.child.build(cx)) {
+            Ok((id, state, element)) => CatchErrorsContent::Child { id, state, element },
+            Err(error) => self.build_fallback(cx, error),
+        }
+    }
+
+    fn build_fallback(&self, cx: &mut Cx, error: CaughtError) -> Content<V, FV, T, A> {
+        let view = (self.fallback)(&error);
+        let (id, state, element) = view.build(cx);
+        CatchErrorsContent::Fallback {
+            error,
+            view,
+            id,
+            state,
+            element,
+        }
+    }
+}
+
+impl<V, F, T, A, K> ViewMarker for CatchErrors<V, F, T, A, K> {}
+impl<V, F, T, A, K> Sealed for CatchErrors<V, F, T, A, K> {}
+
+impl<V, F, FV, T, A, K> View<T, A> for CatchErrors<V, F, T, A, K>
+where
+    V: View<T, A>,
+    F: Fn(&CaughtError) -> FV,
+    FV: View<T, A>,
+    K: PartialEq,
+{
+    type State = CatchErrorsState<V::State, V::Element, FV, FV::State, FV::Element>;
+    type Element = web_sys::Node;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, content) = cx.with_new_id(|cx| self.build_content(cx));
+        let element = content.node().clone();
+        let state = CatchErrorsState {
+            content,
+            reported: None,
+        };
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = cx.with_id(*id, |cx| {
+            if let Some(error) = state.reported.take() {
+                if let CatchErrorsContent::Child { .. } = state.content {
+                    state.content = self.build_fallback(cx, error);
+                    return ChangeFlags::STRUCTURE;
+                }
+            }
+            match &mut state.content {
+                CatchErrorsContent::Child {
+                    id,
+                    state: child_state,
+                    element,
+                } => {
+                    let rebuilt = Self::try_child(cx, |cx| {
+                        self.child
+                            .rebuild(cx, &prev.child, id, child_state, element)
+                    });
+                    match rebuilt {
+                        Ok(changed) => changed,
+                        Err(error) => {
+                            state.content = self.build_fallback(cx, error);
+                            ChangeFlags::STRUCTURE
+                        }
+                    }
+                }
+                CatchErrorsContent::Fallback {
+                    error,
+                    view,
+                    id,
+                    state: fallback_state,
+                    element,
+                } => {
+                    if self.retry_key != prev.retry_key {
+                        match Self::try_child(cx, |cx| self.child.build(cx)) {
+                            Ok((id, child_state, element)) => {
+                                state.content = CatchErrorsContent::Child {
+                                    id,
+                                    state: child_state,
+                                    element,
+                                };
+                                return ChangeFlags::STRUCTURE;
+                            }
+                            Err(new_error) => *error = new_error,
+                        }
+                    }
+                    let new_view = (self.fallback)(error);
+                    let changed = new_view.rebuild(cx, view, id, fallback_state, element);
+                    *view = new_view;
+                    changed
+                }
+            }
+        });
+
+        let node = state.content.node();
+        if node != element {
+            *element = node.clone();
+            changed |= ChangeFlags::STRUCTURE;
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match (id_path, &mut state.content) {
+            ([], _) => match message.downcast::<ReportedError>() {
+                Ok(reported) => {
+                    state.reported = Some(CaughtError::reported(reported.0));
+                    MessageResult::RequestRebuild
+                }
+                Err(message) => MessageResult::Stale(message),
+            },
+            ([first, rest_path @ ..], CatchErrorsContent::Child { id, state, .. })
+                if first == id =>
+            {
+                self.child.message(rest_path, state, message, app_state)
+            }
+            (
+                [first, rest_path @ ..],
+                CatchErrorsContent::Fallback {
+                    view, id, state, ..
+                },
+            ) if first == id => view.message(rest_path, state, message, app_state),
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaughtError;
+
+    #[test]
+    fn panic_message_is_extracted() {
+        let payload = std::panic::catch_unwind(|| panic!("static message")).unwrap_err();
+        assert_eq!(
+            CaughtError::from_panic(&*payload).message(),
+            Some("static message")
+        );
+
+        let n = 13;
+        let payload = std::panic::catch_unwind(|| panic!("formatted {n}")).unwrap_err();
+        assert_eq!(
+            CaughtError::from_panic(&*payload).message(),
+            Some("formatted 13")
+        );
+
+        let payload = std::panic::catch_unwind(|| std::panic::panic_any(13)).unwrap_err();
+        assert_eq!(CaughtError::from_panic(&*payload).message(), None);
+    }
+
+    // Panics abort by default on `wasm32-unknown-unknown`
+    #[cfg(all(target_arch = "wasm32", panic = "unwind"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn fallback_is_rendered_on_panic() {
+        use super::catch_errors;
+        use crate::{elements::html, lazy, Cx, View};
+
+        let counter = |n: u32| {
+            catch_errors(
+                lazy(true, move || {
+                    assert_ne!(n, 13, "unlucky number");
+                    html::span::<(), (), _>(n.to_string())
+                }),
+                |error| html::em(format!("failed: {}", error.message().unwrap_or_default())),
+            )
+            .retry_on(n)
+        };
+
+        let mut cx = Cx::new();
+        let first = counter(12);
+        let (mut id, mut state, mut element) = View::build(&first, &mut cx);
+        assert_eq!(element.text_content().as_deref(), Some("12"));
+
+        let unlucky = counter(13);
+        let changed = View::rebuild(&unlucky, &mut cx, &first, &mut id, &mut state, &mut element);
+        assert!(changed.contains(crate::ChangeFlags::STRUCTURE));
+        assert_eq!(element.node_name(), "EM");
+        assert!(cx.id_path().is_empty());
+
+        // the child is shown again when it doesn't panic anymore
+        let next = counter(14);
+        View::rebuild(&next, &mut cx, &unlucky, &mut id, &mut state, &mut element);
+        assert_eq!(element.text_content().as_deref(), Some("14"));
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod browser_tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use xilem_core::{Id, MessageResult};
+
+    use super::{catch_errors, ErrorReporter};
+    use crate::{
+        elements::html, interfaces::sealed::Sealed, test_util::mount, ChangeFlags, Cx, View,
+        ViewMarker,
+    };
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Shows `n`, but reports an error when it's 13, and keeps its [`ErrorReporter`] in `reporter`.
+    struct Flaky {
+        n: u32,
+        builds: Rc<RefCell<u32>>,
+        reporter: Rc<RefCell<Option<ErrorReporter>>>,
+    }
+
+    impl Flaky {
+        fn check(&self, cx: &mut Cx) {
+            if self.n == 13 {
+                cx.report_error("unlucky number");
+            }
+            *self.reporter.borrow_mut() = cx.error_reporter();
+        }
+    }
+
+    impl ViewMarker for Flaky {}
+    impl Sealed for Flaky {}
+
+    impl View<()> for Flaky {
+        type State = ();
+        type Element = web_sys::Text;
+
+        fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+            *self.builds.borrow_mut() += 1;
+            self.check(cx);
+            (
+                Id::next(),
+                (),
+                cx.document().create_text_node(&self.n.to_string()),
+            )
+        }
+
+        fn rebuild(
+            &self,
+            cx: &mut Cx,
+            _prev: &Self,
+            _id: &mut Id,
+            _state: &mut Self::State,
+            element: &mut Self::Element,
+        ) -> ChangeFlags {
+            self.check(cx);
+            element.set_data(&self.n.to_string());
+            ChangeFlags::OTHER_CHANGE
+        }
+
+        fn message(
+            &self,
+            _id_path: &[Id],
+            _state: &mut Self::State,
+            message: Box<dyn std::any::Any>,
+            _app_state: &mut (),
+        ) -> MessageResult<()> {
+            MessageResult::Stale(message)
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn fallback_is_rendered_on_reported_error() {
+        let builds = Rc::new(RefCell::new(0));
+        let reporter = Rc::new(RefCell::new(None));
+        let counter = |n: u32| {
+            let child = Flaky {
+                n,
+                builds: builds.clone(),
+                reporter: reporter.clone(),
+            };
+            let fallback = |error: &super::CaughtError| {
+                html::em(format!("failed: {}", error.message().unwrap_or_default()))
+            };
+            html::div(catch_errors(child, fallback).retry_on(n))
+        };
+
+        let mut harness = mount((), counter(12));
+        assert_eq!(harness.root_element().text_content().unwrap(), "12");
+
+        harness.rebuild(counter(13));
+        let div = harness.root_element();
+        assert_eq!(div.first_element_child().unwrap().tag_name(), "EM");
+        assert_eq!(div.text_content().unwrap(), "failed: unlucky number");
+        assert!(harness.cx().id_path().is_empty());
+
+        // the child isn't built again while the retry key is the same
+        let builds_before = *builds.borrow();
+        harness.rebuild(counter(13));
+        assert_eq!(*builds.borrow(), builds_before);
+        assert_eq!(div.text_content().unwrap(), "failed: unlucky number");
+
+        harness.rebuild(counter(14));
+        assert_eq!(div.text_content().unwrap(), "14");
+
+        // errors that occur after the rebuild are rendered on the next one
+        let reported = reporter.borrow_mut().take().unwrap();
+        reported.report("lost connection");
+        harness.handle_messages();
+        harness.rebuild(counter(14));
+        assert_eq!(div.text_content().unwrap(), "failed: lost connection");
+    }
+}
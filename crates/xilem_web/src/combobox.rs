@@ -0,0 +1,255 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! An `<input>` with autocomplete suggestions from a `<datalist>`, see [`combobox`].
+
+use std::{borrow::Cow, marker::PhantomData};
+
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::{ChangeFlags, Cx, HtmlProps},
+    elements::html,
+    interfaces::{sealed::Sealed, Element, HtmlElement, HtmlInputElement, HtmlSpanElement},
+    view::{DomNode, View, ViewMarker},
+    Attr, AttributeValue, OnCommittedInput, OptionalAction, HTML_NS,
+};
+
+type CowStr = Cow<'static, str>;
+
+/// The `<option>` view of a suggestion of a [`combobox`].
+pub type ComboboxSuggestion<T, A = ()> = Attr<html::OptionElement<T, A, ()>, T, A>;
+
+/// The `<input>` view of a [`combobox`], with the `on_change` callback `F`.
+pub type ComboboxInput<T, A, F> = OnCommittedInput<Attr<html::Input<T, A, ()>, T, A>, T, A, F>;
+
+pub struct Combobox<I, T, A = ()> {
+    input: I,
+    datalist: html::Datalist<T, A, Vec<ComboboxSuggestion<T, A>>>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct ComboboxState<IS, IE, DS, DE> {
+    list_id: CowStr,
+    props: HtmlProps,
+    input_view_id: Id,
+    input_state: IS,
+    input_element: IE,
+    datalist_view_id: Id,
+    datalist_state: DS,
+    datalist_element: DE,
+    has_debug_id: bool,
+}
+
+/// A `<span>` with an `<input>` with the value `value`, which suggests the `suggestions`
+/// from a `<datalist>` while typing. `on_change` is called with the new value,
+/// when the user typed (with [`on_committed_input`](HtmlElement::on_committed_input))
+/// or picked a suggestion.
+///
+/// A unique `id` is generated for the datalist and the `list` attribute of the input is set to it.
+/// The `id` is generated once, when the view is built, and kept across rebuilds.
+///
+/// The suggestions are `<option>` elements that are updated in place on rebuild, so only
+/// the values of the suggestions that changed are written, e.g. when they're filtered by the value.
+/// The browser may show fewer suggestions, as it filters them by the value itself.
+pub fn combobox<T, A, F, OA>(
+    value: impl Into<CowStr>,
+    suggestions: impl IntoIterator<Item = impl Into<CowStr>>,
+    on_change: F,
+) -> Combobox<ComboboxInput<T, A, F>, T, A>
+where
+    F: Fn(&mut T, String) -> OA,
+    OA: OptionalAction<A>,
+{
+    let input = html::input(()).attr("value", value.into());
+    let suggestions = suggestions
+        .into_iter()
+        .map(|suggestion| html::option(()).attr("value", suggestion.into()))
+        .collect();
+    Combobox {
+        input: input.on_committed_input(on_change),
+        datalist: html::datalist(suggestions),
+        phantom: PhantomData,
+    }
+}
+
+/// The `id` attribute used for the datalist of the [`Combobox`] view with the given `id`,
+/// i.e. its [`Cx::unique_id`].
+fn list_id(id: Id) -> CowStr {
+    crate::context::unique_id("xilem-combobox", id)
+}
+
+impl<I, T, A> ViewMarker for Combobox<I, T, A> {}
+impl<I, T, A> Sealed for Combobox<I, T, A> {}
+
+impl<I, T, A> View<T, A> for Combobox<I, T, A>
+where
+    I: HtmlInputElement<T, A>,
+{
+    type State = ComboboxState<
+        I::State,
+        I::Element,
+        <html::Datalist<T, A, Vec<ComboboxSuggestion<T, A>>> as View<T, A>>::State,
+        web_sys::HtmlDataListElement,
+    >;
+    type Element = web_sys::HtmlSpanElement;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let id = Id::next();
+        let list_id = list_id(id);
+
+        let (el, props) = cx.build_element(HTML_NS, "span");
+
+        cx.add_attr_to_element(
+            &"list".into(),
+            &Some(AttributeValue::String(list_id.clone())),
+        );
+        let (input_view_id, input_state, input_element) = cx.with_id(id, |cx| self.input.build(cx));
+        el.append_child(input_element.as_node_ref()).unwrap_throw();
+
+        cx.add_attr_to_element(&"id".into(), &Some(AttributeValue::String(list_id.clone())));
+        let (datalist_view_id, datalist_state, datalist_element) =
+            cx.with_id(id, |cx| self.datalist.build(cx));
+        el.append_child(datalist_element.as_node_ref())
+            .unwrap_throw();
+
+        let mut has_debug_id = false;
+        cx.update_debug_id(&el, id, &mut has_debug_id);
+
+        let state = ComboboxState {
+            list_id,
+            props,
+            input_view_id,
+            input_state,
+            input_element,
+            datalist_view_id,
+            datalist_state,
+            datalist_element,
+            has_debug_id,
+        };
+        (id, state, el.dyn_into().unwrap_throw())
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = cx.rebuild_element(element, &mut state.props);
+        cx.update_debug_id(element, *id, &mut state.has_debug_id);
+
+        cx.add_attr_to_element(
+            &"list".into(),
+            &Some(AttributeValue::String(state.list_id.clone())),
+        );
+        let prev_input = state.input_element.as_node_ref().clone();
+        changed |= cx.with_id(*id, |cx| {
+            self.input.rebuild(
+                cx,
+                &prev.input,
+                &mut state.input_view_id,
+                &mut state.input_state,
+                &mut state.input_element,
+            )
+        });
+        // the input may have been replaced with a new element
+        let input = state.input_element.as_node_ref();
+        if *input != prev_input {
+            element.replace_child(input, &prev_input).unwrap_throw();
+        }
+
+        cx.add_attr_to_element(
+            &"id".into(),
+            &Some(AttributeValue::String(state.list_id.clone())),
+        );
+        changed |= cx.with_id(*id, |cx| {
+            self.datalist.rebuild(
+                cx,
+                &prev.datalist,
+                &mut state.datalist_view_id,
+                &mut state.datalist_state,
+                &mut state.datalist_element,
+            )
+        });
+        changed.remove(ChangeFlags::STRUCTURE);
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path.split_first() {
+            Some((first, rest_path)) if *first == state.input_view_id => {
+                self.input
+                    .message(rest_path, &mut state.input_state, message, app_state)
+            }
+            Some((first, rest_path)) if *first == state.datalist_view_id => {
+                self.datalist
+                    .message(rest_path, &mut state.datalist_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+impl<I: HtmlInputElement<T, A>, T, A> Element<T, A> for Combobox<I, T, A> {}
+impl<I: HtmlInputElement<T, A>, T, A> HtmlElement<T, A> for Combobox<I, T, A> {}
+impl<I: HtmlInputElement<T, A>, T, A> HtmlSpanElement<T, A> for Combobox<I, T, A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::list_id;
+    use xilem_core::Id;
+
+    #[test]
+    fn list_id_is_stable() {
+        let id = Id::next();
+        assert_eq!(list_id(id), list_id(id));
+        assert_ne!(list_id(id), list_id(Id::next()));
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn input_list_is_the_datalist_id() {
+        use super::combobox;
+        use crate::test_util::mount;
+
+        let view = |value: &'static str| {
+            combobox::<_, (), _, _>(
+                value,
+                ["Apple", "Apricot", "Avocado"],
+                |value: &mut String, new| {
+                    *value = new;
+                },
+            )
+        };
+        let mut harness = mount(String::new(), view("A"));
+        let span = harness.root_element();
+        let input = span.query_selector("input").unwrap().unwrap();
+        let datalist = span.query_selector("datalist").unwrap().unwrap();
+        let list = input.get_attribute("list").unwrap();
+        assert_eq!(list, datalist.id());
+        assert_eq!(datalist.child_element_count(), 3);
+        assert_eq!(
+            datalist
+                .query_selector("option:nth-child(2)")
+                .unwrap()
+                .unwrap()
+                .get_attribute("value")
+                .as_deref(),
+            Some("Apricot")
+        );
+
+        harness.rebuild(view("Ap"));
+        let input = span.query_selector("input").unwrap().unwrap();
+        assert_eq!(input.get_attribute("list"), Some(list));
+    }
+}
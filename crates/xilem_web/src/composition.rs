@@ -0,0 +1,245 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Text input that is aware of IME compositions (e.g. for CJK input), see [`HtmlElement::on_committed_input`].
+
+use std::{any::Any, cell::Cell, marker::PhantomData, rc::Rc};
+
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    events::normalize_editable_text,
+    interfaces::{sealed::Sealed, HtmlElement},
+    view::DomNode,
+    ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Calls a handler with the text of an element, except while it's being composed, see
+/// [`HtmlElement::on_committed_input`].
+pub struct OnCommittedInput<E, T, A, F> {
+    element: E,
+    handler: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, F> OnCommittedInput<E, T, A, F> {
+    pub(crate) fn new(element: E, handler: F) -> Self {
+        OnCommittedInput {
+            element,
+            handler,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct OnCommittedInputState<S> {
+    child_id: Id,
+    child_state: S,
+    /// The text of the last `compositionend`, to ignore the `input` event Safari fires after it.
+    committed: Option<String>,
+    #[allow(unused)]
+    listeners: [EventListener; 3],
+}
+
+enum TextInput {
+    /// The text after an `input` event outside of a composition.
+    Input(String),
+    /// The text after a composition was committed.
+    Committed(String),
+}
+
+/// The text of `target`, i.e. the value of an `<input>` or `<textarea>`, or the text of a `contenteditable` element.
+fn text_of(target: Option<web_sys::EventTarget>) -> Option<String> {
+    let target = target?;
+    if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+        Some(input.value())
+    } else if let Some(textarea) = target.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        Some(textarea.value())
+    } else {
+        let element = target.dyn_ref::<web_sys::HtmlElement>()?;
+        Some(normalize_editable_text(&element.inner_text()))
+    }
+}
+
+fn create_listeners(node: &web_sys::Node, cx: &Cx) -> [EventListener; 3] {
+    // Older browsers don't set `isComposing` on the `input` events of a composition
+    let composing = Rc::new(Cell::new(false));
+    let thunk = Rc::new(cx.message_thunk());
+    let start = EventListener::new(node, "compositionstart", {
+        let composing = composing.clone();
+        move |_| composing.set(true)
+    });
+    let end = EventListener::new(node, "compositionend", {
+        let composing = composing.clone();
+        let thunk = thunk.clone();
+        move |event| {
+            composing.set(false);
+            if let Some(text) = text_of(event.target()) {
+                thunk.push_message(TextInput::Committed(text));
+            }
+        }
+    });
+    let input = EventListener::new(node, "input", move |event| {
+        let is_composing = event
+            .dyn_ref::<web_sys::InputEvent>()
+            .is_some_and(web_sys::InputEvent::is_composing);
+        if composing.get() || is_composing {
+            return;
+        }
+        if let Some(text) = text_of(event.target()) {
+            thunk.push_message(TextInput::Input(text));
+        }
+    });
+    [start, end, input]
+}
+
+impl<E, T, A, F> ViewMarker for OnCommittedInput<E, T, A, F> {}
+impl<E, T, A, F> Sealed for OnCommittedInput<E, T, A, F> {}
+
+impl<E, T, A, F, OA> View<T, A> for OnCommittedInput<E, T, A, F>
+where
+    E: HtmlElement<T, A>,
+    F: Fn(&mut T, String) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = OnCommittedInputState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            let (child_id, child_state, element) = self.element.build(cx);
+            let listeners = create_listeners(element.as_node_ref(), cx);
+            let state = OnCommittedInputState {
+                child_id,
+                child_state,
+                committed: None,
+                listeners,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            if changed.contains(ChangeFlags::STRUCTURE) {
+                state.listeners = create_listeners(element.as_node_ref(), cx);
+                state.committed = None;
+            }
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] => {
+                let text = match message.downcast::<TextInput>() {
+                    Ok(input) => match *input {
+                        TextInput::Committed(text) => {
+                            state.committed = Some(text.clone());
+                            text
+                        }
+                        TextInput::Input(text) => {
+                            if state.committed.take().as_ref() == Some(&text) {
+                                return MessageResult::Nop;
+                            }
+                            text
+                        }
+                    },
+                    Err(message) => return MessageResult::Stale(message),
+                };
+                match (self.handler)(app_state, text).action() {
+                    Some(action) => MessageResult::Action(action),
+                    None => MessageResult::Nop,
+                }
+            }
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlElement,
+    OnCommittedInput,
+    vars: <F, OA,>,
+    vars_on_ty: <F,>,
+    bounds: {
+        F: Fn(&mut T, String) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn only_committed_text_reaches_handler() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{elements::html, interfaces::HtmlElement as _, test_util::mount};
+
+        fn input(is_composing: bool) -> web_sys::Event {
+            let mut init = web_sys::InputEventInit::new();
+            init.is_composing(is_composing).bubbles(true);
+            web_sys::InputEvent::new_with_event_init_dict("input", &init)
+                .unwrap()
+                .into()
+        }
+
+        fn composition(name: &str) -> web_sys::Event {
+            let mut init = web_sys::CompositionEventInit::new();
+            init.bubbles(true);
+            web_sys::CompositionEvent::new_with_event_init_dict(name, &init)
+                .unwrap()
+                .into()
+        }
+
+        let view = html::input::<Vec<String>, (), _>(())
+            .on_committed_input(|texts: &mut Vec<String>, text| texts.push(text));
+        let mut harness = mount(vec![], view);
+        let element: web_sys::HtmlInputElement = harness.root_element().unchecked_into();
+
+        element.set_value("a");
+        harness.dispatch_event("input", &input(false));
+
+        harness.dispatch_event("input", &composition("compositionstart"));
+        for text in ["aに", "aにほ", "aにほん"] {
+            element.set_value(text);
+            harness.dispatch_event("input", &input(true));
+        }
+        element.set_value("a日本");
+        harness.dispatch_event("input", &composition("compositionend"));
+        // Safari fires the last `input` of a composition after `compositionend`
+        harness.dispatch_event("input", &input(false));
+        assert_eq!(*harness.app_state(), ["a", "a日本"]);
+
+        element.set_value("a日本b");
+        harness.dispatch_event("input", &input(false));
+        assert_eq!(*harness.app_state(), ["a", "a日本", "a日本b"]);
+    }
+}
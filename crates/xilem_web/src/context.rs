@@ -1,7 +1,7 @@
 // Copyright 2023 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use std::any::Any;
+use std::{any::Any, cell::RefCell, collections::VecDeque, rc::Rc};
 
 use bitflags::bitflags;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
@@ -9,9 +9,20 @@ use web_sys::Document;
 
 use xilem_core::{Id, IdPath};
 
+#[cfg(debug_assertions)]
+use crate::message_trace::{MessageTrace, RoutedMessage};
 use crate::{
     app::AppRunner,
+    catch_errors::ErrorReporter,
+    deferred::DeferredAttributes,
     diff::{diff_kv_iterables, Diff},
+    dom_op_log::{DomOp, DomOpLog},
+    head::{HeadKey, HeadNode, HeadNodes},
+    location::LocationInfo,
+    media_query::{match_media, ColorScheme, PREFERS_DARK},
+    node_pool::NodePool,
+    perf_stats::{PerfCounters, PerfStats},
+    storage::{StorageKind, WebStorage},
     vecmap::VecMap,
     view::DomNode,
     AttributeValue, Message, Pod,
@@ -27,15 +38,18 @@ pub struct HtmlProps {
 }
 
 impl HtmlProps {
-    fn apply(&mut self, el: &web_sys::Element) -> Self {
+    /// Apply all props to the newly created element `el`, returns the applied props and the number of DOM writes.
+    fn apply(&mut self, el: &web_sys::Element) -> (Self, usize) {
         let attributes = self.apply_attributes(el);
         let classes = self.apply_classes(el);
         let styles = self.apply_styles(el);
-        Self {
+        let writes = attributes.len() + classes.len() + styles.len();
+        let props = Self {
             attributes,
             classes,
             styles,
-        }
+        };
+        (props, writes)
     }
 
     fn apply_attributes(&mut self, element: &web_sys::Element) -> VecMap<CowStr, AttributeValue> {
@@ -65,100 +79,111 @@ impl HtmlProps {
         styles
     }
 
-    fn apply_changes(&mut self, element: &web_sys::Element, props: &mut HtmlProps) -> ChangeFlags {
+    /// Apply the difference between `props` (the previous props of `element`) and these props to `element`.
+    ///
+    /// Returns the number of DOM writes.
+    fn apply_changes(&mut self, element: &web_sys::Element, props: &mut HtmlProps) -> usize {
         self.apply_attribute_changes(element, &mut props.attributes)
-            | self.apply_class_changes(element, &mut props.classes)
-            | self.apply_style_changes(element, &mut props.styles)
+            + self.apply_class_changes(element, &mut props.classes)
+            + self.apply_style_changes(element, &mut props.styles)
     }
 
     pub(crate) fn apply_attribute_changes(
         &mut self,
         element: &web_sys::Element,
         attributes: &mut VecMap<CowStr, AttributeValue>,
-    ) -> ChangeFlags {
-        let mut changed = ChangeFlags::empty();
-        // update attributes
-        for itm in diff_kv_iterables(&*attributes, &self.attributes) {
-            match itm {
-                Diff::Add(name, value) | Diff::Change(name, value) => {
-                    set_attribute(element, name, &value.serialize());
-                    changed |= ChangeFlags::OTHER_CHANGE;
-                }
-                Diff::Remove(name) => {
-                    remove_attribute(element, name);
-                    changed |= ChangeFlags::OTHER_CHANGE;
-                }
-            }
-        }
-        std::mem::swap(attributes, &mut self.attributes);
-        self.attributes.clear();
-        changed
+    ) -> usize {
+        apply_kv_changes(
+            attributes,
+            &mut self.attributes,
+            |name, value| set_attribute(element, name, &value.serialize()),
+            |name| remove_attribute(element, name),
+        )
+    }
+
+    /// Like [`HtmlProps::apply_attribute_changes`], but the writes are buffered in `deferred`.
+    fn defer_attribute_changes(
+        &mut self,
+        element: &web_sys::Element,
+        attributes: &mut VecMap<CowStr, AttributeValue>,
+        deferred: &DeferredAttributes,
+    ) -> usize {
+        apply_kv_changes(
+            attributes,
+            &mut self.attributes,
+            |name, value| deferred.write(element, name, Some(value.clone())),
+            |name| deferred.write(element, name, None),
+        )
     }
 
     pub(crate) fn apply_class_changes(
         &mut self,
         element: &web_sys::Element,
         classes: &mut VecMap<CowStr, ()>,
-    ) -> ChangeFlags {
-        let mut changed = ChangeFlags::empty();
-        // update attributes
-        for itm in diff_kv_iterables(&*classes, &self.classes) {
-            match itm {
-                Diff::Add(class_name, ()) | Diff::Change(class_name, ()) => {
-                    set_class(element, class_name);
-                    changed |= ChangeFlags::OTHER_CHANGE;
-                }
-                Diff::Remove(class_name) => {
-                    remove_class(element, class_name);
-                    changed |= ChangeFlags::OTHER_CHANGE;
-                }
-            }
-        }
-        std::mem::swap(classes, &mut self.classes);
-        self.classes.clear();
-        changed
+    ) -> usize {
+        apply_kv_changes(
+            classes,
+            &mut self.classes,
+            |class_name, ()| set_class(element, class_name),
+            |class_name| remove_class(element, class_name),
+        )
     }
 
     pub(crate) fn apply_style_changes(
         &mut self,
         element: &web_sys::Element,
         styles: &mut VecMap<CowStr, CowStr>,
-    ) -> ChangeFlags {
-        let mut changed = ChangeFlags::empty();
-        // update attributes
-        for itm in diff_kv_iterables(&*styles, &self.styles) {
-            match itm {
-                Diff::Add(name, value) | Diff::Change(name, value) => {
-                    set_style(element, name, value);
-                    changed |= ChangeFlags::OTHER_CHANGE;
-                }
-                Diff::Remove(name) => {
-                    remove_style(element, name);
-                    changed |= ChangeFlags::OTHER_CHANGE;
-                }
-            }
+    ) -> usize {
+        apply_kv_changes(
+            styles,
+            &mut self.styles,
+            |name, value| set_style(element, name, value),
+            |name| remove_style(element, name),
+        )
+    }
+}
+
+/// Diffs `next` against `prev`, calling `set` for every added or changed entry and `remove` for every removed entry.
+///
+/// Afterwards `prev` contains the entries of `next` and `next` is empty.
+/// Returns the number of calls to `set` and `remove`, i.e. the number of DOM writes.
+fn apply_kv_changes<V: PartialEq>(
+    prev: &mut VecMap<CowStr, V>,
+    next: &mut VecMap<CowStr, V>,
+    mut set: impl FnMut(&str, &V),
+    mut remove: impl FnMut(&str),
+) -> usize {
+    let mut writes = 0;
+    for itm in diff_kv_iterables(&*prev, &*next) {
+        match itm {
+            Diff::Add(name, value) | Diff::Change(name, value) => set(name, value),
+            Diff::Remove(name) => remove(name),
         }
-        std::mem::swap(styles, &mut self.styles);
-        self.styles.clear();
-        changed
+        writes += 1;
     }
+    std::mem::swap(prev, next);
+    next.clear();
+    writes
 }
 
-fn set_attribute(element: &web_sys::Element, name: &str, value: &str) {
+pub(crate) fn set_attribute(element: &web_sys::Element, name: &str, value: &str) {
     // we have to special-case `value` because setting the value using `set_attribute`
     // doesn't work after the value has been changed.
-    if name == "value" {
-        let element: &web_sys::HtmlInputElement = element.dyn_ref().unwrap_throw();
+    // Other elements with a `value` attribute (e.g. `<option>`) don't have that issue.
+    if name == "value" && element.is_instance_of::<web_sys::HtmlInputElement>() {
+        let element: &web_sys::HtmlInputElement = element.unchecked_ref();
         element.set_value(value);
     } else if name == "checked" {
         let element: &web_sys::HtmlInputElement = element.dyn_ref().unwrap_throw();
         element.set_checked(true);
+    } else if name == "inert" && !supports_inert() {
+        // the attribute would do nothing, except matching `[inert]` selectors
     } else {
         element.set_attribute(name, value).unwrap_throw();
     }
 }
 
-fn remove_attribute(element: &web_sys::Element, name: &str) {
+pub(crate) fn remove_attribute(element: &web_sys::Element, name: &str) {
     // we have to special-case `checked` because setting the value using `set_attribute`
     // doesn't work after the value has been changed.
     if name == "checked" {
@@ -169,6 +194,23 @@ fn remove_attribute(element: &web_sys::Element, name: &str) {
     }
 }
 
+/// Whether the browser supports the `inert` property, warns once when it doesn't.
+fn supports_inert() -> bool {
+    thread_local! {
+        static SUPPORTED: std::cell::OnceCell<bool> = const { std::cell::OnceCell::new() };
+    }
+    SUPPORTED.with(|supported| {
+        *supported.get_or_init(|| {
+            let probe = crate::document().create_element("div").unwrap_throw();
+            let supported = js_sys::Reflect::has(&probe, &"inert".into()).unwrap_or(false);
+            if !supported {
+                tracing::warn!("`inert` isn't supported by this browser, it's ignored");
+            }
+            supported
+        })
+    })
+}
+
 fn set_class(element: &web_sys::Element, class_name: &str) {
     debug_assert!(
         !class_name.is_empty(),
@@ -209,6 +251,58 @@ fn remove_style(element: &web_sys::Element, name: &str) {
     }
 }
 
+/// Keeps track of the DOM `id`s used during a single build/rebuild pass, to detect duplicates.
+///
+/// `O` identifies the element owning an id, so that registering the same element twice
+/// (e.g. when it is recreated because its tag name changed) isn't reported.
+///
+/// Elements within a memoized subtree aren't visited during a rebuild, so duplicates
+/// of their ids are only detected in the pass that (re)builds them.
+#[cfg(debug_assertions)]
+pub(crate) struct IdRegistry<O> {
+    ids: VecMap<CowStr, O>,
+    /// The number of duplicates detected since the registry was created, for tests.
+    duplicates: usize,
+}
+
+#[cfg(debug_assertions)]
+impl<O> Default for IdRegistry<O> {
+    fn default() -> Self {
+        Self {
+            ids: VecMap::default(),
+            duplicates: 0,
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<O: PartialEq> IdRegistry<O> {
+    /// Forget all registered ids, this should be called before every build/rebuild pass.
+    pub(crate) fn clear(&mut self) {
+        self.ids.clear();
+    }
+
+    /// Register `id` as used by `owner`, returns `false` if another element already uses it.
+    pub(crate) fn register(&mut self, id: &CowStr, owner: O) -> bool {
+        match self.ids.get(id) {
+            Some(prev_owner) if *prev_owner == owner => true,
+            Some(_) => {
+                self.duplicates += 1;
+                false
+            }
+            None => {
+                self.ids.insert(id.clone(), owner);
+                true
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn duplicate_count(&self) -> usize {
+        self.duplicates
+    }
+}
+
 // Note: xilem has derive Clone here. Not sure.
 pub struct Cx {
     id_path: IdPath,
@@ -216,6 +310,21 @@ pub struct Cx {
     // TODO There's likely a cleaner more robust way to propagate the attributes to an element
     pub(crate) current_element_props: HtmlProps,
     app_ref: Option<Box<dyn AppRunner>>,
+    pending_messages: Vec<Message>,
+    debug_ids: bool,
+    head_nodes: Rc<RefCell<HeadNodes<web_sys::Element>>>,
+    node_pool: NodePool<web_sys::Element>,
+    dom_op_log: DomOpLog,
+    pub(crate) perf: PerfCounters,
+    deferred_attributes: Option<DeferredAttributes>,
+    /// The [`crate::catch_errors`] views whose child is currently built or rebuilt, innermost last.
+    error_boundaries: Vec<ErrorBoundary>,
+    #[cfg(debug_assertions)]
+    pub(crate) id_registry: IdRegistry<web_sys::Element>,
+    #[cfg(debug_assertions)]
+    pub(crate) message_trace: MessageTrace,
+    #[cfg(debug_assertions)]
+    dom_write_count: usize,
 }
 
 pub struct MessageThunk {
@@ -223,6 +332,12 @@ pub struct MessageThunk {
     app_ref: Box<dyn AppRunner>,
 }
 
+struct ErrorBoundary {
+    id_path: IdPath,
+    /// The first error reported with [`Cx::report_error`] while building the child.
+    error: Option<String>,
+}
+
 bitflags! {
     #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
     pub struct ChangeFlags: u32 {
@@ -231,6 +346,11 @@ bitflags! {
     }
 }
 
+/// The id returned by [`Cx::unique_id`] within the id of the view `id`.
+pub(crate) fn unique_id(prefix: &str, id: Id) -> CowStr {
+    format!("{prefix}-{}", id.to_raw()).into()
+}
+
 impl Cx {
     pub fn new() -> Self {
         Cx {
@@ -238,10 +358,26 @@ impl Cx {
             document: crate::document(),
             app_ref: None,
             current_element_props: Default::default(),
+            pending_messages: Vec::new(),
+            debug_ids: cfg!(debug_assertions),
+            head_nodes: Default::default(),
+            node_pool: Default::default(),
+            dom_op_log: Default::default(),
+            perf: Default::default(),
+            deferred_attributes: None,
+            error_boundaries: Vec::new(),
+            #[cfg(debug_assertions)]
+            id_registry: Default::default(),
+            #[cfg(debug_assertions)]
+            message_trace: Default::default(),
+            #[cfg(debug_assertions)]
+            dom_write_count: 0,
         }
     }
 
     pub fn push(&mut self, id: Id) {
+        #[cfg(debug_assertions)]
+        self.message_trace.visit(id);
         self.id_path.push(id);
     }
 
@@ -268,11 +404,268 @@ impl Cx {
     ///
     /// Also an ergonomic helper.
     pub fn with_new_id<T, F: FnOnce(&mut Cx) -> T>(&mut self, f: F) -> (Id, T) {
-        let id = Id::next();
+        let id = self.alloc_id();
+        let result = self.run_in(id, f);
+        (id, result)
+    }
+
+    /// Run `f`, and when it panics, catch the panic and restore the state of the context
+    /// (i.e. the id path and the props of the current element), see [`crate::catch_errors`].
+    pub(crate) fn catch_panic<T>(
+        &mut self,
+        f: impl FnOnce(&mut Cx) -> T,
+    ) -> Result<T, Box<dyn Any + Send>> {
+        let id_path_len = self.id_path.len();
+        let error_boundaries_len = self.error_boundaries.len();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(self)));
+        if result.is_err() {
+            self.id_path.truncate(id_path_len);
+            self.error_boundaries.truncate(error_boundaries_len);
+            self.current_element_props = Default::default();
+        }
+        result
+    }
+
+    /// Run `f` as the child of the [`crate::catch_errors`] view at the current id path,
+    /// returns the first error that was reported with [`Cx::report_error`] within `f`.
+    pub(crate) fn with_error_boundary<T>(
+        &mut self,
+        f: impl FnOnce(&mut Cx) -> T,
+    ) -> (T, Option<String>) {
+        self.error_boundaries.push(ErrorBoundary {
+            id_path: self.id_path.clone(),
+            error: None,
+        });
+        let result = f(self);
+        let boundary = self.error_boundaries.pop().unwrap_throw();
+        (result, boundary.error)
+    }
+
+    /// Report that the view that is currently built or rebuilt failed, so that the innermost
+    /// [`crate::catch_errors`] view renders its fallback instead of its child.
+    ///
+    /// Unlike a panic, this works without `panic = "unwind"`. The view still has to return
+    /// from `build` or `rebuild` as usual, its state and element are dropped afterwards.
+    /// Outside of a `catch_errors` view the error is only logged.
+    pub fn report_error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        match self.error_boundaries.last_mut() {
+            Some(boundary) => {
+                boundary.error.get_or_insert(message);
+            }
+            None => tracing::error!("error reported outside of `catch_errors`: {message}"),
+        }
+    }
+
+    /// An [`ErrorReporter`] for errors that occur after the current build or rebuild,
+    /// e.g. in an event handler or a future, see [`Cx::report_error`].
+    ///
+    /// Returns `None` outside of a [`crate::catch_errors`] view.
+    pub fn error_reporter(&self) -> Option<ErrorReporter> {
+        let boundary = self.error_boundaries.last()?;
+        Some(ErrorReporter::new(MessageThunk {
+            id_path: boundary.id_path.clone(),
+            app_ref: self.app_ref.as_ref()?.clone_box(),
+        }))
+    }
+
+    /// Allocate a new id, e.g. for a child that is built later with [`Cx::run_in`].
+    ///
+    /// Ids are never reused, so an id allocated after a view was torn down can't
+    /// collide with the ids of that view.
+    pub fn alloc_id(&mut self) -> Id {
+        Id::next()
+    }
+
+    /// A string id that is unique within the session, e.g. for the `id` of an element that is
+    /// referenced by `for` or `aria-controls`, which starts with `prefix`.
+    ///
+    /// The id is derived from the innermost id of the id path, i.e. it has to be called within
+    /// [`Cx::with_id`] (or [`Cx::with_new_id`]) of the owning view. So it's the same across
+    /// rebuilds of that view, without storing it, and different for each view.
+    /// Ids of a single view are distinguished by their `prefix`.
+    pub fn unique_id(&self, prefix: &str) -> CowStr {
+        debug_assert!(
+            !self.id_path.is_empty(),
+            "Cx::unique_id has to be called within the id of the owning view"
+        );
+        let id = self.id_path.last().copied().unwrap_or_else(Id::next);
+        unique_id(prefix, id)
+    }
+
+    /// Run some logic with the previously allocated `id` added to the id path.
+    ///
+    /// Messages of views built or rebuilt within `f` are routed through `id`,
+    /// i.e. the id path passed to [`View::message`](crate::View::message) starts with `id`.
+    pub fn run_in<T, F: FnOnce(&mut Cx) -> T>(&mut self, id: Id, f: F) -> T {
         self.push(id);
         let result = f(self);
         self.pop();
-        (id, result)
+        result
+    }
+
+    /// Set whether elements get a `data-debugid` attribute with the id of their view.
+    ///
+    /// This allows the user to see if an element has been re-created or only altered.
+    /// It's enabled by default in debug builds. Changing it takes effect on the next rebuild,
+    /// i.e. existing attributes are then added or removed.
+    pub fn set_debug_ids(&mut self, enabled: bool) {
+        self.debug_ids = enabled;
+    }
+
+    /// Whether elements get a `data-debugid` attribute, see [`Cx::set_debug_ids`].
+    pub fn debug_ids(&self) -> bool {
+        self.debug_ids
+    }
+
+    /// Set how many removed elements are retained per element type, to be reused when an element of the same type is built.
+    ///
+    /// Pooling is disabled by default (i.e. the capacity is `0`). A reused element has all its attributes
+    /// and children removed before it's used again. Event listeners are removed by the views that added them,
+    /// when they're torn down. Form controls, media elements, canvases and custom elements are never reused,
+    /// as they have state that can't be reset via their attributes.
+    pub fn set_node_pool_capacity(&mut self, capacity: usize) {
+        self.node_pool.set_capacity(capacity);
+    }
+
+    /// The number of removed elements that are retained per element type, see [`Cx::set_node_pool_capacity`].
+    pub fn node_pool_capacity(&self) -> usize {
+        self.node_pool.capacity()
+    }
+
+    /// Set how many of the last element creations and node removals are recorded, see [`Cx::dom_op_log`].
+    ///
+    /// Recording is disabled by default (i.e. the capacity is `0`), when the log is full, the oldest ops are dropped.
+    pub fn set_dom_op_log_capacity(&mut self, capacity: usize) {
+        self.dom_op_log.set_capacity(capacity);
+    }
+
+    /// The last element creations and node removals, oldest first, see [`Cx::set_dom_op_log_capacity`].
+    ///
+    /// This allows to assert e.g. that rebuilding an unchanged view doesn't recreate any nodes.
+    /// Elements reused from the node pool (see [`Cx::set_node_pool_capacity`]) aren't recorded as created.
+    pub fn dom_op_log(&self) -> &VecDeque<DomOp> {
+        self.dom_op_log.ops()
+    }
+
+    /// Remove all recorded ops from the [`Cx::dom_op_log`].
+    pub fn clear_dom_op_log(&mut self) {
+        self.dom_op_log.clear();
+    }
+
+    /// The work done by the last build or rebuild of the app, e.g. to show the cost of a frame
+    /// in the app itself, or to assert in tests that a change only rebuilds what's necessary.
+    ///
+    /// While views are built or rebuilt, these are the stats of the frame before.
+    pub fn perf_stats(&self) -> PerfStats {
+        self.perf.last_frame()
+    }
+
+    /// Set how many of the last messages dispatched to the views are recorded, see [`Cx::message_trace`].
+    ///
+    /// Recording is disabled by default (i.e. the capacity is `0`), when the trace is full, the oldest messages are dropped.
+    /// Independent of that, a warning is logged for a message that no view handled, once per unroutable id.
+    #[cfg(debug_assertions)]
+    pub fn set_message_trace_capacity(&mut self, capacity: usize) {
+        self.message_trace.set_capacity(capacity);
+    }
+
+    /// The last messages dispatched to the views, oldest first, with what the views did with them,
+    /// see [`Cx::set_message_trace_capacity`].
+    ///
+    /// This allows to find out why a handler doesn't fire, e.g. because the message was sent to an id path
+    /// with a view that was removed in the meantime (see [`MessageOutcome::Stale`](crate::MessageOutcome::Stale)).
+    /// The ids are the same as in the `data-debugid` attributes (see [`Cx::set_debug_ids`]).
+    #[cfg(debug_assertions)]
+    pub fn message_trace(&self) -> &VecDeque<RoutedMessage> {
+        self.message_trace.messages()
+    }
+
+    /// Remove all recorded messages from the [`Cx::message_trace`].
+    #[cfg(debug_assertions)]
+    pub fn clear_message_trace(&mut self) {
+        self.message_trace.clear();
+    }
+
+    /// Set whether attribute changes on rebuild are buffered and written to the DOM in the next animation frame.
+    ///
+    /// This is disabled by default. When enabled, repeated changes of an attribute within a frame
+    /// (e.g. of a rapidly updating view) result in a single write of the last value, which avoids
+    /// layout thrashing. Attributes of newly built elements, classes and styles are still written directly.
+    /// The buffered writes are flushed before [`Cx::computed_style`] reads, and by [`Cx::flush_attribute_writes`].
+    /// Disabling it flushes the buffered writes.
+    pub fn set_defer_attribute_writes(&mut self, enabled: bool) {
+        if enabled {
+            self.deferred_attributes
+                .get_or_insert_with(Default::default);
+        } else if let Some(deferred) = self.deferred_attributes.take() {
+            deferred.flush();
+            #[cfg(debug_assertions)]
+            {
+                self.dom_write_count += deferred.take_flushed_writes();
+            }
+        }
+    }
+
+    /// Whether attribute changes are buffered until the next animation frame, see [`Cx::set_defer_attribute_writes`].
+    pub fn defers_attribute_writes(&self) -> bool {
+        self.deferred_attributes.is_some()
+    }
+
+    /// Write the attribute changes buffered since the last animation frame to the DOM now,
+    /// e.g. before measuring an element, see [`Cx::set_defer_attribute_writes`].
+    pub fn flush_attribute_writes(&self) {
+        if let Some(deferred) = &self.deferred_attributes {
+            deferred.flush();
+        }
+    }
+
+    /// Whether [`Cx::node_removed`] has to be called for removed nodes.
+    pub(crate) fn tracks_removed_nodes(&self) -> bool {
+        self.node_pool.capacity() > 0 || self.dom_op_log.capacity() > 0
+    }
+
+    /// Count the removal of `count` nodes from their parent in the [`Cx::perf_stats`].
+    pub(crate) fn count_removed_nodes(&mut self, count: usize) {
+        self.perf.current.nodes_removed += count;
+    }
+
+    /// Record the removal of `node` from its parent, and retain it for reuse if pooling is enabled
+    /// and it was removed immediately (i.e. it's not in a leave transition).
+    pub(crate) fn node_removed(&mut self, node: &web_sys::Node) {
+        if self.dom_op_log.capacity() > 0 {
+            let id = node
+                .dyn_ref::<web_sys::Element>()
+                .map(|element| element.id())
+                .filter(|id| !id.is_empty());
+            self.dom_op_log.record(DomOp::Remove {
+                name: node.node_name(),
+                id,
+            });
+        }
+        if self.node_pool.capacity() > 0 && node.parent_node().is_none() {
+            self.node_pool.release_node(node);
+        }
+    }
+
+    /// Add or remove the `data-debugid` attribute of `element` according to [`Cx::debug_ids`].
+    ///
+    /// `has_debug_id` tracks whether `element` currently has the attribute,
+    /// so that the DOM is only touched when the setting has changed.
+    pub(crate) fn update_debug_id(
+        &self,
+        element: &web_sys::Element,
+        id: Id,
+        has_debug_id: &mut bool,
+    ) {
+        if self.debug_ids && !*has_debug_id {
+            element
+                .set_attribute("data-debugid", &id.to_raw().to_string())
+                .unwrap_throw();
+        } else if !self.debug_ids && *has_debug_id {
+            element.remove_attribute("data-debugid").unwrap_throw();
+        }
+        *has_debug_id = self.debug_ids;
     }
 
     /// Run some logic within a new Pod context and return the newly created Pod,
@@ -309,12 +702,141 @@ impl Cx {
         &self.document
     }
 
+    /// Create an (empty) comment node as a placeholder for a view that renders nothing,
+    /// e.g. [`lazy`](crate::lazy) before it's built.
+    ///
+    /// The placeholder keeps the position of the view among its siblings, so its content
+    /// can later replace the marker in place, and vice versa when the view becomes empty again.
+    pub fn create_marker(&self) -> Pod {
+        Pod::new(self.document.create_comment(""))
+    }
+
+    /// The current URL of the document, e.g. to derive the routing state of the app from it.
+    ///
+    /// See [`build_url`](crate::build_url) and [`navigate`](crate::navigate) to change it.
+    pub fn location(&self) -> LocationInfo {
+        LocationInfo::from_location(&self.document.location().unwrap_throw())
+    }
+
+    /// The color scheme preferred by the user, i.e. whether `prefers-color-scheme: dark` matches.
+    ///
+    /// Use a [`media_query`](crate::media_query) with [`PREFERS_DARK`](crate::PREFERS_DARK) to react to changes.
+    pub fn color_scheme(&self) -> ColorScheme {
+        ColorScheme::from_prefers_dark(match_media(PREFERS_DARK).matches())
+    }
+
+    /// The `localStorage` or `sessionStorage` of the document, e.g. to persist a draft across reloads.
+    ///
+    /// Accessing the storage may fail (e.g. when it's disabled in private browsing modes),
+    /// which is reported by each of its operations.
+    pub fn storage(&self, kind: StorageKind) -> WebStorage {
+        WebStorage::new(&web_sys::window().unwrap_throw(), kind)
+    }
+
+    /// Read the computed value of the CSS `property` of `element`, as resolved by the browser.
+    ///
+    /// Styles are only computed for elements that are in the document, so this returns `None`
+    /// for an element that isn't mounted yet (e.g. directly after it was built),
+    /// and for properties the browser doesn't know.
+    pub fn computed_style(&self, element: &web_sys::Element, property: &str) -> Option<String> {
+        self.flush_attribute_writes();
+        if !element.is_connected() {
+            return None;
+        }
+        let window = self.document.default_view()?;
+        let style = window.get_computed_style(element).ok()??;
+        let value = style.get_property_value(property).ok()?;
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Add a `<link rel="stylesheet">` with `href` to the `<head>` of the document, unless it was already added.
+    ///
+    /// The link is shared by all the returned handles, it's removed when the last of them is dropped.
+    pub fn ensure_head_link(&mut self, href: impl Into<CowStr>) -> HeadNode {
+        let href = href.into();
+        self.ensure_head_node(HeadKey::Link(href.clone()), |document| {
+            let link = document.create_element("link").unwrap_throw();
+            link.set_attribute("rel", "stylesheet").unwrap_throw();
+            link.set_attribute("href", &href).unwrap_throw();
+            link
+        })
+    }
+
+    /// Add a `<style>` with `css` to the `<head>` of the document, unless it was already added.
+    ///
+    /// The style is shared by all the returned handles, it's removed when the last of them is dropped.
+    pub fn ensure_head_style(&mut self, css: impl Into<CowStr>) -> HeadNode {
+        let css = css.into();
+        self.ensure_head_node(HeadKey::Style(css.clone()), |document| {
+            let style = document.create_element("style").unwrap_throw();
+            style.set_text_content(Some(&css));
+            style
+        })
+    }
+
+    fn ensure_head_node(
+        &mut self,
+        key: HeadKey,
+        create: impl FnOnce(&Document) -> web_sys::Element,
+    ) -> HeadNode {
+        let element = self.head_nodes.borrow_mut().acquire(&key, || {
+            let element = create(&self.document);
+            self.document
+                .head()
+                .expect_throw("document has no <head>")
+                .append_child(&element)
+                .unwrap_throw();
+            element
+        });
+        HeadNode::new(key, element, self.head_nodes.clone())
+    }
+
+    /// Call the JS function `func` with `this` and `args`, e.g. to interact with an existing JS library.
+    ///
+    /// When `func` throws, the exception is logged and `None` is returned.
+    pub fn call_js(
+        &self,
+        func: &js_sys::Function,
+        this: &wasm_bindgen::JsValue,
+        args: &[wasm_bindgen::JsValue],
+    ) -> Option<wasm_bindgen::JsValue> {
+        crate::js_init::call_js(func, this, args)
+    }
+
     pub(crate) fn build_element(&mut self, ns: &str, name: &str) -> (web_sys::Element, HtmlProps) {
-        let el = self
-            .document
-            .create_element_ns(Some(ns), name)
-            .expect("could not create element");
-        let props = self.current_element_props.apply(&el);
+        let el = match self.node_pool.take_element(ns, name) {
+            Some(el) => {
+                if let Some(deferred) = &self.deferred_attributes {
+                    deferred.discard(&el);
+                }
+                el
+            }
+            None => {
+                self.perf.current.nodes_created += 1;
+                self.dom_op_log.record(DomOp::Create {
+                    name: name.to_owned(),
+                    id: self
+                        .current_element_props
+                        .attributes
+                        .get("id")
+                        .map(|id| id.serialize().to_string()),
+                });
+                // Tag names repeat a lot, so their JS strings are cached instead of being
+                // re-encoded for every element, interning doesn't change the strings
+                self.document
+                    .create_element_ns(Some(wasm_bindgen::intern(ns)), wasm_bindgen::intern(name))
+                    .expect("could not create element")
+            }
+        };
+        #[cfg(debug_assertions)]
+        self.register_element_id(&el);
+        let (props, writes) = self.current_element_props.apply(&el);
+        #[cfg(debug_assertions)]
+        {
+            self.dom_write_count += writes;
+        }
+        self.perf.current.views_built += 1;
+        self.perf.current.attribute_writes += writes;
         (el, props)
     }
 
@@ -323,7 +845,55 @@ impl Cx {
         element: &web_sys::Element,
         props: &mut HtmlProps,
     ) -> ChangeFlags {
-        self.current_element_props.apply_changes(element, props)
+        #[cfg(debug_assertions)]
+        self.register_element_id(element);
+        let (writes, deferred_writes) = match &self.deferred_attributes {
+            Some(deferred) => {
+                let next = &mut self.current_element_props;
+                let deferred_writes =
+                    next.defer_attribute_changes(element, &mut props.attributes, deferred);
+                let writes = next.apply_class_changes(element, &mut props.classes)
+                    + next.apply_style_changes(element, &mut props.styles);
+                (writes, deferred_writes)
+            }
+            None => (self.current_element_props.apply_changes(element, props), 0),
+        };
+        #[cfg(debug_assertions)]
+        {
+            self.dom_write_count += writes;
+        }
+        self.perf.current.views_rebuilt += 1;
+        self.perf.current.attribute_writes += writes + deferred_writes;
+        if writes + deferred_writes > 0 {
+            ChangeFlags::OTHER_CHANGE
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// Return the number of attribute, class and style writes to the DOM since the last call of this method.
+    ///
+    /// Writes of all elements that were built or rebuilt in the meantime are included, e.g. to assert
+    /// that rebuilding an unchanged view doesn't touch the DOM.
+    #[cfg(debug_assertions)]
+    pub fn take_dom_write_count(&mut self) -> usize {
+        // Buffered attribute writes are counted when they're flushed
+        if let Some(deferred) = &self.deferred_attributes {
+            self.dom_write_count += deferred.take_flushed_writes();
+        }
+        std::mem::take(&mut self.dom_write_count)
+    }
+
+    /// Warn when the `id` that is about to be applied to `element` is already used by another element.
+    #[cfg(debug_assertions)]
+    fn register_element_id(&mut self, element: &web_sys::Element) {
+        if let Some(AttributeValue::String(id)) = self.current_element_props.attributes.get("id") {
+            if !self.id_registry.register(id, element.clone()) {
+                tracing::warn!(
+                    "the id \"{id}\" is used by multiple elements, ids should be unique within the document"
+                );
+            }
+        }
     }
 
     // TODO Not sure how multiple attribute definitions with the same name should be handled (e.g. `e.attr("class", "a").attr("class", "b")`)
@@ -373,6 +943,25 @@ impl Cx {
             app_ref: self.app_ref.as_ref().unwrap().clone_box(),
         }
     }
+    /// Post `message` to the view at the current id path.
+    ///
+    /// Like the messages of a [`MessageThunk`], this is usually called within [`Cx::with_id`]
+    /// (or [`Cx::with_new_id`]) with the id of the view itself, the message is then passed
+    /// to its [`View::message`](crate::View::message) with an empty id path.
+    ///
+    /// The message is delivered after the current build/rebuild pass has finished.
+    /// If the view doesn't exist anymore at that point, the message is dropped.
+    pub fn message_self(&mut self, message: Box<dyn Any>) {
+        self.pending_messages.push(Message {
+            id_path: self.id_path.clone(),
+            body: message,
+        });
+    }
+
+    pub(crate) fn take_pending_messages(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.pending_messages)
+    }
+
     pub(crate) fn set_runner(&mut self, runner: impl AppRunner + 'static) {
         self.app_ref = Some(Box::new(runner));
     }
@@ -399,3 +988,304 @@ impl ChangeFlags {
         Self::STRUCTURE
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(entries: &[(&'static str, &'static str)]) -> VecMap<CowStr, CowStr> {
+        let mut map = VecMap::default();
+        for (name, value) in entries {
+            map.insert((*name).into(), (*value).into());
+        }
+        map
+    }
+
+    fn count_writes(
+        prev: &[(&'static str, &'static str)],
+        next: &[(&'static str, &'static str)],
+    ) -> usize {
+        let (mut prev, mut next) = (props(prev), props(next));
+        let (mut sets, mut removes) = (0, 0);
+        let writes = apply_kv_changes(&mut prev, &mut next, |_, _| sets += 1, |_| removes += 1);
+        assert_eq!(writes, sets + removes);
+        assert!(next.is_empty());
+        writes
+    }
+
+    #[test]
+    fn unchanged_props_write_nothing() {
+        let entries = [("href", "/"), ("title", "Home")];
+        assert_eq!(count_writes(&entries, &entries), 0);
+        assert_eq!(count_writes(&[], &[]), 0);
+    }
+
+    #[test]
+    fn changed_props_are_written() {
+        assert_eq!(
+            count_writes(&[("href", "/"), ("title", "Home")], &[("href", "/about")]),
+            2
+        );
+        assert_eq!(count_writes(&[], &[("href", "/")]), 1);
+    }
+
+    #[test]
+    fn unique_ids_are_prefixed() {
+        let id = Id::next();
+        assert_eq!(unique_id("menu", id), format!("menu-{}", id.to_raw()));
+        assert_ne!(unique_id("menu", id), unique_id("menu", Id::next()));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn duplicate_ids_are_detected() {
+        let mut registry = IdRegistry::default();
+        assert!(registry.register(&"name".into(), 1));
+        assert!(registry.register(&"email".into(), 2));
+        assert!(!registry.register(&"name".into(), 3));
+        assert_eq!(registry.duplicate_count(), 1);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn same_element_can_register_its_id_again() {
+        let mut registry = IdRegistry::default();
+        assert!(registry.register(&"name".into(), 1));
+        assert!(registry.register(&"name".into(), 1));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn ids_can_move_between_elements_across_passes() {
+        let mut registry = IdRegistry::default();
+        assert!(registry.register(&"name".into(), 1));
+        registry.clear();
+        // the id disappeared from element 1, and now appears on element 2
+        assert!(registry.register(&"name".into(), 2));
+        registry.clear();
+        assert!(registry.register(&"name".into(), 1));
+        assert_eq!(registry.duplicate_count(), 0);
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod wasm_tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::Cx;
+    use crate::{elements::html, View};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn allocated_ids_are_unique() {
+        let mut cx = Cx::new();
+        let first = cx.alloc_id();
+        let second = cx.alloc_id();
+        assert_ne!(first, second);
+    }
+
+    #[cfg(debug_assertions)]
+    #[wasm_bindgen_test]
+    fn duplicate_element_ids_are_reported() {
+        use crate::interfaces::Element as _;
+
+        let inputs = |ids: [&'static str; 3]| {
+            html::div::<(), (), _>(
+                ids.into_iter()
+                    .map(|id| html::input(()).id(id))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let mut cx = Cx::new();
+        let view = inputs(["name", "name", "email"]);
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        assert_eq!(cx.id_registry.duplicate_count(), 1);
+
+        // in the next pass, the ids move between the elements without any duplicates
+        cx.id_registry.clear();
+        let next = inputs(["email", "name", "phone"]);
+        View::rebuild(&next, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(cx.id_registry.duplicate_count(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn unique_id_is_stable_across_rebuilds() {
+        use crate::labeled;
+
+        let mut cx = Cx::new();
+        let view = labeled::<_, (), ()>("Name", html::input(()));
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        let control_id = element.html_for();
+        assert_eq!(
+            control_id,
+            cx.with_id(id, |cx| cx.unique_id("xilem-labeled"))
+        );
+
+        let renamed = labeled::<_, (), ()>("Full name", html::input(()));
+        View::rebuild(&renamed, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(element.html_for(), control_id);
+        let (another, _, _) = View::build(&view, &mut cx);
+        assert_ne!(
+            cx.with_id(another, |cx| cx.unique_id("xilem-labeled")),
+            control_id
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn messages_are_routed_through_allocated_id() {
+        let mut cx = Cx::new();
+        let parent = cx.alloc_id();
+        let child = cx.alloc_id();
+        cx.run_in(parent, |cx| {
+            cx.run_in(child, |cx| cx.message_self(Box::new(42_u32)));
+        });
+        assert!(cx.id_path().is_empty());
+
+        let messages = cx.take_pending_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id_path, [parent, child]);
+        assert_eq!(messages[0].body.downcast_ref::<u32>(), Some(&42));
+    }
+
+    #[wasm_bindgen_test]
+    fn debug_ids_can_be_toggled() {
+        let mut cx = Cx::new();
+        cx.set_debug_ids(true);
+        let view: html::Div<(), (), ()> = html::div(());
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        assert!(element.has_attribute("data-debugid"));
+
+        cx.set_debug_ids(false);
+        View::rebuild(&view, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert!(!element.has_attribute("data-debugid"));
+
+        cx.set_debug_ids(true);
+        View::rebuild(&view, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(
+            element.get_attribute("data-debugid"),
+            Some(id.to_raw().to_string())
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn head_link_is_shared() {
+        let mut cx = Cx::new();
+        let selector = r#"head link[href="/xilem-test-shared.css"]"#;
+        let count = |cx: &Cx| cx.document().query_selector_all(selector).unwrap().length();
+
+        let first = cx.ensure_head_link("/xilem-test-shared.css");
+        let second = cx.ensure_head_link("/xilem-test-shared.css");
+        assert_eq!(count(&cx), 1);
+        assert_eq!(first.element(), second.element());
+
+        drop(first);
+        assert_eq!(count(&cx), 1);
+        drop(second);
+        assert_eq!(count(&cx), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn removed_elements_are_reused() {
+        use crate::interfaces::Element as _;
+
+        type List = html::Ul<(), (), Vec<crate::Attr<html::Li<(), (), &'static str>, (), ()>>>;
+        fn list(items: &[&'static str]) -> List {
+            html::ul(
+                items
+                    .iter()
+                    .map(|item| html::li(*item).attr("title", *item))
+                    .collect(),
+            )
+        }
+
+        let mut cx = Cx::new();
+        cx.set_node_pool_capacity(4);
+        let two = list(&["a", "b"]);
+        let (mut id, mut state, mut element) = View::build(&two, &mut cx);
+        let removed = element.last_element_child().unwrap();
+
+        let one = list(&["a"]);
+        View::rebuild(&one, &mut cx, &two, &mut id, &mut state, &mut element);
+        assert!(removed.parent_node().is_none());
+
+        let other = list(&["a", "c"]);
+        View::rebuild(&other, &mut cx, &one, &mut id, &mut state, &mut element);
+        let reused = element.last_element_child().unwrap();
+        assert_eq!(reused, removed);
+        assert_eq!(reused.get_attribute("title").as_deref(), Some("c"));
+        assert_eq!(reused.text_content().as_deref(), Some("c"));
+    }
+
+    #[wasm_bindgen_test]
+    fn unchanged_rebuild_has_empty_op_log() {
+        use crate::{interfaces::Element as _, DomOp};
+
+        let mut cx = Cx::new();
+        cx.set_dom_op_log_capacity(16);
+        let view = html::ul::<(), (), _>((html::li("a"), html::li("b"))).attr("id", "list");
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        assert_eq!(cx.dom_op_log().len(), 3);
+        // the parent is created before its children
+        assert_eq!(
+            cx.dom_op_log().front(),
+            Some(&DomOp::Create {
+                name: "ul".into(),
+                id: Some("list".into())
+            })
+        );
+
+        cx.clear_dom_op_log();
+        View::rebuild(&view, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert!(cx.dom_op_log().is_empty());
+    }
+
+    /// Compares building many identical elements with creating them directly,
+    /// run with `wasm-pack test --chrome -- --include-ignored build_10000_divs`.
+    #[wasm_bindgen_test]
+    #[ignore]
+    fn build_10000_divs() {
+        const COUNT: usize = 10_000;
+        let elapsed = |f: &mut dyn FnMut()| {
+            let start = js_sys::Date::now();
+            f();
+            js_sys::Date::now() - start
+        };
+
+        let mut cx = Cx::new();
+        let baseline = elapsed(&mut || {
+            for _ in 0..COUNT {
+                let div = cx.document().create_element_ns(Some(crate::HTML_NS), "div");
+                std::hint::black_box(div.unwrap());
+            }
+        });
+        let view = html::ul::<(), (), _>((0..COUNT).map(|_| html::div(())).collect::<Vec<_>>());
+        let build = elapsed(&mut || {
+            std::hint::black_box(View::build(&view, &mut cx));
+        });
+        let message = format!("{COUNT} divs: create_element_ns {baseline}ms, build {build}ms");
+        web_sys::console::log_1(&message.into());
+    }
+
+    #[cfg(feature = "testing")]
+    #[wasm_bindgen_test]
+    fn computed_style_is_read_from_mounted_element() {
+        use crate::{interfaces::HtmlElement as _, style, test_util::mount};
+
+        let view =
+            html::div::<(), (), _>(()).style((style("width", "120px"), style("z-index", "2")));
+        let mut harness = mount((), view);
+        let element = harness.root_element();
+        // styles aren't computed while the element isn't in the document
+        assert_eq!(harness.cx().computed_style(&element, "width"), None);
+
+        harness.attach_to_document();
+        let cx = harness.cx();
+        assert_eq!(
+            cx.computed_style(&element, "width").as_deref(),
+            Some("120px")
+        );
+        assert_eq!(cx.computed_style(&element, "z-index").as_deref(), Some("2"));
+        assert_eq!(cx.computed_style(&element, "not-a-property"), None);
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Crossfading between the branches of a view, e.g. of a [`OneOf2`](crate::OneOf2) for tabs.
+
+use std::{any::Any, marker::PhantomData, time::Duration};
+
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::sealed::Sealed, transition::LEAVE_CLASS_ATTR, view::DomNode, ChangeFlags, Cx,
+    HeadNode, View, ViewMarker,
+};
+
+const ENTER_CLASS: &str = "xilem-crossfade-enter";
+const LEAVE_CLASS: &str = "xilem-crossfade-leave";
+const DURATION_PROPERTY: &str = "--xilem-crossfade-duration";
+
+const CROSSFADE_CSS: &str = "\
+@keyframes xilem-crossfade-in { from { opacity: 0; } }
+.xilem-crossfade-enter { animation: xilem-crossfade-in var(--xilem-crossfade-duration); }
+.xilem-crossfade-leave { opacity: 0; transition: opacity var(--xilem-crossfade-duration); }";
+
+pub struct Crossfade<V, T, A = ()> {
+    view: V,
+    duration: Duration,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct CrossfadeState<S> {
+    child_state: S,
+    /// The element of the previous branch, while it's fading out.
+    leaving: Option<web_sys::Element>,
+    #[allow(unused)]
+    style: HeadNode,
+}
+
+/// Crossfade between the branches of `view` (e.g. a [`OneOf2`](crate::OneOf2)) for `duration`.
+///
+/// When `view` switches to another branch (i.e. its element is replaced), the new element fades in,
+/// while the old element stays in the DOM (next to the new element) until it has faded out.
+/// To overlap both, the parent has to stack them, e.g. as CSS grid with `grid-area: 1 / 1` for its children.
+///
+/// When the branch is switched again while the old element is still fading out, that fade is cancelled,
+/// i.e. the old element is removed immediately, so at most two branches are present at once.
+pub fn crossfade<V, T, A>(view: V, duration: Duration) -> Crossfade<V, T, A> {
+    Crossfade {
+        view,
+        duration,
+        phantom: PhantomData,
+    }
+}
+
+fn set_duration(element: &web_sys::Element, duration: Duration) {
+    if let Some(element) = element.dyn_ref::<web_sys::HtmlElement>() {
+        let duration = format!("{}ms", duration.as_millis());
+        element
+            .style()
+            .set_property(DURATION_PROPERTY, &duration)
+            .unwrap_throw();
+    }
+}
+
+impl<V, T, A> ViewMarker for Crossfade<V, T, A> {}
+impl<V, T, A> Sealed for Crossfade<V, T, A> {}
+
+impl<V: View<T, A>, T, A> View<T, A> for Crossfade<V, T, A> {
+    type State = CrossfadeState<V::State>;
+    type Element = V::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, child_state, element) = self.view.build(cx);
+        let state = CrossfadeState {
+            child_state,
+            leaving: None,
+            style: cx.ensure_head_style(CROSSFADE_CSS),
+        };
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let prev_node = element.as_node_ref().clone();
+        let changed = self
+            .view
+            .rebuild(cx, &prev.view, id, &mut state.child_state, element);
+        if !changed.contains(ChangeFlags::STRUCTURE) {
+            return changed;
+        }
+        // Cancel the fade of the previous switch
+        if let Some(leaving) = state.leaving.take() {
+            leaving.remove();
+        }
+        if let Some(old) = prev_node.dyn_ref::<web_sys::Element>() {
+            // The parent keeps the old element until its leave transition has finished
+            old.class_list().remove_1(ENTER_CLASS).unwrap_throw();
+            old.set_attribute(LEAVE_CLASS_ATTR, LEAVE_CLASS)
+                .unwrap_throw();
+            set_duration(old, self.duration);
+            state.leaving = Some(old.clone());
+        }
+        if let Some(new) = element.as_node_ref().dyn_ref::<web_sys::Element>() {
+            new.class_list().add_1(ENTER_CLASS).unwrap_throw();
+            set_duration(new, self.duration);
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.view
+            .message(id_path, &mut state.child_state, message, app_state)
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn switching_branches_overlaps_them() {
+        use std::time::Duration;
+
+        use super::crossfade;
+        use crate::{elements::html, test_util::mount, OneOf2};
+
+        type Tabs = html::Div<
+            (),
+            (),
+            super::Crossfade<
+                OneOf2<html::P<(), (), &'static str>, html::Span<(), (), &'static str>>,
+                (),
+            >,
+        >;
+        let tab = |first: bool| -> Tabs {
+            let branch = if first {
+                OneOf2::A(html::p("first"))
+            } else {
+                OneOf2::B(html::span("second"))
+            };
+            html::div(crossfade(branch, Duration::from_millis(200)))
+        };
+        let mut harness = mount((), tab(true));
+        // The leave transition is only detected for elements in the document
+        let tabs = harness.root_element();
+        crate::document_body().append_child(&tabs).unwrap();
+
+        harness.rebuild(tab(false));
+        let names = |tabs: &web_sys::Element| {
+            let children = tabs.child_nodes();
+            (0..children.length())
+                .map(|i| children.get(i).unwrap().node_name())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&tabs), ["SPAN", "P"]);
+
+        // switching again cancels the fade of the first switch
+        harness.rebuild(tab(true));
+        assert_eq!(names(&tabs), ["P", "SPAN"]);
+        tabs.remove();
+    }
+}
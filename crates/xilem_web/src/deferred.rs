@@ -0,0 +1,135 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Buffering attribute writes until the next animation frame, see [`Cx::set_defer_attribute_writes`](crate::Cx::set_defer_attribute_writes).
+
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+
+use crate::{
+    context::{remove_attribute, set_attribute},
+    vecmap::VecMap,
+    AttributeValue,
+};
+
+type CowStr = std::borrow::Cow<'static, str>;
+
+#[derive(Default)]
+struct Buffer {
+    /// The last value written to each attribute of an element, `None` removes it.
+    elements: Vec<(web_sys::Element, VecMap<CowStr, Option<AttributeValue>>)>,
+    /// Whether a flush in the next animation frame is requested.
+    frame_requested: bool,
+    /// The number of DOM writes of the flushes since the last [`DeferredAttributes::take_flushed_writes`].
+    #[cfg(debug_assertions)]
+    flushed_writes: usize,
+}
+
+/// Attribute writes that are buffered until they're flushed in the next animation frame.
+#[derive(Default)]
+pub(crate) struct DeferredAttributes(Rc<RefCell<Buffer>>);
+
+impl DeferredAttributes {
+    /// Buffer a write of `value` to the attribute `name` of `element`, replacing a buffered write to the same attribute.
+    pub(crate) fn write(
+        &self,
+        element: &web_sys::Element,
+        name: &str,
+        value: Option<AttributeValue>,
+    ) {
+        let name: CowStr = name.to_owned().into();
+        let mut buffer = self.0.borrow_mut();
+        match buffer.elements.iter_mut().find(|(el, _)| el == element) {
+            Some((_, writes)) => {
+                writes.insert(name, value);
+            }
+            None => {
+                let mut writes = VecMap::default();
+                writes.insert(name, value);
+                buffer.elements.push((element.clone(), writes));
+            }
+        }
+        if !buffer.frame_requested {
+            buffer.frame_requested = true;
+            request_flush(Rc::downgrade(&self.0));
+        }
+    }
+
+    /// Drop the buffered writes to `element`, e.g. when it's reused for another view.
+    pub(crate) fn discard(&self, element: &web_sys::Element) {
+        self.0.borrow_mut().elements.retain(|(el, _)| el != element);
+    }
+
+    /// Apply all buffered writes to the DOM now.
+    pub(crate) fn flush(&self) {
+        flush(&self.0);
+    }
+
+    #[cfg(debug_assertions)]
+    pub(crate) fn take_flushed_writes(&self) -> usize {
+        std::mem::take(&mut self.0.borrow_mut().flushed_writes)
+    }
+}
+
+fn flush(buffer: &RefCell<Buffer>) {
+    let elements = std::mem::take(&mut buffer.borrow_mut().elements);
+    let mut _writes = 0;
+    for (element, attributes) in elements {
+        for (name, value) in attributes.iter() {
+            match value {
+                Some(value) => set_attribute(&element, name, &value.serialize()),
+                None => remove_attribute(&element, name),
+            }
+            _writes += 1;
+        }
+    }
+    #[cfg(debug_assertions)]
+    {
+        buffer.borrow_mut().flushed_writes += _writes;
+    }
+}
+
+/// Flush `buffer` in the next animation frame.
+///
+/// An earlier flush (e.g. before reading computed styles) doesn't cancel the frame, it just flushes nothing then.
+fn request_flush(buffer: Weak<RefCell<Buffer>>) {
+    let callback = Closure::once_into_js(move || {
+        if let Some(buffer) = buffer.upgrade() {
+            buffer.borrow_mut().frame_requested = false;
+            flush(&buffer);
+        }
+    });
+    web_sys::window()
+        .unwrap_throw()
+        .request_animation_frame(callback.unchecked_ref())
+        .unwrap_throw();
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing", debug_assertions))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn writes_in_one_frame_are_coalesced() {
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        let view = |title: &'static str| html::div::<(), (), _>(()).attr("title", title);
+        let mut harness = mount((), view("a"));
+        let div = harness.root_element();
+        harness.cx().set_defer_attribute_writes(true);
+        harness.cx().take_dom_write_count();
+
+        for title in ["b", "c", "d"] {
+            harness.rebuild(view(title));
+        }
+        assert_eq!(div.get_attribute("title").as_deref(), Some("a"));
+        assert_eq!(harness.cx().take_dom_write_count(), 0);
+
+        // reading computed styles flushes the buffered writes first
+        harness.cx().computed_style(&div, "color");
+        assert_eq!(div.get_attribute("title").as_deref(), Some("d"));
+        assert_eq!(harness.cx().take_dom_write_count(), 1);
+    }
+}
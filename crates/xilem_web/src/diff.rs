@@ -89,9 +89,14 @@ impl<'a, K: Ord + 'a, V: PartialEq, I: Iterator<Item = (&'a K, &'a V)>> Iterator
     }
 }
 
+/// A difference between two ordered maps, see [`VecMap::diff_against`](crate::VecMap::diff_against).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Diff<K, V> {
+    /// The key with its value was added.
     Add(K, V),
+    /// The key was removed.
     Remove(K),
+    /// The value of the key was changed to this value.
     Change(K, V),
 }
 
@@ -0,0 +1,86 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recording the creation and removal of DOM nodes, see [`Cx::set_dom_op_log_capacity`](crate::Cx::set_dom_op_log_capacity).
+
+use std::collections::VecDeque;
+
+/// A DOM node that was created or removed while building or rebuilding views.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomOp {
+    /// An element was created, with its (local) tag name, and the value of its `id` attribute, if any.
+    Create { name: String, id: Option<String> },
+    /// A node was removed from its parent, with its node name (e.g. `"LI"` or `"#text"`),
+    /// and the value of its `id` attribute, if it's an element with one.
+    Remove { name: String, id: Option<String> },
+}
+
+/// A ring buffer with the last `capacity` [`DomOp`]s.
+#[derive(Debug, Default)]
+pub(crate) struct DomOpLog {
+    capacity: usize,
+    ops: VecDeque<DomOp>,
+}
+
+impl DomOpLog {
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the number of recorded ops, the oldest ones above it are dropped.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.ops.len().saturating_sub(capacity);
+        self.ops.drain(..excess);
+    }
+
+    pub(crate) fn record(&mut self, op: DomOp) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.ops.len() == self.capacity {
+            self.ops.pop_front();
+        }
+        self.ops.push_back(op);
+    }
+
+    pub(crate) fn ops(&self) -> &VecDeque<DomOp> {
+        &self.ops
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.ops.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DomOp, DomOpLog};
+
+    fn create(name: &str) -> DomOp {
+        DomOp::Create {
+            name: name.into(),
+            id: None,
+        }
+    }
+
+    #[test]
+    fn nothing_is_recorded_by_default() {
+        let mut log = DomOpLog::default();
+        log.record(create("div"));
+        assert!(log.ops().is_empty());
+    }
+
+    #[test]
+    fn oldest_ops_are_dropped() {
+        let mut log = DomOpLog::default();
+        log.set_capacity(2);
+        for name in ["a", "b", "c"] {
+            log.record(create(name));
+        }
+        assert_eq!(log.ops(), &[create("b"), create("c")]);
+
+        log.set_capacity(1);
+        assert_eq!(log.ops(), &[create("c")]);
+    }
+}
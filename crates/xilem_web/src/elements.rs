@@ -7,8 +7,8 @@ use wasm_bindgen::{JsCast, UnwrapThrowExt};
 use xilem_core::{Id, MessageResult, VecSplice};
 
 use crate::{
-    context::HtmlProps, interfaces::sealed::Sealed, view::DomNode, ChangeFlags, Cx, ElementsSplice,
-    Pod, View, ViewMarker, ViewSequence, HTML_NS,
+    context::HtmlProps, interfaces::sealed::Sealed, transition, view::DomNode, Attr, ChangeFlags,
+    Cx, ElementsSplice, Pod, Shadow, View, ViewMarker, ViewSequence, HTML_NS,
 };
 
 use super::interfaces::Element;
@@ -26,6 +26,71 @@ pub struct ElementState<ViewSeqState> {
     /// after usage it shouldn't contain any elements,
     /// and is mainly here to avoid unnecessary allocations
     pub(crate) scratch: Vec<Pod>,
+    /// Whether the element currently has a `data-debugid` attribute, see [`Cx::set_debug_ids`].
+    pub(crate) has_debug_id: bool,
+}
+
+/// Describes how the elements built by a [`ViewSequence`] don't match its [`ViewSequence::count`], if they don't.
+///
+/// `produced` is the number of elements that were pushed or mutated, and `remaining` the number of
+/// previous elements that were neither mutated nor deleted.
+fn children_count_mismatch(count: usize, produced: usize, remaining: usize) -> Option<String> {
+    match (count == produced, remaining) {
+        (true, 0) => None,
+        (true, remaining) => Some(format!(
+            "{remaining} previous elements were neither mutated nor deleted"
+        )),
+        (false, _) => Some(format!(
+            "the sequence reported a count of {count}, but produced {produced} elements"
+        )),
+    }
+}
+
+/// Panics in debug builds when the children of `<tag_name>` didn't keep the contract of [`ViewSequence`],
+/// which would otherwise silently mix up the child elements on the next rebuild.
+///
+/// `produced` is the length of the [`ElementsSplice`] after the build/rebuild.
+fn debug_check_children(
+    tag_name: &str,
+    count: usize,
+    produced: usize,
+    child_elements: &[Pod],
+    scratch: &[Pod],
+) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let remaining = child_elements.len() - produced + scratch.len();
+    if let Some(mismatch) = children_count_mismatch(count, produced, remaining) {
+        panic!(
+            "the children of <{tag_name}> violated the ViewSequence contract: {mismatch}. \
+            Every previous element has to be mutated (and marked) or deleted exactly once, \
+            and `count` has to return the number of elements in the sequence"
+        );
+    }
+}
+
+/// The span around the build of an element, its `id` and number of `children` are recorded once known.
+#[cfg(feature = "tracing")]
+fn build_span(element: &str) -> tracing::Span {
+    tracing::trace_span!(
+        "build",
+        element,
+        id = tracing::field::Empty,
+        children = tracing::field::Empty,
+    )
+}
+
+/// The span around the rebuild of an element, its number of `children` and the `changed` flags are recorded once known.
+#[cfg(feature = "tracing")]
+fn rebuild_span(element: &str, id: Id) -> tracing::Span {
+    tracing::trace_span!(
+        "rebuild",
+        element,
+        id = id.to_raw(),
+        children = tracing::field::Empty,
+        changed = tracing::field::Empty,
+    )
 }
 
 // TODO something like the `after_update` of the former `Element` view (likely as a wrapper view instead)
@@ -49,23 +114,48 @@ pub fn custom_element<T, A, Children: ViewSequence<T, A>>(
     }
 }
 
+/// Project `child` into the slot named `slot_name` of the (custom) element it's a child of.
+///
+/// This sets the `slot` attribute of `child`, so that it is rendered in the `<slot name="...">`
+/// of the shadow DOM of a web component. An empty `slot_name` places `child` in the default (unnamed) slot.
+pub fn slotted<E: Element<T, A>, T, A>(slot_name: impl Into<CowStr>, child: E) -> Attr<E, T, A> {
+    let slot_name = slot_name.into();
+    // an element without `slot` attribute is assigned to the default slot
+    let slot_name = (!slot_name.is_empty()).then_some(slot_name);
+    child.attr("slot", slot_name)
+}
+
 impl<T, A, Children> CustomElement<T, A, Children> {
     fn node_name(&self) -> &str {
         &self.name
     }
+
+    /// Attach a shadow root with `mode` to this element and mount `children` into it.
+    ///
+    /// The children of the element itself stay in the light DOM, they are rendered in the
+    /// `<slot>`s of the shadow root (see [`slotted`]). `mode` can't be changed after the
+    /// shadow root was attached.
+    pub fn shadow<ShadowChildren: ViewSequence<T, A>>(
+        self,
+        mode: web_sys::ShadowRootMode,
+        children: ShadowChildren,
+    ) -> Shadow<Self, T, A, ShadowChildren> {
+        Shadow::new(self, mode, children)
+    }
 }
 
 /// An `ElementsSplice` that does DOM updates in place
-struct ChildrenSplice<'a, 'b, 'c> {
+pub(crate) struct ChildrenSplice<'a, 'b, 'c> {
     children: VecSplice<'a, 'b, Pod>,
     child_idx: u32,
     parent: &'c web_sys::Node,
-    node_list: Option<web_sys::NodeList>,
+    /// The child nodes of `parent`, without elements that are in their leave transition
+    child_nodes: Option<Vec<web_sys::Node>>,
     prev_element_count: usize,
 }
 
 impl<'a, 'b, 'c> ChildrenSplice<'a, 'b, 'c> {
-    fn new(
+    pub(crate) fn new(
         children: &'a mut Vec<Pod>,
         scratch: &'b mut Vec<Pod>,
         parent: &'c web_sys::Node,
@@ -75,17 +165,48 @@ impl<'a, 'b, 'c> ChildrenSplice<'a, 'b, 'c> {
             children: VecSplice::new(children, scratch),
             child_idx: 0,
             parent,
-            node_list: None,
+            child_nodes: None,
             prev_element_count,
         }
     }
+
+    // lazy child node access, in case it's not necessary at all, which is slightly faster when there's no need for the child nodes
+    fn child_nodes(&mut self) -> &mut Vec<web_sys::Node> {
+        self.child_nodes.get_or_insert_with(|| {
+            let node_list = self.parent.child_nodes();
+            (0..node_list.length())
+                .filter_map(|idx| node_list.get(idx))
+                .filter(|node| !transition::is_leaving(node))
+                .collect()
+        })
+    }
+
+    fn has_leave_transitions(&self) -> bool {
+        self.parent
+            .dyn_ref::<web_sys::Element>()
+            .and_then(|parent| {
+                let selector = format!(":scope > [{}]", transition::LEAVE_CLASS_ATTR);
+                parent.query_selector(&selector).ok().flatten()
+            })
+            .is_some()
+    }
 }
 
 impl<'a, 'b, 'c> ElementsSplice for ChildrenSplice<'a, 'b, 'c> {
     fn push(&mut self, element: Pod, _cx: &mut Cx) {
-        self.parent
-            .append_child(element.0.as_node_ref())
-            .unwrap_throw();
+        let node = element.0.as_node_ref();
+        if self.prev_element_count == 0 && self.child_nodes.is_none() {
+            // Fast path when building, there are no following siblings
+            self.parent.append_child(node).unwrap_throw();
+        } else {
+            // e.g. an `Option` that became `Some`, its element is inserted before the next sibling
+            let (parent, child_idx) = (self.parent, self.child_idx as usize);
+            let child_nodes = self.child_nodes();
+            parent
+                .insert_before(node, child_nodes.get(child_idx))
+                .unwrap_throw();
+            child_nodes.insert(child_idx, node.clone());
+        }
         self.child_idx += 1;
         self.children.push(element);
     }
@@ -94,22 +215,26 @@ impl<'a, 'b, 'c> ElementsSplice for ChildrenSplice<'a, 'b, 'c> {
         self.children.mutate()
     }
 
-    fn delete(&mut self, n: usize, _cx: &mut Cx) {
-        // Optimization in case all elements are deleted at once
-        if n == self.prev_element_count {
+    fn delete(&mut self, n: usize, cx: &mut Cx) {
+        // Optimization in case all elements are deleted at once (and none were pushed before them)
+        if n == self.prev_element_count && self.child_idx == 0 && !self.has_leave_transitions() {
+            let removed = cx
+                .tracks_removed_nodes()
+                .then(|| self.child_nodes().clone());
             self.parent.set_text_content(None);
+            self.child_nodes = None;
+            cx.count_removed_nodes(n);
+            for child in removed.iter().flatten() {
+                cx.node_removed(child);
+            }
         } else {
-            // lazy NodeList access, in case it's not necessary at all, which is slightly faster when there's no need for the NodeList
-            let node_list = if let Some(node_list) = &self.node_list {
-                node_list
-            } else {
-                self.node_list = Some(self.parent.child_nodes());
-                self.node_list.as_ref().unwrap()
-            };
-            for _ in 0..n {
-                let child = node_list.get(self.child_idx).unwrap_throw();
-                self.parent.remove_child(&child).unwrap_throw();
+            let (parent, child_idx) = (self.parent, self.child_idx as usize);
+            let child_nodes = self.child_nodes();
+            for child in child_nodes.drain(child_idx..child_idx + n) {
+                transition::remove_child(parent, &child);
+                cx.node_removed(&child);
             }
+            cx.count_removed_nodes(n);
         }
         self.children.delete(n);
     }
@@ -118,19 +243,42 @@ impl<'a, 'b, 'c> ElementsSplice for ChildrenSplice<'a, 'b, 'c> {
         self.children.len()
     }
 
-    fn mark(&mut self, mut changeflags: ChangeFlags, _cx: &mut Cx) -> ChangeFlags {
+    fn mark(&mut self, mut changeflags: ChangeFlags, cx: &mut Cx) -> ChangeFlags {
+        // Fast path for the common case, where only some siblings have changed: the DOM node of
+        // an unchanged child is left untouched. Only a `STRUCTURE` change below writes to the DOM,
+        // this makes explicit that nothing else may be added for unchanged children.
+        if changeflags.is_empty() {
+            self.child_idx += 1;
+            return changeflags;
+        }
         if changeflags.contains(ChangeFlags::STRUCTURE) {
-            let node_list = if let Some(node_list) = &self.node_list {
-                node_list
+            let cur_child = self
+                .children
+                .last_mutated()
+                .unwrap_throw()
+                .0
+                .as_node_ref()
+                .clone();
+            let (parent, child_idx) = (self.parent, self.child_idx as usize);
+            let old_child = std::mem::replace(&mut self.child_nodes()[child_idx], cur_child);
+            let has_leave_transition = old_child
+                .dyn_ref::<web_sys::Element>()
+                .is_some_and(|el| el.has_attribute(transition::LEAVE_CLASS_ATTR));
+            if has_leave_transition {
+                // keep the old child next to the new one until its transition has finished
+                parent
+                    .insert_before(&self.child_nodes()[child_idx], Some(&old_child))
+                    .unwrap_throw();
+                transition::remove_child(parent, &old_child);
             } else {
-                self.node_list = Some(self.parent.child_nodes());
-                self.node_list.as_ref().unwrap()
-            };
-            let cur_child = self.children.last_mutated_mut().unwrap_throw();
-            let old_child = node_list.get(self.child_idx).unwrap_throw();
-            self.parent
-                .replace_child(cur_child.0.as_node_ref(), &old_child)
-                .unwrap_throw();
+                parent
+                    .replace_child(&self.child_nodes()[child_idx], &old_child)
+                    .unwrap_throw();
+            }
+            cx.count_removed_nodes(1);
+            if cx.tracks_removed_nodes() {
+                cx.node_removed(&old_child);
+            }
             // TODO(#160) do something else with the structure information?
             changeflags.remove(ChangeFlags::STRUCTURE);
         }
@@ -153,6 +301,11 @@ where
     type Element = web_sys::HtmlElement;
 
     fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        #[cfg(feature = "tracing")]
+        let span = build_span(&self.name);
+        #[cfg(feature = "tracing")]
+        let _guard = span.enter();
+
         let (el, props) = cx.build_element(HTML_NS, &self.name);
 
         let mut child_elements = vec![];
@@ -160,14 +313,21 @@ where
         let mut splice = ChildrenSplice::new(&mut child_elements, &mut scratch, &el);
 
         let (id, children_states) = cx.with_new_id(|cx| self.children.build(cx, &mut splice));
+        let produced = splice.len();
+        debug_check_children(
+            &self.name,
+            self.children.count(&children_states),
+            produced,
+            &child_elements,
+            &scratch,
+        );
 
-        debug_assert!(scratch.is_empty());
+        let mut has_debug_id = false;
+        cx.update_debug_id(&el, id, &mut has_debug_id);
 
-        // Set the id used internally to the `data-debugid` attribute.
-        // This allows the user to see if an element has been re-created or only altered.
-        #[cfg(debug_assertions)]
-        el.set_attribute("data-debugid", &id.to_raw().to_string())
-            .unwrap_throw();
+        #[cfg(feature = "tracing")]
+        span.record("id", id.to_raw())
+            .record("children", child_elements.len());
 
         let el = el.dyn_into().unwrap_throw();
         let state = ElementState {
@@ -175,6 +335,7 @@ where
             child_elements,
             scratch,
             props,
+            has_debug_id,
         };
         (id, state, el)
     }
@@ -187,6 +348,11 @@ where
         state: &mut Self::State,
         element: &mut Self::Element,
     ) -> ChangeFlags {
+        #[cfg(feature = "tracing")]
+        let span = rebuild_span(&self.name, *id);
+        #[cfg(feature = "tracing")]
+        let _guard = span.enter();
+
         let mut changed = ChangeFlags::empty();
 
         // update tag name
@@ -203,10 +369,12 @@ where
                 new_element.append_child(&child).unwrap_throw();
             }
             *element = new_element.dyn_into().unwrap_throw();
+            state.has_debug_id = false;
             changed |= ChangeFlags::STRUCTURE;
         }
 
         changed |= cx.rebuild_element(element, &mut state.props);
+        cx.update_debug_id(element, *id, &mut state.has_debug_id);
 
         // update children
         let mut splice =
@@ -215,8 +383,20 @@ where
             self.children
                 .rebuild(cx, &prev.children, &mut state.children_states, &mut splice)
         });
-        debug_assert!(state.scratch.is_empty());
+        let produced = splice.len();
+        debug_check_children(
+            &self.name,
+            self.children.count(&state.children_states),
+            produced,
+            &state.child_elements,
+            &state.scratch,
+        );
         changed.remove(ChangeFlags::STRUCTURE);
+
+        #[cfg(feature = "tracing")]
+        span.record("children", state.child_elements.len())
+            .record("changed", tracing::field::debug(changed));
+
         changed
     }
 
@@ -283,6 +463,11 @@ macro_rules! define_element {
             type Element = web_sys::$dom_interface;
 
             fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+                #[cfg(feature = "tracing")]
+                let span = super::build_span($tag_name);
+                #[cfg(feature = "tracing")]
+                let _guard = span.enter();
+
                 let (el, props) = cx.build_element($ns, $tag_name);
 
                 let mut child_elements = vec![];
@@ -290,13 +475,20 @@ macro_rules! define_element {
                 let mut splice = ChildrenSplice::new(&mut child_elements, &mut scratch, &el);
 
                 let (id, children_states) = cx.with_new_id(|cx| self.0.build(cx, &mut splice));
-                debug_assert!(scratch.is_empty());
+                let produced = crate::ElementsSplice::len(&splice);
+                super::debug_check_children(
+                    $tag_name,
+                    self.0.count(&children_states),
+                    produced,
+                    &child_elements,
+                    &scratch,
+                );
 
-                // Set the id used internally to the `data-debugid` attribute.
-                // This allows the user to see if an element has been re-created or only altered.
-                #[cfg(debug_assertions)]
-                el.set_attribute("data-debugid", &id.to_raw().to_string())
-                    .unwrap_throw();
+                let mut has_debug_id = false;
+                cx.update_debug_id(&el, id, &mut has_debug_id);
+
+                #[cfg(feature = "tracing")]
+                span.record("id", id.to_raw()).record("children", child_elements.len());
 
                 let el = el.dyn_into().unwrap_throw();
                 let state = ElementState {
@@ -304,6 +496,7 @@ macro_rules! define_element {
                     child_elements,
                     scratch,
                     props,
+                    has_debug_id,
                 };
                 (id, state, el)
             }
@@ -316,17 +509,35 @@ macro_rules! define_element {
                 state: &mut Self::State,
                 element: &mut Self::Element,
             ) -> ChangeFlags {
+                #[cfg(feature = "tracing")]
+                let span = super::rebuild_span($tag_name, *id);
+                #[cfg(feature = "tracing")]
+                let _guard = span.enter();
+
                 let mut changed = ChangeFlags::empty();
 
                 changed |= cx.rebuild_element(element, &mut state.props);
+                cx.update_debug_id(element, *id, &mut state.has_debug_id);
 
                 // update children
                 let mut splice = ChildrenSplice::new(&mut state.child_elements, &mut state.scratch, element);
                 changed |= cx.with_id(*id, |cx| {
                     self.0.rebuild(cx, &prev.0, &mut state.children_states, &mut splice)
                 });
-                debug_assert!(state.scratch.is_empty());
+                let produced = crate::ElementsSplice::len(&splice);
+                super::debug_check_children(
+                    $tag_name,
+                    self.0.count(&state.children_states),
+                    produced,
+                    &state.child_elements,
+                    &state.scratch,
+                );
                 changed.remove(ChangeFlags::STRUCTURE); // this is handled by the ChildrenSplice already
+
+                #[cfg(feature = "tracing")]
+                span.record("children", state.child_elements.len())
+                    .record("changed", tracing::field::debug(changed));
+
                 changed
             }
 
@@ -498,6 +709,331 @@ pub mod html {
         (Slot, slot, HtmlSlotElement),
         (Template, template, HtmlTemplateElement),
     );
+
+    use std::collections::BTreeMap;
+
+    use crate::{interfaces::Element as _, Attr};
+
+    type CowStr = std::borrow::Cow<'static, str>;
+
+    /// The `<option>` views created by [`options`].
+    pub type Options<T, A = ()> = Vec<Attr<OptionElement<T, A, CowStr>, T, A>>;
+
+    /// Builder function for `<option>` elements from `(value, label)` pairs, e.g. for the children of a [`select`].
+    pub fn options<T, A, V, L>(items: impl IntoIterator<Item = (V, L)>) -> Options<T, A>
+    where
+        V: Into<CowStr>,
+        L: Into<CowStr>,
+    {
+        items
+            .into_iter()
+            .map(|(value, label)| option(label.into()).attr("value", value.into()))
+            .collect()
+    }
+
+    /// Builder function for an `<optgroup>` element with the given `label`, containing the [`options`] built from `items`.
+    pub fn option_group<T, A, V, L>(
+        label: impl Into<CowStr>,
+        items: impl IntoIterator<Item = (V, L)>,
+    ) -> Attr<Optgroup<T, A, Options<T, A>>, T, A>
+    where
+        V: Into<CowStr>,
+        L: Into<CowStr>,
+    {
+        optgroup(options(items)).attr("label", label.into())
+    }
+
+    /// The `<track>` view created by [`subtitle_track`].
+    pub type SubtitleTrack<T, A = ()> =
+        Attr<Attr<Attr<Attr<Attr<Track<T, A, ()>, T, A>, T, A>, T, A>, T, A>, T, A>;
+
+    /// Builder function for a `<track kind="subtitles">` with the subtitles at `src` in the language `srclang`
+    /// (e.g. `"en"`), e.g. for the children of a [`video`].
+    ///
+    /// `label` is shown in the list of subtitles, and the `default` track is shown,
+    /// unless the user prefers other subtitles. Only one track of a video should be the default,
+    /// see [`subtitle_tracks`].
+    pub fn subtitle_track<T, A>(
+        src: impl Into<CowStr>,
+        srclang: impl Into<CowStr>,
+        label: impl Into<CowStr>,
+        default: bool,
+    ) -> SubtitleTrack<T, A> {
+        track(())
+            .attr("kind", "subtitles")
+            .attr("src", src.into())
+            .attr("srclang", srclang.into())
+            .attr("label", label.into())
+            .attr("default", default)
+    }
+
+    /// Builder function for the [`subtitle_track`]s from `(src, srclang, label)` tuples,
+    /// where the first track in the language `default_srclang` (if any) is the default.
+    pub fn subtitle_tracks<T, A, S, L, N>(
+        tracks: impl IntoIterator<Item = (S, L, N)>,
+        default_srclang: Option<&str>,
+    ) -> Vec<SubtitleTrack<T, A>>
+    where
+        S: Into<CowStr>,
+        L: Into<CowStr>,
+        N: Into<CowStr>,
+    {
+        let mut default_srclang = default_srclang;
+        tracks
+            .into_iter()
+            .map(|(src, srclang, label)| {
+                let srclang = srclang.into();
+                let default = default_srclang == Some(&*srclang);
+                if default {
+                    default_srclang = None;
+                }
+                subtitle_track(src, srclang, label, default)
+            })
+            .collect()
+    }
+
+    /// The `<tr>` views of the body of a [`table_from`], keyed by the row keys.
+    pub type TableRows<K, T, A = ()> = BTreeMap<K, Tr<T, A, Vec<Td<T, A, CowStr>>>>;
+
+    /// The `<table>` view created by [`table_from`].
+    pub type TableFrom<K, T, A = ()> = Table<
+        T,
+        A,
+        (
+            Thead<T, A, Tr<T, A, Vec<Th<T, A, CowStr>>>>,
+            Tbody<T, A, TableRows<K, T, A>>,
+        ),
+    >;
+
+    /// Builder function for a `<table>` with a `<thead>` containing `headers`,
+    /// and a `<tbody>` with a row for each of the `(key, row)` pairs in `rows`.
+    ///
+    /// Rows with fewer cells than `headers` are padded with empty cells.
+    /// The keys identify the rows like the ones of [`for_each_map`](crate::for_each_map),
+    /// so e.g. inserting a row only builds that row, and the `<tr>` of a key is kept across
+    /// rebuilds. The rows are ordered by key, the order of `rows` doesn't matter.
+    /// When a key occurs more than once, the last of its rows is used.
+    pub fn table_from<T, A, S, K, R>(
+        headers: &[S],
+        rows: impl IntoIterator<Item = (K, R)>,
+    ) -> TableFrom<K, T, A>
+    where
+        S: Clone + Into<CowStr>,
+        K: Ord + Clone,
+        R: IntoIterator,
+        R::Item: Into<CowStr>,
+    {
+        let header_row = tr(headers
+            .iter()
+            .map(|header| th(header.clone().into()))
+            .collect());
+        let rows = rows
+            .into_iter()
+            .map(|(key, row)| {
+                let mut cells: Vec<_> = row.into_iter().map(|cell| td(cell.into())).collect();
+                let padding = headers.len().saturating_sub(cells.len());
+                cells.extend(std::iter::repeat_with(|| td(CowStr::Borrowed(""))).take(padding));
+                (key, tr(cells))
+            })
+            .collect();
+        table((thead(header_row), tbody(rows)))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{interfaces::HtmlProgressElement as _, AttributeValue};
+
+        fn value_and_label<T, A>(option: &Attr<OptionElement<T, A, CowStr>, T, A>) -> (&str, &str) {
+            assert_eq!(option.name, "value");
+            let Some(AttributeValue::String(value)) = &option.value else {
+                panic!("option has no value");
+            };
+            (value, &option.element.0)
+        }
+
+        #[test]
+        fn options_from_tuples() {
+            let select = select::<(), (), _>(options([
+                ("red", "Red"),
+                ("green", "Green"),
+                ("blue", "Blue"),
+            ]));
+            let options: Vec<_> = select.0.iter().map(value_and_label).collect();
+            assert_eq!(
+                options,
+                [("red", "Red"), ("green", "Green"), ("blue", "Blue")]
+            );
+        }
+
+        fn cell_texts<T, A, C>(row: &Tr<T, A, Vec<C>>, text: impl Fn(&C) -> &str) -> Vec<&str> {
+            row.0.iter().map(text).collect()
+        }
+
+        #[test]
+        fn table_from_rows() {
+            let table = table_from::<(), (), _, _, _>(
+                &["Name", "Role", "Location"],
+                [
+                    (2, ["Grace", "Admiral", "Arlington"]),
+                    (1, ["Ada", "Engineer", "London"]),
+                ],
+            );
+            let (thead, tbody) = &table.0;
+            assert_eq!(
+                cell_texts(&thead.0, |th| &th.0),
+                ["Name", "Role", "Location"]
+            );
+            let rows: Vec<_> = tbody
+                .0
+                .values()
+                .map(|row| cell_texts(row, |td| &td.0))
+                .collect();
+            assert_eq!(
+                rows,
+                [
+                    ["Ada", "Engineer", "London"],
+                    ["Grace", "Admiral", "Arlington"]
+                ]
+            );
+        }
+
+        #[test]
+        fn ragged_rows_are_padded() {
+            let table = table_from::<(), (), _, _, _>(
+                &["Name", "Role", "Location"],
+                [
+                    ("ada", vec!["Ada"]),
+                    ("grace", vec!["Grace", "Admiral", "Arlington", "USA"]),
+                ],
+            );
+            let rows = &table.0 .1 .0;
+            assert_eq!(cell_texts(&rows["ada"], |td| &td.0), ["Ada", "", ""]);
+            assert_eq!(
+                cell_texts(&rows["grace"], |td| &td.0),
+                ["Grace", "Admiral", "Arlington", "USA"]
+            );
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[wasm_bindgen_test::wasm_bindgen_test]
+        fn table_from_builds_cells() {
+            let mut cx = crate::Cx::new();
+            let view = table_from::<(), (), _, _, _>(
+                &["a", "b", "c"],
+                [(1, vec!["1", "2"]), (2, vec!["4", "5", "6"])],
+            );
+            let (_, _, element) = View::build(&view, &mut cx);
+            let cells = element.query_selector_all("tbody td").unwrap();
+            let texts: Vec<_> = (0..cells.length())
+                .map(|i| cells.get(i).unwrap().text_content().unwrap())
+                .collect();
+            assert_eq!(texts, ["1", "2", "", "4", "5", "6"]);
+            assert_eq!(element.query_selector_all("thead th").unwrap().length(), 3);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[wasm_bindgen_test::wasm_bindgen_test]
+        fn table_rows_are_keyed() {
+            let table = |rows: &[(u32, &'static str)]| {
+                table_from::<(), (), _, _, _>(
+                    &["Name"],
+                    rows.iter().map(|(key, name)| (*key, [*name])),
+                )
+            };
+            let rows = |element: &web_sys::HtmlTableElement| {
+                let rows = element.query_selector_all("tbody tr").unwrap();
+                (0..rows.length())
+                    .map(|i| rows.get(i).unwrap())
+                    .collect::<Vec<_>>()
+            };
+
+            let mut cx = crate::Cx::new();
+            let view = table(&[(1, "Ada"), (2, "Grace"), (3, "Margaret")]);
+            let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+            let built = rows(&element);
+
+            // reordering the rows, renaming one and inserting one in front keeps the row nodes
+            let next = table(&[(3, "Margaret"), (0, "Alan"), (2, "Grace H."), (1, "Ada")]);
+            View::rebuild(&next, &mut cx, &view, &mut id, &mut state, &mut element);
+            let rebuilt = rows(&element);
+            assert_eq!(rebuilt.len(), 4);
+            assert_eq!(rebuilt[1..], built[..]);
+            let texts: Vec<_> = rebuilt
+                .iter()
+                .map(|row| row.text_content().unwrap())
+                .collect();
+            assert_eq!(texts, ["Alan", "Ada", "Grace H.", "Margaret"]);
+        }
+
+        #[test]
+        fn progress_value() {
+            let determinate = progress::<(), (), _>(()).value(0.5);
+            assert_eq!(determinate.value, Some(AttributeValue::F64(0.5)));
+            let indeterminate = progress::<(), (), _>(()).value(None);
+            assert_eq!(indeterminate.name, "value");
+            assert_eq!(indeterminate.value, None);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[wasm_bindgen_test::wasm_bindgen_test]
+        fn progress_value_sets_dom_property() {
+            let mut cx = crate::Cx::new();
+            let view = progress::<(), (), _>(()).value(0.5).max(2.0);
+            let (_, _, element) = View::build(&view, &mut cx);
+            assert_eq!(element.value(), 0.5);
+            assert_eq!(element.max(), 2.0);
+
+            let view = progress::<(), (), _>(()).value(None);
+            let (_, _, indeterminate) = View::build(&view, &mut cx);
+            assert!(!indeterminate.has_attribute("value"));
+        }
+
+        #[test]
+        fn only_one_subtitle_track_is_default() {
+            let tracks = subtitle_tracks::<(), (), _, _, _>(
+                [
+                    ("en.vtt", "en", "English"),
+                    ("de.vtt", "de", "Deutsch"),
+                    ("de-ch.vtt", "de", "Schwiizerdütsch"),
+                ],
+                Some("de"),
+            );
+            let defaults: Vec<_> = tracks
+                .iter()
+                .map(|track| {
+                    assert_eq!(track.name, "default");
+                    track.value.is_some()
+                })
+                .collect();
+            assert_eq!(defaults, [false, true, false]);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[wasm_bindgen_test::wasm_bindgen_test]
+        fn video_with_default_subtitle_track() {
+            let mut cx = crate::Cx::new();
+            let view = video::<(), (), _>(subtitle_track("en.vtt", "en", "English", true));
+            let (_, _, element) = View::build(&view, &mut cx);
+            let track = element.query_selector("track").unwrap().unwrap();
+            let attr = |name| track.get_attribute(name);
+            assert_eq!(attr("kind").as_deref(), Some("subtitles"));
+            assert_eq!(attr("src").as_deref(), Some("en.vtt"));
+            assert_eq!(attr("srclang").as_deref(), Some("en"));
+            assert_eq!(attr("label").as_deref(), Some("English"));
+            assert!(track.has_attribute("default"));
+        }
+
+        #[test]
+        fn grouped_options() {
+            let group =
+                option_group::<(), (), _, _>("Warm", [("red", "Red"), ("orange", "Orange")]);
+            assert_eq!(group.name, "label");
+            assert_eq!(group.value, Some(AttributeValue::String("Warm".into())));
+            assert_eq!(group.element.0.len(), 2);
+            assert_eq!(value_and_label(&group.element.0[1]), ("orange", "Orange"));
+        }
+    }
 }
 
 pub mod mathml {
@@ -706,3 +1242,444 @@ pub mod svg {
         (SvgView, view, SvgViewElement),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttributeValue;
+
+    #[test]
+    fn slotted_sets_slot_attribute() {
+        let card = custom_element::<(), (), _>("my-card", slotted("header", html::h1("Title")));
+        assert_eq!(card.children.name, "slot");
+        assert_eq!(
+            card.children.value,
+            Some(AttributeValue::String("header".into()))
+        );
+    }
+
+    #[test]
+    fn slotted_into_default_slot_has_no_slot_attribute() {
+        let card = custom_element::<(), (), _>("my-card", slotted("", html::p("Content")));
+        assert_eq!(card.children.value, None);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn nested_sequences_are_flattened() {
+        type List = html::Ul<(), (), Vec<Vec<html::Li<(), (), &'static str>>>>;
+
+        fn texts(element: &web_sys::HtmlElement) -> Vec<String> {
+            let children = element.child_nodes();
+            (0..children.length())
+                .map(|i| children.get(i).unwrap().text_content().unwrap())
+                .collect()
+        }
+
+        let mut cx = Cx::new();
+        let view: List = html::ul(vec![
+            vec![html::li("a"), html::li("b")],
+            vec![html::li("c")],
+        ]);
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        assert_eq!(texts(&element), ["a", "b", "c"]);
+
+        // the first inner sequence shrinks, the second one grows
+        let next: List = html::ul(vec![
+            vec![html::li("a")],
+            vec![html::li("c"), html::li("d"), html::li("e")],
+        ]);
+        View::rebuild(&next, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(texts(&element), ["a", "c", "d", "e"]);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn option_is_inserted_between_siblings() {
+        type Row = html::Div<
+            (),
+            (),
+            (
+                html::B<(), (), &'static str>,
+                Option<html::I<(), (), &'static str>>,
+                html::U<(), (), &'static str>,
+            ),
+        >;
+        fn row(shown: bool) -> Row {
+            html::div((html::b("a"), shown.then(|| html::i("b")), html::u("c")))
+        }
+
+        let mut cx = Cx::new();
+        let hidden = row(false);
+        let (mut id, mut state, mut element) = View::build(&hidden, &mut cx);
+        assert_eq!(element.inner_html(), "<b>a</b><u>c</u>");
+        let last = element.last_child().unwrap();
+
+        let shown = row(true);
+        View::rebuild(&shown, &mut cx, &hidden, &mut id, &mut state, &mut element);
+        assert_eq!(element.inner_html(), "<b>a</b><i>b</i><u>c</u>");
+        assert_eq!(element.last_child().unwrap(), last);
+
+        View::rebuild(&hidden, &mut cx, &shown, &mut id, &mut state, &mut element);
+        assert_eq!(element.inner_html(), "<b>a</b><u>c</u>");
+        View::rebuild(&shown, &mut cx, &hidden, &mut id, &mut state, &mut element);
+        assert_eq!(element.inner_html(), "<b>a</b><i>b</i><u>c</u>");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn unchanged_child_is_kept_when_siblings_change() {
+        type Row = html::Div<
+            (),
+            (),
+            (
+                crate::OneOf2<html::B<(), (), &'static str>, html::I<(), (), &'static str>>,
+                html::Span<(), (), &'static str>,
+                html::U<(), (), &'static str>,
+            ),
+        >;
+        fn row(bold: bool, last: &'static str) -> Row {
+            let first = if bold {
+                crate::OneOf2::A(html::b("first"))
+            } else {
+                crate::OneOf2::B(html::i("first"))
+            };
+            html::div((first, html::span("middle"), html::u(last)))
+        }
+        fn child(element: &web_sys::HtmlElement, index: u32) -> web_sys::Element {
+            element
+                .child_nodes()
+                .get(index)
+                .unwrap()
+                .dyn_into()
+                .unwrap()
+        }
+
+        let mut cx = Cx::new();
+        cx.set_debug_ids(true);
+        let view = row(true, "a");
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        let middle = child(&element, 1);
+        let debug_id = middle.get_attribute("data-debugid");
+        assert!(debug_id.is_some());
+
+        // the first child is replaced by another element, the text of the last one changes
+        let next = row(false, "b");
+        View::rebuild(&next, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(child(&element, 0).tag_name(), "I");
+        assert_eq!(child(&element, 2).text_content().unwrap(), "b");
+        assert_eq!(child(&element, 1), middle);
+        assert_eq!(middle.get_attribute("data-debugid"), debug_id);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn mixed_text_and_element_children() {
+        type Greeting = html::Div<(), (), (String, html::Strong<(), (), &'static str>, CowStr)>;
+
+        fn greeting(name: &str, suffix: &'static str) -> Greeting {
+            html::div((format!("Hello {name} "), html::strong("and"), suffix.into()))
+        }
+
+        let mut cx = Cx::new();
+        let view = greeting("world", "!");
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        assert_eq!(element.inner_html(), "Hello world <strong>and</strong>!");
+        let text = element.first_child().unwrap();
+
+        // the text nodes are updated in place, also when the text becomes empty
+        let next = greeting("xilem", "");
+        View::rebuild(&next, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(element.inner_html(), "Hello xilem <strong>and</strong>");
+        assert_eq!(element.child_nodes().length(), 3);
+        assert_eq!(element.first_child().unwrap(), text);
+
+        let last = greeting("xilem", "?");
+        View::rebuild(&last, &mut cx, &next, &mut id, &mut state, &mut element);
+        assert_eq!(element.inner_html(), "Hello xilem <strong>and</strong>?");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn map_entries_are_keyed() {
+        use std::collections::BTreeMap;
+
+        use crate::for_each_map;
+
+        type List = html::Ul<(), (), BTreeMap<u32, html::Li<(), (), &'static str>>>;
+        fn list(map: &BTreeMap<u32, &'static str>) -> List {
+            html::ul(for_each_map(map, |_, value| html::li(*value)))
+        }
+        let items = |element: &web_sys::HtmlElement| {
+            let children = element.child_nodes();
+            (0..children.length())
+                .map(|i| children.item(i).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let mut cx = Cx::new();
+        let mut map = BTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+        let view = list(&map);
+        let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+        let built = items(&element);
+
+        // only the value changes, so the node is reused
+        map.insert(2, "B");
+        let next = list(&map);
+        View::rebuild(&next, &mut cx, &view, &mut id, &mut state, &mut element);
+        assert_eq!(items(&element), built);
+        assert_eq!(built[1].text_content().as_deref(), Some("B"));
+
+        // removing and inserting entries leaves the other nodes alone
+        map.remove(&1);
+        map.insert(0, "z");
+        map.insert(4, "d");
+        let last = list(&map);
+        View::rebuild(&last, &mut cx, &next, &mut id, &mut state, &mut element);
+        let texts: Vec<_> = items(&element)
+            .iter()
+            .map(|item| item.text_content().unwrap())
+            .collect();
+        assert_eq!(texts, ["z", "B", "c", "d"]);
+        assert_eq!(items(&element)[1..3], built[1..]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn indexed_items_pass_their_index_to_handlers() {
+        use crate::{indexed, interfaces::Element as _, test_util::mount};
+
+        type Items = Vec<&'static str>;
+        fn list(items: &Items) -> impl Element<Items> {
+            html::ul(indexed(items, |index, item| {
+                html::li((
+                    *item,
+                    html::button("Remove").on_click(move |items: &mut Items, _| {
+                        items.remove(index);
+                    }),
+                ))
+            }))
+        }
+        let click = || web_sys::MouseEvent::new("click").unwrap();
+
+        let mut harness = mount(vec!["a", "b", "c"], list(&vec!["a", "b", "c"]));
+        harness.dispatch_event("li:nth-child(2) button", &click());
+        assert_eq!(*harness.app_state(), ["a", "c"]);
+
+        // after the removal, "c" is at index 1, which its button has to use
+        harness.rebuild(list(harness.app_state()));
+        harness.dispatch_event("li:nth-child(2) button", &click());
+        assert_eq!(*harness.app_state(), ["a"]);
+        harness.rebuild(list(harness.app_state()));
+        assert_eq!(harness.root_element().text_content().unwrap(), "aRemove");
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn separators_are_only_between_items() {
+        use crate::{separated, test_util::mount};
+
+        fn list(items: &[&'static str]) -> impl Element<()> {
+            html::div(separated(
+                items.iter().map(|item| html::span(*item)),
+                || html::hr(()),
+            ))
+        }
+        fn node_names(element: &web_sys::Element) -> Vec<String> {
+            let children = element.child_nodes();
+            (0..children.length())
+                .map(|i| children.get(i).unwrap().node_name())
+                .collect()
+        }
+
+        let mut harness = mount((), list(&["a", "b", "c"]));
+        let element = harness.root_element();
+        assert_eq!(node_names(&element), ["SPAN", "HR", "SPAN", "HR", "SPAN"]);
+        assert_eq!(element.text_content().unwrap(), "abc");
+
+        harness.rebuild(list(&["a"]));
+        assert_eq!(node_names(&element), ["SPAN"]);
+        harness.rebuild(list(&[]));
+        assert!(node_names(&element).is_empty());
+        harness.rebuild(list(&["a", "b"]));
+        assert_eq!(node_names(&element), ["SPAN", "HR", "SPAN"]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn inert_can_be_toggled() {
+        use crate::{interfaces::Element as _, test_util::mount};
+
+        let view =
+            |inert: bool| html::div::<(), (), _>(html::button("Behind the modal")).inert(inert);
+        let mut harness = mount((), view(true));
+        let backdrop = harness.root_element();
+        let is_inert = |element: &web_sys::Element| {
+            js_sys::Reflect::get(element, &"inert".into())
+                .unwrap()
+                .as_bool()
+        };
+        assert!(backdrop.has_attribute("inert"));
+        assert_eq!(is_inert(&backdrop), Some(true));
+
+        harness.rebuild(view(false));
+        assert!(!backdrop.has_attribute("inert"));
+        assert_eq!(is_inert(&backdrop), Some(false));
+    }
+
+    #[test]
+    fn children_count_mismatch_is_described() {
+        assert_eq!(children_count_mismatch(3, 3, 0), None);
+        // an empty sequence is fine as well
+        assert_eq!(children_count_mismatch(0, 0, 0), None);
+        assert_eq!(
+            children_count_mismatch(2, 1, 0).as_deref(),
+            Some("the sequence reported a count of 2, but produced 1 elements")
+        );
+        assert_eq!(
+            children_count_mismatch(1, 1, 2).as_deref(),
+            Some("2 previous elements were neither mutated nor deleted")
+        );
+    }
+
+    #[cfg(all(target_arch = "wasm32", debug_assertions))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    #[should_panic(expected = "reported a count of 2, but produced 1 elements")]
+    fn miscounting_sequence_panics() {
+        /// Reports one element more than its child builds.
+        struct Miscounted<V>(V);
+
+        impl<V: ViewSequence<(), ()>> ViewSequence<(), ()> for Miscounted<V> {
+            type State = V::State;
+
+            fn build(&self, cx: &mut Cx, elements: &mut dyn ElementsSplice) -> Self::State {
+                self.0.build(cx, elements)
+            }
+
+            fn rebuild(
+                &self,
+                cx: &mut Cx,
+                prev: &Self,
+                state: &mut Self::State,
+                elements: &mut dyn ElementsSplice,
+            ) -> ChangeFlags {
+                self.0.rebuild(cx, &prev.0, state, elements)
+            }
+
+            fn message(
+                &self,
+                id_path: &[Id],
+                state: &mut Self::State,
+                message: Box<dyn std::any::Any>,
+                app_state: &mut (),
+            ) -> MessageResult<()> {
+                self.0.message(id_path, state, message, app_state)
+            }
+
+            fn count(&self, state: &Self::State) -> usize {
+                self.0.count(state) + 1
+            }
+        }
+
+        let mut cx = Cx::new();
+        let view = html::div(Miscounted(html::span("only child")));
+        View::build(&view, &mut cx);
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "tracing"))]
+mod tracing_tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{field::Field, span, Metadata, Subscriber};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::html;
+    use crate::{Cx, View};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    /// A span with the `element` field and the index of the span it was entered in.
+    struct RecordedSpan {
+        name: &'static str,
+        element: String,
+        parent: Option<usize>,
+    }
+
+    #[derive(Default)]
+    struct SpanRecorder {
+        spans: Mutex<Vec<RecordedSpan>>,
+        entered: Mutex<Vec<usize>>,
+    }
+
+    #[derive(Default)]
+    struct ElementField(String);
+
+    impl tracing::field::Visit for ElementField {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "element" {
+                self.0 = value.to_string();
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+            let mut element = ElementField::default();
+            attrs.record(&mut element);
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(RecordedSpan {
+                name: attrs.metadata().name(),
+                element: element.0,
+                parent: self.entered.lock().unwrap().last().copied(),
+            });
+            span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, _event: &tracing::Event<'_>) {}
+
+        fn enter(&self, span: &span::Id) {
+            let index = span.into_u64() as usize - 1;
+            self.entered.lock().unwrap().push(index);
+        }
+
+        fn exit(&self, _span: &span::Id) {
+            self.entered.lock().unwrap().pop();
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn nested_rebuild_emits_spans() {
+        let recorder = Arc::new(SpanRecorder::default());
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let mut cx = Cx::new();
+            let view: html::Div<(), (), _> = html::div(html::span("text"));
+            let (mut id, mut state, mut element) = View::build(&view, &mut cx);
+            View::rebuild(&view, &mut cx, &view, &mut id, &mut state, &mut element);
+        });
+
+        let spans = recorder.spans.lock().unwrap();
+        let rebuilds: Vec<_> = spans
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| span.name == "rebuild")
+            .collect();
+        assert_eq!(rebuilds.len(), 2);
+        let (div_index, div) = rebuilds[0];
+        let (_, span) = rebuilds[1];
+        assert_eq!(div.element, "div");
+        assert_eq!(span.element, "span");
+        assert_eq!(span.parent, Some(div_index));
+        assert_eq!(spans.iter().filter(|span| span.name == "build").count(), 2);
+    }
+}
@@ -64,10 +64,15 @@ where
     }
 }
 
+/// Creates a listener that sends the `event`s to the view at the current id path.
+///
+/// With `prevent_default`, the browser's default action is prevented in the listener itself,
+/// as the message may be handled after the event was dispatched.
 fn create_event_listener<Ev: JsCast + 'static>(
     target: &web_sys::EventTarget,
     event: impl Into<Cow<'static, str>>,
     options: EventListenerOptions,
+    prevent_default: bool,
     cx: &Cx,
 ) -> gloo::events::EventListener {
     let thunk = cx.message_thunk();
@@ -76,6 +81,9 @@ fn create_event_listener<Ev: JsCast + 'static>(
         event,
         options,
         move |event: &web_sys::Event| {
+            if prevent_default {
+                event.prevent_default();
+            }
             let event = (*event).clone().dyn_into::<Ev>().unwrap_throw();
             thunk.push_message(event);
         },
@@ -111,6 +119,7 @@ where
                 element.as_node_ref(),
                 self.event.clone(),
                 self.options,
+                false,
                 cx,
             );
             let state = OnEventState {
@@ -149,6 +158,7 @@ where
                     element.as_node_ref(),
                     self.event.clone(),
                     self.options,
+                    false,
                     cx,
                 );
                 changed |= ChangeFlags::OTHER_CHANGE;
@@ -193,9 +203,16 @@ crate::interfaces::impl_dom_interfaces_for_ty!(
     }
 );
 
-macro_rules! event_definitions {
-    ($(($ty_name:ident, $event_name:literal, $web_sys_ty:ident)),*) => {
-        $(
+/// Defines a view that listens to `$event_name` and calls its callback with a `$payload_ty`,
+/// which is extracted from the `web_sys::$web_sys_ty` event via `$extract`.
+///
+/// When `$extract` returns `None`, the callback isn't invoked.
+/// The listener uses `$options` (or [`EventListenerOptions::default`] if omitted) until changed by the user.
+macro_rules! event_definition {
+    ($ty_name:ident, $event_name:literal, $web_sys_ty:ident, $payload_ty:ty, $extract:expr) => {
+        event_definition!($ty_name, $event_name, $web_sys_ty, $payload_ty, $extract, EventListenerOptions::default());
+    };
+    ($ty_name:ident, $event_name:literal, $web_sys_ty:ident, $payload_ty:ty, $extract:expr, $options:expr) => {
         $crate::interfaces::impl_dom_interfaces_for_ty!(
             Element,
             $ty_name,
@@ -203,7 +220,7 @@ macro_rules! event_definitions {
             vars_on_ty: <C,>,
             bounds: {
                 OA: OptionalAction<A>,
-                C: Fn(&mut T, web_sys::$web_sys_ty ) -> OA,
+                C: Fn(&mut T, $payload_ty) -> OA,
             }
         );
 
@@ -211,6 +228,7 @@ macro_rules! event_definitions {
             target: E,
             callback: C,
             options: EventListenerOptions,
+            prevent_default: bool,
             phantom: PhantomData<fn() -> (T, A)>,
         }
 
@@ -218,13 +236,22 @@ macro_rules! event_definitions {
             pub fn new(target: E, callback: C) -> Self {
                 Self {
                     target,
-                    options: Default::default(),
+                    options: $options,
+                    prevent_default: false,
                     callback,
                     phantom: PhantomData,
                 }
             }
 
-            /// Whether the event handler should be passive. (default = `true`)
+            /// Prevent the browser's default action for the event, e.g. to show a custom menu
+            /// instead of the browser's context menu. This makes the listener non-passive.
+            pub fn prevent_default(mut self) -> Self {
+                self.prevent_default = true;
+                self.options.passive = false;
+                self
+            }
+
+            /// Whether the event handler should be passive. (default = `true`, except for listeners that prevent the default action themselves)
             ///
             /// Passive event handlers can't prevent the browser's default action from
             /// running (otherwise possible with `event.prevent_default()`), which
@@ -241,7 +268,7 @@ macro_rules! event_definitions {
         impl<E, T, A, C, OA> View<T, A> for $ty_name<E, T, A, C>
         where
             OA: OptionalAction<A>,
-            C: Fn(&mut T, web_sys::$web_sys_ty) -> OA,
+            C: Fn(&mut T, $payload_ty) -> OA,
             E: Element<T, A>,
         {
             type State = OnEventState<E::State>;
@@ -251,7 +278,7 @@ macro_rules! event_definitions {
             fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
                 let (id, (element, state)) = cx.with_new_id(|cx| {
                     let (child_id, child_state, el) = self.target.build(cx);
-                    let listener = create_event_listener::<web_sys::$web_sys_ty>(el.as_node_ref(), $event_name, self.options, cx);
+                    let listener = create_event_listener::<web_sys::$web_sys_ty>(el.as_node_ref(), $event_name, self.options, self.prevent_default, cx);
                     (el, OnEventState { child_state, child_id, listener })
                 });
                 (id, state, element)
@@ -272,8 +299,8 @@ macro_rules! event_definitions {
                         changed |= ChangeFlags::OTHER_CHANGE;
                     }
                     // TODO check equality of prev and current element somehow
-                    if changed.contains(ChangeFlags::STRUCTURE) {
-                        state.listener = create_event_listener::<web_sys::$web_sys_ty>(element.as_node_ref(), $event_name, self.options, cx);
+                    if prev.prevent_default != self.prevent_default || changed.contains(ChangeFlags::STRUCTURE) {
+                        state.listener = create_event_listener::<web_sys::$web_sys_ty>(element.as_node_ref(), $event_name, self.options, self.prevent_default, cx);
                         changed |= ChangeFlags::OTHER_CHANGE;
                     }
                     changed
@@ -290,7 +317,10 @@ macro_rules! event_definitions {
                 match id_path {
                     [] if message.downcast_ref::<web_sys::$web_sys_ty>().is_some() => {
                         let event = message.downcast::<web_sys::$web_sys_ty>().unwrap();
-                        match (self.callback)(app_state, *event).action() {
+                        let Some(payload) = ($extract)(*event) else {
+                            return MessageResult::Nop;
+                        };
+                        match (self.callback)(app_state, payload).action() {
                             Some(a) => MessageResult::Action(a),
                             None => MessageResult::Nop,
                         }
@@ -302,6 +332,13 @@ macro_rules! event_definitions {
                 }
             }
         }
+    };
+}
+
+macro_rules! event_definitions {
+    ($(($ty_name:ident, $event_name:literal, $web_sys_ty:ident)),*) => {
+        $(
+            event_definition!($ty_name, $event_name, $web_sys_ty, web_sys::$web_sys_ty, Some);
         )*
     };
 }
@@ -314,13 +351,14 @@ event_definitions!(
     (OnBeforeInput, "beforeinput", InputEvent),
     (OnBeforeMatch, "beforematch", Event),
     (OnBeforeToggle, "beforetoggle", Event),
-    (OnBlur, "blur", FocusEvent),
     (OnCancel, "cancel", Event),
     (OnCanPlay, "canplay", Event),
     (OnCanPlayThrough, "canplaythrough", Event),
     (OnChange, "change", Event),
     (OnClick, "click", MouseEvent),
     (OnClose, "close", Event),
+    (OnCompositionEnd, "compositionend", CompositionEvent),
+    (OnCompositionStart, "compositionstart", CompositionEvent),
     (OnContextLost, "contextlost", Event),
     (OnContextMenu, "contextmenu", MouseEvent),
     (OnContextRestored, "contextrestored", Event),
@@ -332,14 +370,10 @@ event_definitions!(
     (OnDragEnd, "dragend", Event),
     (OnDragEnter, "dragenter", Event),
     (OnDragLeave, "dragleave", Event),
-    (OnDragOver, "dragover", Event),
-    (OnDragStart, "dragstart", Event),
-    (OnDrop, "drop", Event),
     (OnDurationChange, "durationchange", Event),
     (OnEmptied, "emptied", Event),
     (OnEnded, "ended", Event),
     (OnError, "error", Event),
-    (OnFocus, "focus", FocusEvent),
     (OnFocusIn, "focusin", FocusEvent),
     (OnFocusOut, "focusout", FocusEvent),
     (OnFormData, "formdata", Event),
@@ -358,7 +392,6 @@ event_definitions!(
     (OnMouseOut, "mouseout", MouseEvent),
     (OnMouseOver, "mouseover", MouseEvent),
     (OnMouseUp, "mouseup", MouseEvent),
-    (OnPaste, "paste", Event),
     (OnPause, "pause", Event),
     (OnPlay, "play", Event),
     (OnPlaying, "playing", Event),
@@ -366,7 +399,6 @@ event_definitions!(
     (OnRateChange, "ratechange", Event),
     (OnReset, "reset", Event),
     (OnResize, "resize", Event),
-    (OnScroll, "scroll", Event),
     (OnScrollEnd, "scrollend", Event),
     (OnSecurityPolicyViolation, "securitypolicyviolation", Event),
     (OnSeeked, "seeked", Event),
@@ -379,6 +411,558 @@ event_definitions!(
     (OnTimeUpdate, "timeupdate", Event),
     (OnToggle, "toggle", Event),
     (OnVolumeChange, "volumechange", Event),
-    (OnWaiting, "waiting", Event),
-    (OnWheel, "wheel", WheelEvent)
+    (OnWaiting, "waiting", Event)
 );
+
+/// Whether the `contextmenu` event was triggered via the keyboard (e.g. the menu key or Shift+F10),
+/// rather than a right click (or a Ctrl+click on macOS).
+///
+/// The coordinates of a keyboard-triggered event don't refer to the pointer, so a custom menu should be
+/// positioned relative to the event target instead.
+pub fn is_keyboard_context_menu(event: &web_sys::MouseEvent) -> bool {
+    event.button() != 2 && !event.ctrl_key()
+}
+
+/// Returns the files selected in the `<input type="file">` element that fired `event`.
+fn input_files(event: web_sys::Event) -> Option<web_sys::FileList> {
+    event
+        .target()?
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .ok()?
+        .files()
+}
+
+event_definition!(OnFiles, "change", Event, web_sys::FileList, input_files);
+
+/// `valueAsNumber` is `NaN` when the input is empty (or its value isn't a number).
+fn number_or_none(value: f64) -> Option<f64> {
+    (!value.is_nan()).then_some(value)
+}
+
+/// Returns the value of the `<input>` element that fired `event` as number, if it has one.
+fn input_number(event: web_sys::Event) -> Option<Option<f64>> {
+    let input = event
+        .target()?
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .ok()?;
+    Some(number_or_none(input.value_as_number()))
+}
+
+event_definition!(OnNumberInput, "input", Event, Option<f64>, input_number);
+
+/// Returns the length of the value of the `<input>` or `<textarea>` element that fired `event`,
+/// in UTF-16 code units like the `maxlength` attribute, see [`HtmlInputElement::on_length_change`](crate::interfaces::HtmlInputElement::on_length_change).
+fn value_length(event: web_sys::Event) -> Option<usize> {
+    let target = event.target()?;
+    let value = match target.dyn_into::<web_sys::HtmlInputElement>() {
+        Ok(input) => input.value(),
+        Err(target) => target
+            .dyn_into::<web_sys::HtmlTextAreaElement>()
+            .ok()?
+            .value(),
+    };
+    Some(value.encode_utf16().count())
+}
+
+event_definition!(OnLengthChange, "input", Event, usize, value_length);
+
+/// Whether the `keydown` `event` is a press of Enter that isn't part of an IME composition.
+fn is_enter_key(event: &web_sys::KeyboardEvent) -> bool {
+    // Safari fires the `keydown` that commits a composition after `compositionend`,
+    // so it's only flagged by the `keyCode` 229 ("Process")
+    event.key() == "Enter" && !event.is_composing() && event.key_code() != 229
+}
+
+/// Returns the value of the `<input>` element when Enter was pressed in it.
+fn entered_value(event: web_sys::KeyboardEvent) -> Option<String> {
+    if !is_enter_key(&event) {
+        return None;
+    }
+    let input = event
+        .target()?
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .ok()?;
+    Some(input.value())
+}
+
+event_definition!(OnEnter, "keydown", KeyboardEvent, String, entered_value);
+
+/// Returns the element that lost (for `focus`) or gained (for `blur`) focus, if there's any.
+fn related_target(event: web_sys::FocusEvent) -> Option<Option<web_sys::EventTarget>> {
+    Some(event.related_target())
+}
+
+// `focus` and `blur` don't bubble, so these only fire for the element itself, not its children
+event_definition!(
+    OnFocus,
+    "focus",
+    FocusEvent,
+    Option<web_sys::EventTarget>,
+    related_target
+);
+event_definition!(
+    OnBlur,
+    "blur",
+    FocusEvent,
+    Option<web_sys::EventTarget>,
+    related_target
+);
+
+/// Turns the rendered text of a `contenteditable` element into plain text.
+///
+/// Browsers insert non-breaking spaces to keep consecutive spaces visible while editing,
+/// and a trailing `<br>` (rendered as a newline) to keep an empty last line editable.
+pub fn normalize_editable_text(text: &str) -> String {
+    let text = text.strip_suffix('\n').unwrap_or(text);
+    text.replace('\u{a0}', " ")
+}
+
+fn editable_text(event: web_sys::Event) -> Option<String> {
+    let element = event.target()?.dyn_into::<web_sys::HtmlElement>().ok()?;
+    // `innerText` (unlike `textContent`) respects line breaks from markup, e.g. of pasted rich content
+    Some(normalize_editable_text(&element.inner_text()))
+}
+
+event_definition!(OnContentChange, "input", Event, String, editable_text);
+
+// `drop` only fires when the drag ends over a valid drop target, so drag state set in `on_dragstart`
+// should be reset in `on_dragend`, which always fires on the element being dragged.
+fn drag_data(event: web_sys::DragEvent) -> Option<web_sys::DataTransfer> {
+    event.data_transfer()
+}
+
+/// Prevents the browser's default action, which is necessary to allow dropping on an element.
+fn drag_data_prevent_default(event: web_sys::DragEvent) -> Option<web_sys::DataTransfer> {
+    event.prevent_default();
+    event.data_transfer()
+}
+
+event_definition!(
+    OnDragStart,
+    "dragstart",
+    DragEvent,
+    web_sys::DataTransfer,
+    drag_data
+);
+event_definition!(
+    OnDragOver,
+    "dragover",
+    DragEvent,
+    web_sys::DataTransfer,
+    drag_data_prevent_default,
+    EventListenerOptions::enable_prevent_default()
+);
+event_definition!(
+    OnDrop,
+    "drop",
+    DragEvent,
+    web_sys::DataTransfer,
+    drag_data_prevent_default,
+    EventListenerOptions::enable_prevent_default()
+);
+
+/// The contents of a `paste` event, see [`Element::on_paste`](crate::interfaces::Element::on_paste).
+#[derive(Debug, Clone)]
+pub struct ClipboardData {
+    /// The pasted plain text (`text/plain`), empty if only e.g. an image was pasted.
+    ///
+    /// When files are pasted from a file manager, some platforms also put their names here,
+    /// so [`files`](Self::files) should be checked first.
+    pub text: String,
+    /// The pasted files, e.g. an image copied from another app or a screenshot,
+    /// which is delivered as `image/png` file.
+    pub files: Vec<web_sys::File>,
+    /// All pasted items, e.g. to read `text/html` of rich content.
+    pub data_transfer: web_sys::DataTransfer,
+}
+
+fn clipboard_data(event: web_sys::ClipboardEvent) -> Option<ClipboardData> {
+    let data_transfer = event.clipboard_data()?;
+    let text = data_transfer.get_data("text/plain").unwrap_or_default();
+    let files = match data_transfer.files() {
+        Some(files) => (0..files.length()).filter_map(|i| files.get(i)).collect(),
+        None => Vec::new(),
+    };
+    Some(ClipboardData {
+        text,
+        files,
+        data_transfer,
+    })
+}
+
+event_definition!(
+    OnPaste,
+    "paste",
+    ClipboardEvent,
+    ClipboardData,
+    clipboard_data
+);
+
+/// The assumed height of a line in pixels, used for `wheel` events with [`web_sys::WheelEvent::DOM_DELTA_LINE`].
+///
+/// Browsers don't expose the line height they used (Firefox reports line deltas for mouse wheels),
+/// so this is an approximation of a typical line of text.
+pub const WHEEL_LINE_HEIGHT: f64 = 16.0;
+
+/// Details of a `wheel` event, with the deltas normalized to pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelDetails {
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub alt_key: bool,
+    pub ctrl_key: bool,
+    pub meta_key: bool,
+    pub shift_key: bool,
+}
+
+/// Converts a `wheel` delta in the given `delta_mode` to pixels.
+///
+/// `page_size` is the size of a page in pixels along the axis of the delta
+/// (only used for [`web_sys::WheelEvent::DOM_DELTA_PAGE`]).
+pub fn normalize_wheel_delta(delta: f64, delta_mode: u32, page_size: f64) -> f64 {
+    match delta_mode {
+        web_sys::WheelEvent::DOM_DELTA_LINE => delta * WHEEL_LINE_HEIGHT,
+        web_sys::WheelEvent::DOM_DELTA_PAGE => delta * page_size,
+        _ => delta,
+    }
+}
+
+fn wheel_details(event: web_sys::WheelEvent) -> Option<WheelDetails> {
+    let delta_mode = event.delta_mode();
+    let (page_width, page_height) = if delta_mode == web_sys::WheelEvent::DOM_DELTA_PAGE {
+        let window = web_sys::window()?;
+        (
+            window.inner_width().ok()?.as_f64()?,
+            window.inner_height().ok()?.as_f64()?,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+    Some(WheelDetails {
+        delta_x: normalize_wheel_delta(event.delta_x(), delta_mode, page_width),
+        delta_y: normalize_wheel_delta(event.delta_y(), delta_mode, page_height),
+        alt_key: event.alt_key(),
+        ctrl_key: event.ctrl_key(),
+        meta_key: event.meta_key(),
+        shift_key: event.shift_key(),
+    })
+}
+
+event_definition!(OnWheel, "wheel", WheelEvent, WheelDetails, wheel_details);
+
+fn transition_property(event: web_sys::TransitionEvent) -> Option<String> {
+    Some(event.property_name())
+}
+
+fn animation_name(event: web_sys::AnimationEvent) -> Option<String> {
+    Some(event.animation_name())
+}
+
+// Both events bubble, so they also fire for transitions and animations of children
+event_definition!(
+    OnTransitionEnd,
+    "transitionend",
+    TransitionEvent,
+    String,
+    transition_property
+);
+event_definition!(
+    OnAnimationEnd,
+    "animationend",
+    AnimationEvent,
+    String,
+    animation_name
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use web_sys::WheelEvent;
+
+    #[test]
+    fn editable_text_is_plain_text() {
+        assert_eq!(normalize_editable_text("hello"), "hello");
+        assert_eq!(normalize_editable_text("two\u{a0} spaces\n"), "two  spaces");
+        assert_eq!(normalize_editable_text("line\n\n"), "line\n");
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn context_menu_prevents_default() {
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        let view = html::div::<Vec<bool>, (), _>(()).on_contextmenu(|opened, event| {
+            opened.push(is_keyboard_context_menu(&event));
+        });
+        let mut harness = mount(vec![], view.prevent_default());
+
+        let mut init = web_sys::MouseEventInit::new();
+        init.cancelable(true).button(2);
+        let right_click =
+            web_sys::MouseEvent::new_with_mouse_event_init_dict("contextmenu", &init).unwrap();
+        harness.dispatch_event("div", &right_click);
+        assert!(right_click.default_prevented());
+
+        init.button(0);
+        let menu_key =
+            web_sys::MouseEvent::new_with_mouse_event_init_dict("contextmenu", &init).unwrap();
+        harness.dispatch_event("div", &menu_key);
+        assert_eq!(*harness.app_state(), [false, true]);
+    }
+
+    #[test]
+    fn empty_number_is_none() {
+        assert_eq!(number_or_none(f64::NAN), None);
+        assert_eq!(number_or_none(0.0), Some(0.0));
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    #[allow(clippy::approx_constant)]
+    fn number_input_is_parsed() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{
+            elements::html,
+            interfaces::{Element as _, HtmlInputElement as _},
+            test_util::mount,
+        };
+
+        let view = html::input::<Vec<Option<f64>>, (), _>(())
+            .attr("type", "number")
+            .on_number_input(|values, value| values.push(value));
+        let mut harness = mount(vec![], view);
+        let input: web_sys::HtmlInputElement = harness.root_element().unchecked_into();
+
+        input.set_value("3.14");
+        harness.dispatch_event("input", &web_sys::Event::new("input").unwrap());
+        input.set_value("");
+        harness.dispatch_event("input", &web_sys::Event::new("input").unwrap());
+        assert_eq!(*harness.app_state(), [Some(3.14), None]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn remaining_length_counts_utf16_code_units() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{elements::html, interfaces::HtmlTextAreaElement as _, test_util::mount};
+
+        const MAX_LENGTH: usize = 10;
+        let view = html::textarea::<usize, (), _>(())
+            .maxlength(MAX_LENGTH as u32)
+            .on_length_change(|remaining: &mut usize, length| *remaining = MAX_LENGTH - length);
+        let mut harness = mount(MAX_LENGTH, view);
+        let textarea: web_sys::HtmlTextAreaElement = harness.root_element().unchecked_into();
+        assert_eq!(textarea.max_length(), MAX_LENGTH as i32);
+
+        textarea.set_value("hello");
+        harness.dispatch_event("textarea", &web_sys::Event::new("input").unwrap());
+        assert_eq!(*harness.app_state(), 5);
+
+        // "é" is one code unit, the emoji is a surrogate pair
+        textarea.set_value("héllo 👋");
+        harness.dispatch_event("textarea", &web_sys::Event::new("input").unwrap());
+        assert_eq!(*harness.app_state(), 2);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn pasted_text_is_delivered() {
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        let view = html::textarea::<Vec<String>, (), _>(())
+            .on_paste(|pasted: &mut Vec<String>, data| {
+                assert!(data.files.is_empty());
+                pasted.push(data.text);
+            })
+            .prevent_default();
+        let mut harness = mount(vec![], view);
+
+        let data_transfer = web_sys::DataTransfer::new().unwrap();
+        data_transfer
+            .set_data("text/plain", "a@example.com, b@example.com")
+            .unwrap();
+        let mut init = web_sys::ClipboardEventInit::new();
+        init.cancelable(true).clipboard_data(Some(&data_transfer));
+        let paste = web_sys::ClipboardEvent::new_with_event_init_dict("paste", &init).unwrap();
+        harness.dispatch_event("textarea", &paste);
+        assert!(paste.default_prevented());
+        assert_eq!(*harness.app_state(), ["a@example.com, b@example.com"]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn enter_submits_value_unless_composing() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{elements::html, interfaces::HtmlInputElement as _, test_util::mount};
+
+        fn keydown(key: &str, is_composing: bool) -> web_sys::KeyboardEvent {
+            let mut init = web_sys::KeyboardEventInit::new();
+            init.key(key).is_composing(is_composing).bubbles(true);
+            web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap()
+        }
+
+        let view = html::input::<Vec<String>, (), _>(())
+            .on_enter(|submitted: &mut Vec<String>, value| submitted.push(value));
+        let mut harness = mount(vec![], view);
+        let input: web_sys::HtmlInputElement = harness.root_element().unchecked_into();
+
+        input.set_value("にほん");
+        // the Enter that commits the IME composition doesn't submit
+        harness.dispatch_event("input", &keydown("Enter", true));
+        harness.dispatch_event("input", &keydown("a", false));
+        assert!(harness.app_state().is_empty());
+
+        harness.dispatch_event("input", &keydown("Enter", false));
+        assert_eq!(*harness.app_state(), ["にほん"]);
+    }
+
+    #[test]
+    fn pixel_wheel_delta_is_unchanged() {
+        assert_eq!(
+            normalize_wheel_delta(12.5, WheelEvent::DOM_DELTA_PIXEL, 600.0),
+            12.5
+        );
+    }
+
+    #[test]
+    fn line_wheel_delta_uses_line_height() {
+        assert_eq!(
+            normalize_wheel_delta(3.0, WheelEvent::DOM_DELTA_LINE, 600.0),
+            3.0 * WHEEL_LINE_HEIGHT
+        );
+        assert_eq!(
+            normalize_wheel_delta(-1.0, WheelEvent::DOM_DELTA_LINE, 600.0),
+            -WHEEL_LINE_HEIGHT
+        );
+    }
+
+    #[test]
+    fn page_wheel_delta_uses_page_size() {
+        assert_eq!(
+            normalize_wheel_delta(1.0, WheelEvent::DOM_DELTA_PAGE, 600.0),
+            600.0
+        );
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn transition_end_has_property_name() {
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        let view = html::div::<Vec<String>, (), _>(())
+            .on_transitionend(|properties, property| properties.push(property));
+        let mut harness = mount(vec![], view);
+
+        // one event is fired for each transitioned property
+        for property in ["opacity", "transform"] {
+            let mut init = web_sys::TransitionEventInit::new();
+            init.property_name(property);
+            let event =
+                web_sys::TransitionEvent::new_with_event_init_dict("transitionend", &init).unwrap();
+            harness.dispatch_event("div", &event);
+        }
+        assert_eq!(*harness.app_state(), ["opacity", "transform"]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn dropped_data_is_delivered() {
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        let view = html::div::<Vec<String>, (), _>(())
+            .on_dragover(|_, _| {})
+            .on_drop(|dropped: &mut Vec<String>, data| {
+                dropped.push(data.get_data("text/plain").unwrap());
+            });
+        let mut harness = mount(vec![], view);
+
+        let data_transfer = web_sys::DataTransfer::new().unwrap();
+        data_transfer.set_data("text/plain", "item-1").unwrap();
+        let mut init = web_sys::DragEventInit::new();
+        init.cancelable(true).data_transfer(Some(&data_transfer));
+
+        // the drop is only allowed when `dragover` is cancelled
+        let drag_over = web_sys::DragEvent::new_with_event_init_dict("dragover", &init).unwrap();
+        harness.dispatch_event("div", &drag_over);
+        assert!(drag_over.default_prevented());
+
+        let drop = web_sys::DragEvent::new_with_event_init_dict("drop", &init).unwrap();
+        harness.dispatch_event("div", &drop);
+        assert!(drop.default_prevented());
+        assert_eq!(*harness.app_state(), ["item-1"]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn selected_files_are_delivered() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{elements::html, interfaces::HtmlInputElement as _, test_util::mount};
+
+        let view = html::input::<Vec<String>, (), _>(())
+            .attr("type", "file")
+            .on_files(|names: &mut Vec<String>, files| {
+                names.extend((0..files.length()).map(|i| files.get(i).unwrap().name()));
+            });
+        let mut harness = mount(vec![], view);
+        let input: web_sys::HtmlInputElement = harness.root_element().unchecked_into();
+
+        // a `DataTransfer` is the only way to create a `FileList`
+        let data_transfer = web_sys::DataTransfer::new().unwrap();
+        let parts = js_sys::Array::of1(&"contents".into());
+        let file = web_sys::File::new_with_str_sequence(&parts, "report.txt").unwrap();
+        data_transfer.items().add_with_file(&file).unwrap();
+        input.set_files(Some(&data_transfer.files().unwrap()));
+
+        harness.dispatch_event("input", &web_sys::Event::new("change").unwrap());
+        assert_eq!(*harness.app_state(), ["report.txt"]);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn focus_and_blur_have_related_target() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        fn id(target: Option<web_sys::EventTarget>) -> String {
+            target.map_or("none".into(), |target| {
+                target.unchecked_into::<web_sys::Element>().id()
+            })
+        }
+        fn input(name: &'static str) -> impl crate::interfaces::Element<Vec<String>> {
+            html::input(())
+                .attr("id", name)
+                .on_focus(move |log: &mut Vec<String>, related| {
+                    log.push(format!("{name} focus from {}", id(related)));
+                })
+                .on_blur(move |log: &mut Vec<String>, related| {
+                    log.push(format!("{name} blur to {}", id(related)));
+                })
+        }
+
+        // focus moving between the children doesn't blur the parent
+        let view = html::div((input("a"), input("b"))).on_blur(|log: &mut Vec<String>, related| {
+            log.push(format!("div blur to {}", id(related)));
+        });
+        let mut harness = mount(vec![], view);
+        let root = harness.root_element();
+        let element = |selector| root.query_selector(selector).unwrap().unwrap();
+
+        let focus_event = |event_type, related: Option<&web_sys::Element>| {
+            let mut init = web_sys::FocusEventInit::new();
+            init.related_target(related.map(|related| related.as_ref()));
+            web_sys::FocusEvent::new_with_focus_event_init_dict(event_type, &init).unwrap()
+        };
+        harness.dispatch_event("#a", &focus_event("focus", None));
+        harness.dispatch_event("#a", &focus_event("blur", Some(&element("#b"))));
+        harness.dispatch_event("#b", &focus_event("focus", Some(&element("#a"))));
+        assert_eq!(
+            *harness.app_state(),
+            ["a focus from none", "a blur to b", "b focus from a"]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resetting a `<form>`, see [`HtmlFormElement::on_form_reset`] and [`HtmlFormElement::reset_generation`].
+
+use std::{any::Any, marker::PhantomData, rc::Rc};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::MessageThunk,
+    interfaces::{sealed::Sealed, HtmlFormElement},
+    view::DomNode,
+    ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Calls a handler after the form was reset, see [`HtmlFormElement::on_form_reset`].
+pub struct OnFormReset<E, T, A, F> {
+    element: E,
+    handler: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, F> OnFormReset<E, T, A, F> {
+    pub(crate) fn new(element: E, handler: F) -> Self {
+        OnFormReset {
+            element,
+            handler,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct OnFormResetState<S> {
+    child_id: Id,
+    child_state: S,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// Sent when the controls of the form have been reset.
+struct FormWasReset;
+
+/// The `reset` event is fired before the controls are reset, so the message is sent from a timeout,
+/// otherwise the values of a rebuild in response would be overwritten by the reset.
+/// This also avoids handling the message within a rebuild that called [`web_sys::HtmlFormElement::reset`].
+fn create_reset_listener(node: &web_sys::Node, cx: &Cx) -> EventListener {
+    let thunk = Rc::new(cx.message_thunk());
+    EventListener::new(node, "reset", move |_| {
+        push_after_timeout(thunk.clone());
+    })
+}
+
+fn push_after_timeout(thunk: Rc<MessageThunk>) {
+    let callback = Closure::once_into_js(move || thunk.push_message(FormWasReset));
+    web_sys::window()
+        .unwrap_throw()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(callback.unchecked_ref(), 0)
+        .unwrap_throw();
+}
+
+impl<E, T, A, F> ViewMarker for OnFormReset<E, T, A, F> {}
+impl<E, T, A, F> Sealed for OnFormReset<E, T, A, F> {}
+
+impl<E, T, A, F, OA> View<T, A> for OnFormReset<E, T, A, F>
+where
+    E: HtmlFormElement<T, A>,
+    F: Fn(&mut T) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = OnFormResetState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            let (child_id, child_state, element) = self.element.build(cx);
+            let listener = create_reset_listener(element.as_node_ref(), cx);
+            let state = OnFormResetState {
+                child_id,
+                child_state,
+                listener,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            if changed.contains(ChangeFlags::STRUCTURE) {
+                state.listener = create_reset_listener(element.as_node_ref(), cx);
+            }
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.downcast_ref::<FormWasReset>().is_some() => {
+                match (self.handler)(app_state).action() {
+                    Some(action) => MessageResult::Action(action),
+                    None => MessageResult::Nop,
+                }
+            }
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlFormElement,
+    OnFormReset,
+    vars: <F, OA,>,
+    vars_on_ty: <F,>,
+    bounds: {
+        F: Fn(&mut T) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+/// Resets the form when its generation changes, see [`HtmlFormElement::reset_generation`].
+pub struct ResetGeneration<E, T, A = ()> {
+    element: E,
+    generation: u32,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A> ResetGeneration<E, T, A> {
+    pub(crate) fn new(element: E, generation: u32) -> Self {
+        ResetGeneration {
+            element,
+            generation,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<E, T, A> ViewMarker for ResetGeneration<E, T, A> {}
+impl<E, T, A> Sealed for ResetGeneration<E, T, A> {}
+
+impl<E: HtmlFormElement<T, A>, T, A> View<T, A> for ResetGeneration<E, T, A> {
+    type State = E::State;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        self.element.build(cx)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = self.element.rebuild(cx, &prev.element, id, state, element);
+        // A new form element is in its default state already
+        if prev.generation != self.generation && !changed.contains(ChangeFlags::STRUCTURE) {
+            let form: &web_sys::HtmlFormElement = element.as_node_ref().unchecked_ref();
+            form.reset();
+            changed |= ChangeFlags::OTHER_CHANGE;
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.element.message(id_path, state, message, app_state)
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(HtmlFormElement, ResetGeneration);
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn reset_clears_bound_state() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{
+            elements::html,
+            interfaces::{Element as _, HtmlFormElement as _},
+            test_util::mount,
+        };
+
+        async fn after_timeout() {
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                web_sys::window()
+                    .unwrap()
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0)
+                    .unwrap();
+            });
+            wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+        }
+
+        let form = |name: &str, generation: u32| {
+            html::form(html::input::<_, (), _>(()).attr("value", name.to_string()))
+                .on_form_reset(|name: &mut String| name.clear())
+                .reset_generation(generation)
+        };
+        let mut harness = mount("Alice".to_string(), form("Alice", 0));
+        let input: web_sys::HtmlInputElement = harness
+            .root_element()
+            .first_element_child()
+            .unwrap()
+            .unchecked_into();
+        assert_eq!(input.value(), "Alice");
+
+        // the input has no default value, as its `value` attribute is set as property
+        harness.rebuild(form("Alice", 1));
+        assert_eq!(input.value(), "");
+        assert_eq!(harness.app_state(), "Alice");
+        after_timeout().await;
+        harness.handle_messages();
+        assert_eq!(harness.app_state(), "");
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stylesheets in the `<head>` of the document, which are shared by the views that need them.
+
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
+
+use crate::vecmap::VecMap;
+
+type CowStr = Cow<'static, str>;
+
+/// Identifies a node in `<head>` by its content.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum HeadKey {
+    /// A `<link rel="stylesheet">` with this `href`.
+    Link(CowStr),
+    /// A `<style>` with this CSS.
+    Style(CowStr),
+}
+
+/// The nodes in `<head>` added via [`Cx::ensure_head_link`](crate::Cx::ensure_head_link)
+/// and [`Cx::ensure_head_style`](crate::Cx::ensure_head_style), with the number of handles to each of them.
+pub(crate) struct HeadNodes<N> {
+    nodes: VecMap<HeadKey, (N, usize)>,
+}
+
+impl<N> Default for HeadNodes<N> {
+    fn default() -> Self {
+        Self {
+            nodes: VecMap::default(),
+        }
+    }
+}
+
+impl<N: Clone> HeadNodes<N> {
+    /// Returns the node for `key`, which is created with `create` if there's none yet,
+    /// and counts the new reference to it.
+    pub(crate) fn acquire(&mut self, key: &HeadKey, create: impl FnOnce() -> N) -> N {
+        if let Some((node, count)) = self.nodes.get_mut(key) {
+            *count += 1;
+            return node.clone();
+        }
+        let node = create();
+        self.nodes.insert(key.clone(), (node.clone(), 1));
+        node
+    }
+
+    /// Drops a reference to the node for `key`, returns the node when it isn't referenced anymore.
+    pub(crate) fn release(&mut self, key: &HeadKey) -> Option<N> {
+        let (_, count) = self.nodes.get_mut(key)?;
+        *count -= 1;
+        if *count > 0 {
+            return None;
+        }
+        self.nodes.remove(key).map(|(node, _)| node)
+    }
+}
+
+/// A handle to a node in the `<head>` of the document, see [`Cx::ensure_head_link`](crate::Cx::ensure_head_link).
+///
+/// The node is removed when the last handle to it is dropped, so views usually keep it in their state.
+pub struct HeadNode {
+    key: HeadKey,
+    element: web_sys::Element,
+    nodes: Rc<RefCell<HeadNodes<web_sys::Element>>>,
+}
+
+impl HeadNode {
+    pub(crate) fn new(
+        key: HeadKey,
+        element: web_sys::Element,
+        nodes: Rc<RefCell<HeadNodes<web_sys::Element>>>,
+    ) -> Self {
+        HeadNode {
+            key,
+            element,
+            nodes,
+        }
+    }
+
+    /// The `<link>` or `<style>` element.
+    pub fn element(&self) -> &web_sys::Element {
+        &self.element
+    }
+}
+
+impl Drop for HeadNode {
+    fn drop(&mut self) {
+        if let Some(element) = self.nodes.borrow_mut().release(&self.key) {
+            element.remove();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeadKey, HeadNodes};
+
+    #[test]
+    fn same_key_shares_node() {
+        let mut nodes = HeadNodes::default();
+        let key = HeadKey::Link("style.css".into());
+        let mut created = 0;
+        let first = nodes.acquire(&key, || {
+            created += 1;
+            created
+        });
+        let second = nodes.acquire(&key, || {
+            created += 1;
+            created
+        });
+        assert_eq!((first, second, created), (1, 1, 1));
+
+        assert_eq!(nodes.release(&key), None);
+        assert_eq!(nodes.release(&key), Some(1));
+        assert_eq!(nodes.release(&key), None);
+    }
+
+    #[test]
+    fn links_and_styles_are_distinct() {
+        let mut nodes = HeadNodes::default();
+        let link = nodes.acquire(&HeadKey::Link("a".into()), || "link");
+        let style = nodes.acquire(&HeadKey::Style("a".into()), || "style");
+        assert_eq!((link, style), ("link", "style"));
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reacting to an `<img>` that finished loading (or failed to), see [`HtmlImageElement::on_image_load`].
+
+use std::{any::Any, marker::PhantomData};
+
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, HtmlImageElement},
+    view::DomNode,
+    ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Whether an [`ImageSettled`] view reacts to the image being loaded or failing to load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImageOutcome {
+    Load,
+    Error,
+}
+
+impl ImageOutcome {
+    fn event_name(self) -> &'static str {
+        match self {
+            ImageOutcome::Load => "load",
+            ImageOutcome::Error => "error",
+        }
+    }
+
+    /// The outcome of an image that is already `complete`, if it has a source.
+    fn of_complete(image: &web_sys::HtmlImageElement) -> Option<Self> {
+        if !image.complete() || image.current_src().is_empty() {
+            return None;
+        }
+        // A broken image is complete as well, but has no natural size
+        Some(if image.natural_width() > 0 {
+            ImageOutcome::Load
+        } else {
+            ImageOutcome::Error
+        })
+    }
+}
+
+/// Calls a handler once the image has loaded (or failed to), see [`HtmlImageElement::on_image_load`]
+/// and [`HtmlImageElement::on_image_error`].
+pub struct ImageSettled<E, T, A, F> {
+    element: E,
+    outcome: ImageOutcome,
+    handler: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, F> ImageSettled<E, T, A, F> {
+    pub(crate) fn new(element: E, outcome: ImageOutcome, handler: F) -> Self {
+        ImageSettled {
+            element,
+            outcome,
+            handler,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct ImageSettledState<S> {
+    child_id: Id,
+    child_state: S,
+    /// The source the handler was last called for.
+    reported_src: Option<String>,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// Sent when the image with the source `src` has settled with the outcome of the view.
+struct Settled {
+    src: String,
+}
+
+fn as_image(node: &web_sys::Node) -> &web_sys::HtmlImageElement {
+    node.unchecked_ref()
+}
+
+fn create_listener(node: &web_sys::Node, outcome: ImageOutcome, cx: &Cx) -> EventListener {
+    let thunk = cx.message_thunk();
+    let image = as_image(node).clone();
+    EventListener::new(node, outcome.event_name(), move |_| {
+        thunk.push_message(Settled {
+            src: image.current_src(),
+        });
+    })
+}
+
+/// A cached image can already be complete before the listener is attached (e.g. when the handler
+/// is added to an existing element), so report it directly, the following event is ignored then.
+fn report_if_complete(node: &web_sys::Node, outcome: ImageOutcome, cx: &mut Cx) {
+    let image = as_image(node);
+    if ImageOutcome::of_complete(image) == Some(outcome) {
+        cx.message_self(Box::new(Settled {
+            src: image.current_src(),
+        }));
+    }
+}
+
+impl<E, T, A, F> ViewMarker for ImageSettled<E, T, A, F> {}
+impl<E, T, A, F> Sealed for ImageSettled<E, T, A, F> {}
+
+impl<E, T, A, F, OA> View<T, A> for ImageSettled<E, T, A, F>
+where
+    E: HtmlImageElement<T, A>,
+    F: Fn(&mut T) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = ImageSettledState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            let (child_id, child_state, element) = self.element.build(cx);
+            let node = element.as_node_ref();
+            let listener = create_listener(node, self.outcome, cx);
+            report_if_complete(node, self.outcome, cx);
+            let state = ImageSettledState {
+                child_id,
+                child_state,
+                reported_src: None,
+                listener,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            let node = element.as_node_ref();
+            if changed.contains(ChangeFlags::STRUCTURE) || prev.outcome != self.outcome {
+                state.listener = create_listener(node, self.outcome, cx);
+                state.reported_src = None;
+            }
+            if !changed.is_empty() {
+                report_if_complete(node, self.outcome, cx);
+            }
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] => match message.downcast::<Settled>() {
+                // Either the event or the `complete` check was first
+                Ok(settled) if state.reported_src.as_ref() == Some(&settled.src) => {
+                    MessageResult::Nop
+                }
+                Ok(settled) => {
+                    state.reported_src = Some(settled.src);
+                    match (self.handler)(app_state).action() {
+                        Some(action) => MessageResult::Action(action),
+                        None => MessageResult::Nop,
+                    }
+                }
+                Err(message) => MessageResult::Stale(message),
+            },
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlImageElement,
+    ImageSettled,
+    vars: <F, OA,>,
+    vars_on_ty: <F,>,
+    bounds: {
+        F: Fn(&mut T) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn load_event_calls_handler_once_per_source() {
+        use crate::{
+            elements::html,
+            interfaces::{Element as _, HtmlImageElement as _},
+            test_util::mount,
+        };
+
+        let view = html::img::<u32, (), _>(())
+            .attr("src", "cat.png")
+            .lazy()
+            .decoding_async()
+            .on_image_load(|loaded: &mut u32| *loaded += 1);
+        let mut harness = mount(0, view);
+        let img = harness.root_element();
+        assert_eq!(img.get_attribute("loading").as_deref(), Some("lazy"));
+        assert_eq!(img.get_attribute("decoding").as_deref(), Some("async"));
+
+        harness.dispatch_event("img", &web_sys::Event::new("load").unwrap());
+        assert_eq!(*harness.app_state(), 1);
+        // e.g. the event after the image was reported as `complete`
+        harness.dispatch_event("img", &web_sys::Event::new("load").unwrap());
+        assert_eq!(*harness.app_state(), 1);
+    }
+}
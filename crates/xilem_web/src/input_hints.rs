@@ -0,0 +1,207 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed values of the attributes that hint browsers (and on-screen keyboards) how to fill in a form control.
+
+/// A token of the `autocomplete` attribute, i.e. the kind of data the browser may fill in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocompleteToken {
+    /// Don't autofill the control, e.g. for data that is never reused.
+    Off,
+    /// Let the browser decide what to autofill.
+    On,
+    Name,
+    HonorificPrefix,
+    GivenName,
+    AdditionalName,
+    FamilyName,
+    HonorificSuffix,
+    Nickname,
+    Email,
+    Username,
+    /// A new password, e.g. when signing up, for which browsers may suggest a generated one.
+    NewPassword,
+    CurrentPassword,
+    /// A one-time code, e.g. sent by SMS, to verify the identity of the user.
+    OneTimeCode,
+    OrganizationTitle,
+    Organization,
+    StreetAddress,
+    AddressLine1,
+    AddressLine2,
+    AddressLine3,
+    AddressLevel1,
+    AddressLevel2,
+    AddressLevel3,
+    AddressLevel4,
+    /// A country code.
+    Country,
+    CountryName,
+    PostalCode,
+    CcName,
+    CcGivenName,
+    CcAdditionalName,
+    CcFamilyName,
+    CcNumber,
+    CcExp,
+    CcExpMonth,
+    CcExpYear,
+    CcCsc,
+    CcType,
+    TransactionCurrency,
+    TransactionAmount,
+    Language,
+    Bday,
+    BdayDay,
+    BdayMonth,
+    BdayYear,
+    Sex,
+    Tel,
+    TelCountryCode,
+    TelNational,
+    TelAreaCode,
+    TelLocal,
+    TelExtension,
+    Impp,
+    Url,
+    Photo,
+    /// A passkey, which browsers may offer in the autofill suggestions.
+    Webauthn,
+}
+
+impl AutocompleteToken {
+    /// The value of the token in the `autocomplete` attribute, e.g. `"email"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AutocompleteToken::Off => "off",
+            AutocompleteToken::On => "on",
+            AutocompleteToken::Name => "name",
+            AutocompleteToken::HonorificPrefix => "honorific-prefix",
+            AutocompleteToken::GivenName => "given-name",
+            AutocompleteToken::AdditionalName => "additional-name",
+            AutocompleteToken::FamilyName => "family-name",
+            AutocompleteToken::HonorificSuffix => "honorific-suffix",
+            AutocompleteToken::Nickname => "nickname",
+            AutocompleteToken::Email => "email",
+            AutocompleteToken::Username => "username",
+            AutocompleteToken::NewPassword => "new-password",
+            AutocompleteToken::CurrentPassword => "current-password",
+            AutocompleteToken::OneTimeCode => "one-time-code",
+            AutocompleteToken::OrganizationTitle => "organization-title",
+            AutocompleteToken::Organization => "organization",
+            AutocompleteToken::StreetAddress => "street-address",
+            AutocompleteToken::AddressLine1 => "address-line1",
+            AutocompleteToken::AddressLine2 => "address-line2",
+            AutocompleteToken::AddressLine3 => "address-line3",
+            AutocompleteToken::AddressLevel1 => "address-level1",
+            AutocompleteToken::AddressLevel2 => "address-level2",
+            AutocompleteToken::AddressLevel3 => "address-level3",
+            AutocompleteToken::AddressLevel4 => "address-level4",
+            AutocompleteToken::Country => "country",
+            AutocompleteToken::CountryName => "country-name",
+            AutocompleteToken::PostalCode => "postal-code",
+            AutocompleteToken::CcName => "cc-name",
+            AutocompleteToken::CcGivenName => "cc-given-name",
+            AutocompleteToken::CcAdditionalName => "cc-additional-name",
+            AutocompleteToken::CcFamilyName => "cc-family-name",
+            AutocompleteToken::CcNumber => "cc-number",
+            AutocompleteToken::CcExp => "cc-exp",
+            AutocompleteToken::CcExpMonth => "cc-exp-month",
+            AutocompleteToken::CcExpYear => "cc-exp-year",
+            AutocompleteToken::CcCsc => "cc-csc",
+            AutocompleteToken::CcType => "cc-type",
+            AutocompleteToken::TransactionCurrency => "transaction-currency",
+            AutocompleteToken::TransactionAmount => "transaction-amount",
+            AutocompleteToken::Language => "language",
+            AutocompleteToken::Bday => "bday",
+            AutocompleteToken::BdayDay => "bday-day",
+            AutocompleteToken::BdayMonth => "bday-month",
+            AutocompleteToken::BdayYear => "bday-year",
+            AutocompleteToken::Sex => "sex",
+            AutocompleteToken::Tel => "tel",
+            AutocompleteToken::TelCountryCode => "tel-country-code",
+            AutocompleteToken::TelNational => "tel-national",
+            AutocompleteToken::TelAreaCode => "tel-area-code",
+            AutocompleteToken::TelLocal => "tel-local",
+            AutocompleteToken::TelExtension => "tel-extension",
+            AutocompleteToken::Impp => "impp",
+            AutocompleteToken::Url => "url",
+            AutocompleteToken::Photo => "photo",
+            AutocompleteToken::Webauthn => "webauthn",
+        }
+    }
+}
+
+/// The value of the `autocapitalize` attribute, i.e. which letters on-screen keyboards capitalize automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocapitalizeMode {
+    /// Don't capitalize automatically.
+    Off,
+    /// Capitalize the first letter of each sentence.
+    Sentences,
+    /// Capitalize the first letter of each word.
+    Words,
+    /// Capitalize all letters.
+    Characters,
+}
+
+impl AutocapitalizeMode {
+    /// The value of the mode in the `autocapitalize` attribute, e.g. `"words"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AutocapitalizeMode::Off => "off",
+            AutocapitalizeMode::Sentences => "sentences",
+            AutocapitalizeMode::Words => "words",
+            AutocapitalizeMode::Characters => "characters",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_kebab_case() {
+        assert_eq!(AutocompleteToken::Email.as_str(), "email");
+        assert_eq!(AutocompleteToken::NewPassword.as_str(), "new-password");
+        assert_eq!(AutocompleteToken::AddressLine1.as_str(), "address-line1");
+        assert_eq!(AutocompleteToken::CcExpMonth.as_str(), "cc-exp-month");
+        assert_eq!(AutocapitalizeMode::Characters.as_str(), "characters");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn hints_are_set_as_attributes() {
+        use crate::{
+            elements::html,
+            interfaces::{HtmlInputElement as _, HtmlTextAreaElement as _},
+            Cx, View,
+        };
+
+        let mut cx = Cx::new();
+        let input = html::input::<(), (), _>(())
+            .autocomplete(AutocompleteToken::Email)
+            .spellcheck(false);
+        let (_, _, element) = View::build(&input, &mut cx);
+        assert_eq!(
+            element.get_attribute("autocomplete").as_deref(),
+            Some("email")
+        );
+        // `false` isn't serialized as an omitted attribute, which would mean the browser default
+        assert_eq!(
+            element.get_attribute("spellcheck").as_deref(),
+            Some("false")
+        );
+
+        let textarea = html::textarea::<(), (), _>(())
+            .spellcheck(true)
+            .autocapitalize(AutocapitalizeMode::Sentences);
+        let (_, _, element) = View::build(&textarea, &mut cx);
+        assert_eq!(element.get_attribute("spellcheck").as_deref(), Some("true"));
+        assert_eq!(
+            element.get_attribute("autocapitalize").as_deref(),
+            Some("sentences")
+        );
+    }
+}
@@ -2,8 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    anchor::TargetBlank,
+    autosize::Autosize,
     class::{Class, IntoClasses},
+    composition::OnCommittedInput,
+    form::{OnFormReset, ResetGeneration},
+    image::{ImageOutcome, ImageSettled},
+    input_hints::{AutocapitalizeMode, AutocompleteToken},
+    js_init::JsInit,
+    multi_select::BindMultiSelected,
+    scroll::OnScroll,
+    slider::Slider,
     style::{IntoStyles, Style},
+    validity::CustomValidity,
     Pointer, PointerMsg, View, ViewMarker,
 };
 use std::{borrow::Cow, marker::PhantomData};
@@ -13,7 +24,7 @@ use wasm_bindgen::JsCast;
 
 use crate::{
     events::{self, OnEvent},
-    Attr, IntoAttributeValue, OptionalAction,
+    Attr, AttributeValue, IntoAttributeValue, OptionalAction,
 };
 
 pub(crate) mod sealed {
@@ -98,6 +109,23 @@ where
         }
     }
 
+    /// Set the `id` of this element.
+    ///
+    /// Ids should be unique within the document, in debug builds a warning is logged
+    /// when multiple elements are built with the same id.
+    fn id(self, id: impl Into<Cow<'static, str>>) -> Attr<Self, T, A> {
+        self.attr("id", id.into())
+    }
+
+    /// Set whether this element and its subtree are `inert`, e.g. the background content of an open modal,
+    /// so that it can't be focused or clicked and is hidden from assistive technology.
+    ///
+    /// `false` removes the attribute. In browsers without support for `inert` nothing is set
+    /// and a warning is logged once.
+    fn inert(self, inert: bool) -> Attr<Self, T, A> {
+        self.attr("inert", inert)
+    }
+
     /// Add 0 or more classes to the wrapped element.
     ///
     /// Can pass a string, &'static str, Option, tuple, or vec
@@ -113,6 +141,14 @@ where
         }
     }
 
+    /// Call the JS function `init` with the element (as `this` and as argument), after the element is mounted,
+    /// e.g. to initialize a plugin of an existing JS library on the element.
+    ///
+    /// It's called again when the element is replaced. When `init` throws, the exception is logged.
+    fn with_js_init(self, init: js_sys::Function) -> JsInit<Self, T, A> {
+        JsInit::new(self, init)
+    }
+
     // event list from
     // https://html.spec.whatwg.org/multipage/webappapis.html#idl-definitions
     //
@@ -120,19 +156,30 @@ where
     // any events to the window in xilem_web
     event_handler_mixin!(
         (OnAbort, on_abort, "abort", Event),
-        (OnAuxClick, on_auxclick, "auxclick", PointerEvent),
+        (OnAuxClick, on_auxclick, "auxclick", MouseEvent),
         (OnBeforeInput, on_beforeinput, "beforeinput", InputEvent),
         (OnBeforeMatch, on_beforematch, "beforematch", Event),
         (OnBeforeToggle, on_beforetoggle, "beforetoggle", Event),
-        (OnBlur, on_blur, "blur", FocusEvent),
         (OnCancel, on_cancel, "cancel", Event),
         (OnCanPlay, on_canplay, "canplay", Event),
         (OnCanPlayThrough, on_canplaythrough, "canplaythrough", Event),
         (OnChange, on_change, "change", Event),
         (OnClick, on_click, "click", MouseEvent),
         (OnClose, on_close, "close", Event),
+        (
+            OnCompositionEnd,
+            on_compositionend,
+            "compositionend",
+            CompositionEvent
+        ),
+        (
+            OnCompositionStart,
+            on_compositionstart,
+            "compositionstart",
+            CompositionEvent
+        ),
         (OnContextLost, on_contextlost, "contextlost", Event),
-        (OnContextMenu, on_contextmenu, "contextmenu", PointerEvent),
+        (OnContextMenu, on_contextmenu, "contextmenu", MouseEvent),
         (
             OnContextRestored,
             on_contextrestored,
@@ -147,14 +194,13 @@ where
         (OnDragEnd, on_dragend, "dragend", Event),
         (OnDragEnter, on_dragenter, "dragenter", Event),
         (OnDragLeave, on_dragleave, "dragleave", Event),
-        (OnDragOver, on_dragover, "dragover", Event),
-        (OnDragStart, on_dragstart, "dragstart", Event),
-        (OnDrop, on_drop, "drop", Event),
+        (OnDragOver, on_dragover, "dragover", DataTransfer),
+        (OnDragStart, on_dragstart, "dragstart", DataTransfer),
+        (OnDrop, on_drop, "drop", DataTransfer),
         (OnDurationChange, on_durationchange, "durationchange", Event),
         (OnEmptied, on_emptied, "emptied", Event),
         (OnEnded, on_ended, "ended", Event),
         (OnError, on_error, "error", Event),
-        (OnFocus, on_focus, "focus", FocusEvent),
         (OnFocusIn, on_focusin, "focusin", FocusEvent),
         (OnFocusOut, on_focusout, "focusout", FocusEvent),
         (OnFormData, on_formdata, "formdata", Event),
@@ -173,7 +219,6 @@ where
         (OnMouseOut, on_mouseout, "mouseout", MouseEvent),
         (OnMouseOver, on_mouseover, "mouseover", MouseEvent),
         (OnMouseUp, on_mouseup, "mouseup", MouseEvent),
-        (OnPaste, on_paste, "paste", Event),
         (OnPause, on_pause, "pause", Event),
         (OnPlay, on_play, "play", Event),
         (OnPlaying, on_playing, "playing", Event),
@@ -181,7 +226,6 @@ where
         (OnRateChange, on_ratechange, "ratechange", Event),
         (OnReset, on_reset, "reset", Event),
         (OnResize, on_resize, "resize", Event),
-        (OnScroll, on_scroll, "scroll", Event),
         (OnScrollEnd, on_scrollend, "scrollend", Event),
         (
             OnSecurityPolicyViolation,
@@ -200,8 +244,82 @@ where
         (OnToggle, on_toggle, "toggle", Event),
         (OnVolumeChange, on_volumechange, "volumechange", Event),
         (OnWaiting, on_waiting, "waiting", Event),
-        (OnWheel, on_wheel, "wheel", WheelEvent),
     );
+
+    /// Call `handler` on `wheel` events, with the scroll deltas normalized to pixels.
+    fn on_wheel<EH, OA>(self, handler: EH) -> events::OnWheel<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, events::WheelDetails) -> OA,
+    {
+        events::OnWheel::new(self, handler)
+    }
+
+    /// Call `handler` with the pasted text and files, when the user pasted into this element (or one of its children).
+    ///
+    /// Use [`prevent_default`](events::OnPaste::prevent_default) to handle the paste yourself,
+    /// e.g. to turn a pasted list of emails into tags, instead of inserting the text.
+    fn on_paste<EH, OA>(self, handler: EH) -> events::OnPaste<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, events::ClipboardData) -> OA,
+    {
+        events::OnPaste::new(self, handler)
+    }
+
+    /// Call `handler` with the `scrollTop` and `scrollLeft` of this element, after it was scrolled.
+    ///
+    /// This is throttled to animation frames, i.e. `handler` is called at most once per frame
+    /// with the latest offsets, even when a (smooth) scroll fires many `scroll` events in between.
+    fn on_scroll<EH, OA>(self, handler: EH) -> OnScroll<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, f64, f64) -> OA,
+    {
+        OnScroll::new(self, handler)
+    }
+
+    /// Call `handler` when this element receives focus, with the element that lost focus (the `relatedTarget`), if any.
+    ///
+    /// Unlike `focusin`, this doesn't fire when focus moves to a child of this element.
+    fn on_focus<EH, OA>(self, handler: EH) -> events::OnFocus<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, Option<web_sys::EventTarget>) -> OA,
+    {
+        events::OnFocus::new(self, handler)
+    }
+
+    /// Call `handler` when this element loses focus, with the element that receives focus (the `relatedTarget`), if any.
+    ///
+    /// Unlike `focusout`, this doesn't fire when focus moves away from a child of this element.
+    fn on_blur<EH, OA>(self, handler: EH) -> events::OnBlur<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, Option<web_sys::EventTarget>) -> OA,
+    {
+        events::OnBlur::new(self, handler)
+    }
+
+    /// Call `handler` with the name of the CSS property (the `propertyName`), when its transition has finished.
+    ///
+    /// When several properties are transitioned, `handler` is called once for each of them.
+    fn on_transitionend<EH, OA>(self, handler: EH) -> events::OnTransitionEnd<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, String) -> OA,
+    {
+        events::OnTransitionEnd::new(self, handler)
+    }
+
+    /// Call `handler` with the name of the CSS animation (the `animationName`), when it has finished.
+    fn on_animationend<EH, OA>(self, handler: EH) -> events::OnAnimationEnd<Self, T, A, EH>
+    where
+        OA: OptionalAction<A>,
+        EH: Fn(&mut T, String) -> OA,
+    {
+        events::OnAnimationEnd::new(self, handler)
+    }
 }
 
 // base case for ancestor macros, do nothing, because the body is in all the child interface macros...
@@ -343,9 +461,56 @@ dom_interface_macro_and_trait_definitions!(
                     phantom: PhantomData,
                 }
             }
+
+            /// Set whether the content of this element can be edited by the user.
+            fn content_editable(self, editable: bool) -> Attr<Self, T, A> {
+                self.attr("contenteditable", if editable { "true" } else { "false" })
+            }
+
+            /// Call `handler` with the plain text content of this element, when it was edited by the user.
+            ///
+            /// Use together with [`content_editable`](HtmlElement::content_editable).
+            /// Rich content (e.g. pasted HTML) is delivered as its rendered text.
+            fn on_content_change<EH, OA>(self, handler: EH) -> events::OnContentChange<Self, T, A, EH>
+            where
+                OA: OptionalAction<A>,
+                EH: Fn(&mut T, String) -> OA,
+            {
+                events::OnContentChange::new(self, handler)
+            }
+
+            /// Call `handler` with the text of this element (e.g. the value of an `<input>`) on `input` events,
+            /// except while the text is being composed with an IME (e.g. for CJK input).
+            ///
+            /// The intermediate `input` events of a composition are ignored, `handler` is called once
+            /// with the committed text when the composition ends instead. Use this instead of [`Element::on_input`]
+            /// when the handler changes state on every input, like a controlled value, which would otherwise
+            /// interfere with the composition.
+            fn on_committed_input<EH, OA>(self, handler: EH) -> OnCommittedInput<Self, T, A, EH>
+            where
+                OA: OptionalAction<A>,
+                EH: Fn(&mut T, String) -> OA,
+            {
+                OnCommittedInput::new(self, handler)
+            }
         },
         child_interfaces: {
-            HtmlAnchorElement { methods: {}, child_interfaces: {} },
+            HtmlAnchorElement {
+                methods: {
+                    /// Download the linked resource instead of navigating to it, optionally with a suggested `filename`.
+                    fn download(self, filename: Option<Cow<'static, str>>) -> Attr<Self, T, A> {
+                        self.attr("download", filename.map_or(AttributeValue::True, AttributeValue::String))
+                    }
+                    /// Open the link in a new tab (`target="_blank"`), with `rel="noopener noreferrer"`,
+                    /// so that the opened page can't access this one via `window.opener`.
+                    ///
+                    /// The tokens are added to a `rel` attribute set after this (e.g. `.target_blank().attr("rel", "external")`).
+                    fn target_blank(self) -> TargetBlank<Self, T, A> {
+                        TargetBlank::new(self)
+                    }
+                },
+                child_interfaces: {}
+            },
             HtmlAreaElement { methods: {}, child_interfaces: {} },
             // HtmlBaseElement { methods: {}, child_interfaces: {} }, TODO include metadata?
             // HtmlBodyElement { methods: {}, child_interfaces: {} }, TODO include body element?
@@ -373,7 +538,34 @@ dom_interface_macro_and_trait_definitions!(
             HtmlEmbedElement { methods: {}, child_interfaces: {} },
             HtmlFieldSetElement { methods: {}, child_interfaces: {} },
             // HtmlFontElement { methods: {}, child_interfaces: {} }, deprecated
-            HtmlFormElement { methods: {}, child_interfaces: {} },
+            HtmlFormElement {
+                methods: {
+                    /// Call `handler` after the form was reset (e.g. by a `<button type="reset">`),
+                    /// e.g. to clear the state bound to its controls.
+                    ///
+                    /// Unlike [`Element::on_reset`], the handler is called after the controls have been reset
+                    /// to their default values, so values set in response aren't overwritten by the reset.
+                    fn on_form_reset<F, OA>(self, handler: F) -> OnFormReset<Self, T, A, F>
+                    where
+                        F: Fn(&mut T) -> OA,
+                        OA: OptionalAction<A>,
+                    {
+                        OnFormReset::new(self, handler)
+                    }
+
+                    /// Reset the form whenever `generation` changes (but not when it's built),
+                    /// e.g. by incrementing a counter in the app state after the form was submitted.
+                    ///
+                    /// The controls are reset to their default values, i.e. their `value` attributes.
+                    /// But `.attr("value", ..)` of an input sets its `value` property instead,
+                    /// so such an input is cleared. Use [`HtmlFormElement::on_form_reset`]
+                    /// to update the bound state accordingly.
+                    fn reset_generation(self, generation: u32) -> ResetGeneration<Self, T, A> {
+                        ResetGeneration::new(self, generation)
+                    }
+                },
+                child_interfaces: {}
+            },
             // HtmlFrameElement { methods: {}, child_interfaces: {} }, deprecated
             // HtmlFrameSetElement { methods: {}, child_interfaces: {} }, deprecacted
             // HtmlHeadElement { methods: {}, child_interfaces: {} }, TODO include metadata?
@@ -381,8 +573,167 @@ dom_interface_macro_and_trait_definitions!(
             HtmlHrElement { methods: {}, child_interfaces: {} },
             // HtmlHtmlElement { methods: {}, child_interfaces: {} }, TODO include metadata?
             HtmlIFrameElement { methods: {}, child_interfaces: {} },
-            HtmlImageElement { methods: {}, child_interfaces: {} },
-            HtmlInputElement { methods: {}, child_interfaces: {} },
+            HtmlImageElement {
+                methods: {
+                    /// Defer loading the image until it's close to the viewport (`loading="lazy"`).
+                    fn lazy(self) -> Attr<Self, T, A> {
+                        self.attr("loading", "lazy")
+                    }
+
+                    /// Decode the image off the main thread, so that it doesn't delay other content (`decoding="async"`).
+                    fn decoding_async(self) -> Attr<Self, T, A> {
+                        self.attr("decoding", "async")
+                    }
+
+                    /// Set the intrinsic `width` and `height` of the image, in CSS pixels.
+                    ///
+                    /// Browsers reserve the space for the image with their aspect ratio before it's loaded,
+                    /// so the content doesn't shift when it's loaded. This still works with CSS that
+                    /// overrides the size, e.g. `width: 100%; height: auto` scales the image with the aspect ratio.
+                    fn dimensions(self, width: u32, height: u32) -> Attr<Attr<Self, T, A>, T, A> {
+                        self.attr("width", width).attr("height", height)
+                    }
+
+                    /// Call `handler` when the image has loaded, e.g. to remove a placeholder.
+                    ///
+                    /// Unlike [`on_load`](Element::on_load), this is also called for an image that was already
+                    /// complete (e.g. cached) when the handler was attached, and only once for each source.
+                    fn on_image_load<F, OA>(self, handler: F) -> ImageSettled<Self, T, A, F>
+                    where
+                        OA: OptionalAction<A>,
+                        F: Fn(&mut T) -> OA,
+                    {
+                        ImageSettled::new(self, ImageOutcome::Load, handler)
+                    }
+
+                    /// Call `handler` when the image failed to load, e.g. to swap in a fallback image.
+                    ///
+                    /// Like [`on_image_load`](HtmlImageElement::on_image_load), this is also called for an image
+                    /// that was already broken when the handler was attached, and only once for each source.
+                    fn on_image_error<F, OA>(self, handler: F) -> ImageSettled<Self, T, A, F>
+                    where
+                        OA: OptionalAction<A>,
+                        F: Fn(&mut T) -> OA,
+                    {
+                        ImageSettled::new(self, ImageOutcome::Error, handler)
+                    }
+                },
+                child_interfaces: {}
+            },
+            HtmlInputElement {
+                methods: {
+                    /// Call `handler` with the selected files when the `change` event fires on an `<input type="file">`.
+                    ///
+                    /// Unless the `multiple` attribute is set, the [`web_sys::FileList`] contains at most one file.
+                    fn on_files<EH, OA>(self, handler: EH) -> events::OnFiles<Self, T, A, EH>
+                    where
+                        OA: OptionalAction<A>,
+                        EH: Fn(&mut T, web_sys::FileList) -> OA,
+                    {
+                        events::OnFiles::new(self, handler)
+                    }
+
+                    /// Set whether the browser should check the spelling of the value, the attribute is `"true"` or `"false"`.
+                    fn spellcheck(self, spellcheck: bool) -> Attr<Self, T, A> {
+                        self.attr("spellcheck", if spellcheck { "true" } else { "false" })
+                    }
+
+                    /// Set the `autocomplete` attribute, i.e. the kind of data the browser may fill in.
+                    fn autocomplete(self, token: AutocompleteToken) -> Attr<Self, T, A> {
+                        self.attr("autocomplete", token.as_str())
+                    }
+
+                    /// Set the `autocapitalize` attribute, i.e. which letters on-screen keyboards capitalize automatically.
+                    fn autocapitalize(self, mode: AutocapitalizeMode) -> Attr<Self, T, A> {
+                        self.attr("autocapitalize", mode.as_str())
+                    }
+
+                    /// Call `handler` with the value of an `<input type="number">` (or `type="range"`) on `input` events.
+                    ///
+                    /// The value is `None` when the input is empty or its content isn't a valid number.
+                    fn on_number_input<EH, OA>(self, handler: EH) -> events::OnNumberInput<Self, T, A, EH>
+                    where
+                        OA: OptionalAction<A>,
+                        EH: Fn(&mut T, Option<f64>) -> OA,
+                    {
+                        events::OnNumberInput::new(self, handler)
+                    }
+
+                    /// Set the `maxlength` attribute, i.e. the maximum length of the value the user can enter.
+                    ///
+                    /// Browsers count the length in UTF-16 code units, so e.g. an emoji can count as 2 (or more),
+                    /// see [`on_length_change`](Self::on_length_change) to show the remaining count.
+                    fn maxlength(self, max_length: u32) -> Attr<Self, T, A> {
+                        self.attr("maxlength", max_length)
+                    }
+
+                    /// Call `handler` with the length of the value on `input` events, e.g. to show the remaining
+                    /// count of a [`maxlength`](Self::maxlength).
+                    ///
+                    /// The length is in UTF-16 code units like `maxlength`, not in characters (or grapheme
+                    /// clusters), so `max_length - length` is the number of units the browser still accepts.
+                    fn on_length_change<EH, OA>(self, handler: EH) -> events::OnLengthChange<Self, T, A, EH>
+                    where
+                        OA: OptionalAction<A>,
+                        EH: Fn(&mut T, usize) -> OA,
+                    {
+                        events::OnLengthChange::new(self, handler)
+                    }
+
+                    /// Call `handler` with the value of the input when Enter is pressed in it, e.g. to submit it.
+                    ///
+                    /// The Enter that commits an IME composition (e.g. for CJK input) is ignored.
+                    fn on_enter<EH, OA>(self, handler: EH) -> events::OnEnter<Self, T, A, EH>
+                    where
+                        OA: OptionalAction<A>,
+                        EH: Fn(&mut T, String) -> OA,
+                    {
+                        events::OnEnter::new(self, handler)
+                    }
+
+                    /// Make this a range slider (`<input type="range">`) between `min` and `max` in steps of `step`,
+                    /// with the value `value`, and call `on_change` with the new value when the user drags it.
+                    ///
+                    /// On rebuild, the `valueAsNumber` of the input is set to `value` (when it differs),
+                    /// which is clamped to `min` and `max`, like the values passed to `on_change`.
+                    fn slider<C, OA>(
+                        self,
+                        value: f64,
+                        min: f64,
+                        max: f64,
+                        step: f64,
+                        on_change: C,
+                    ) -> Slider<Self, T, A, C>
+                    where
+                        OA: OptionalAction<A>,
+                        C: Fn(&mut T, f64) -> OA,
+                    {
+                        Slider::new(self, value, min, max, step, on_change)
+                    }
+
+                    /// Set a custom validation message, which makes the input invalid, `None` makes it valid again.
+                    ///
+                    /// This calls `setCustomValidity` whenever the message changes, use e.g. [`Element::on_invalid`]
+                    /// to react on the input being invalid when its form is submitted.
+                    fn custom_validity(self, message: Option<&str>) -> CustomValidity<Self, T, A> {
+                        CustomValidity::new(self, message)
+                    }
+
+                    /// Set the `placeholder` attribute, the hint shown while the value is empty, `None` removes it.
+                    fn placeholder(self, placeholder: Option<Cow<'static, str>>) -> Attr<Self, T, A> {
+                        self.attr("placeholder", placeholder)
+                    }
+
+                    /// Set the boolean `readonly` attribute, so that the user can't edit the value.
+                    ///
+                    /// Unlike a disabled control, a readonly control can still be focused (and its value selected),
+                    /// and its value is submitted with its form.
+                    fn readonly(self, readonly: bool) -> Attr<Self, T, A> {
+                        self.attr("readonly", readonly)
+                    }
+                },
+                child_interfaces: {}
+            },
             HtmlLabelElement { methods: {}, child_interfaces: {} },
             HtmlLegendElement { methods: {}, child_interfaces: {} },
             HtmlLiElement { methods: {}, child_interfaces: {} },
@@ -408,7 +759,30 @@ dom_interface_macro_and_trait_definitions!(
             HtmlMenuElement { methods: {}, child_interfaces: {} },
             // HtmlMenuItemElement { methods: {}, child_interfaces: {} }, deprecated
             // HtmlMetaElement { methods: {}, child_interfaces: {} }, TODO include metadata?
-            HtmlMeterElement { methods: {}, child_interfaces: {} },
+            HtmlMeterElement {
+                methods: {
+                    /// Set the current value, `value` is reflected by the DOM property of the same name.
+                    fn value(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("value", value)
+                    }
+                    fn min(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("min", value)
+                    }
+                    fn max(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("max", value)
+                    }
+                    fn low(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("low", value)
+                    }
+                    fn high(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("high", value)
+                    }
+                    fn optimum(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("optimum", value)
+                    }
+                },
+                child_interfaces: {}
+            },
             HtmlModElement { methods: {}, child_interfaces: {} },
             HtmlObjectElement { methods: {}, child_interfaces: {} },
             HtmlOListElement { methods: {}, child_interfaces: {} },
@@ -419,12 +793,55 @@ dom_interface_macro_and_trait_definitions!(
             // HtmlParamElement { methods: {}, child_interfaces: {} }, deprecated
             HtmlPictureElement { methods: {}, child_interfaces: {} },
             HtmlPreElement { methods: {}, child_interfaces: {} },
-            HtmlProgressElement { methods: {}, child_interfaces: {} },
+            HtmlProgressElement {
+                methods: {
+                    /// Set the current progress, `None` makes the progress indeterminate.
+                    ///
+                    /// `value` is reflected by the DOM property of the same name,
+                    /// so removing it also resets that property.
+                    fn value(self, value: impl Into<Option<f64>>) -> Attr<Self, T, A> {
+                        self.attr("value", value.into())
+                    }
+                    fn max(self, value: f64) -> Attr<Self, T, A> {
+                        self.attr("max", value)
+                    }
+                },
+                child_interfaces: {}
+            },
             HtmlQuoteElement { methods: {}, child_interfaces: {} },
             HtmlScriptElement { methods: {}, child_interfaces: {} },
-            HtmlSelectElement { methods: {}, child_interfaces: {} },
+            HtmlSelectElement {
+                methods: {
+                    /// Bind the selected options of this (`multiple`) select to the values in `selected`.
+                    ///
+                    /// On rebuild, the `selected` property of the options is updated (only of those whose
+                    /// selection changed since the last rebuild), and when the user changes the selection,
+                    /// `on_change` is called with the values of all selected options.
+                    fn bind_multi_selected<C, OA>(
+                        self,
+                        selected: Vec<String>,
+                        on_change: C,
+                    ) -> BindMultiSelected<Self, T, A, C>
+                    where
+                        OA: OptionalAction<A>,
+                        C: Fn(&mut T, Vec<String>) -> OA,
+                    {
+                        BindMultiSelected::new(self, selected, on_change)
+                    }
+                },
+                child_interfaces: {}
+            },
             HtmlSlotElement { methods: {}, child_interfaces: {} },
-            HtmlSourceElement { methods: {}, child_interfaces: {} },
+            HtmlSourceElement {
+                methods: {
+                    /// Set the intrinsic `width` and `height` of the images of this source (in a `<picture>`),
+                    /// when they have another aspect ratio than the `<img>`, see [`HtmlImageElement::dimensions`].
+                    fn dimensions(self, width: u32, height: u32) -> Attr<Attr<Self, T, A>, T, A> {
+                        self.attr("width", width).attr("height", height)
+                    }
+                },
+                child_interfaces: {}
+            },
             HtmlSpanElement { methods: {}, child_interfaces: {} },
             // HtmlStyleElement { methods: {}, child_interfaces: {} }, TODO include metadata?
             HtmlTableCaptionElement { methods: {}, child_interfaces: {} },
@@ -435,7 +852,66 @@ dom_interface_macro_and_trait_definitions!(
             HtmlTableSectionElement { methods: {}, child_interfaces: {} },
             HtmlTemplateElement { methods: {}, child_interfaces: {} },
             HtmlTimeElement { methods: {}, child_interfaces: {} },
-            HtmlTextAreaElement { methods: {}, child_interfaces: {} },
+            HtmlTextAreaElement {
+                methods: {
+                    /// Grow (and shrink) the height of the textarea with its content, on input and when it's rebuilt.
+                    ///
+                    /// When the content is higher than the `max-height` of the textarea, it's capped at it, and the content scrolls.
+                    fn autosize(self) -> Autosize<Self, T, A> {
+                        Autosize::new(self)
+                    }
+
+                    /// Set the `maxlength` attribute, i.e. the maximum length of the value the user can enter.
+                    ///
+                    /// Browsers count the length in UTF-16 code units, so e.g. an emoji can count as 2 (or more),
+                    /// see [`on_length_change`](Self::on_length_change) to show the remaining count.
+                    fn maxlength(self, max_length: u32) -> Attr<Self, T, A> {
+                        self.attr("maxlength", max_length)
+                    }
+
+                    /// Call `handler` with the length of the value on `input` events, e.g. to show the remaining
+                    /// count of a [`maxlength`](Self::maxlength).
+                    ///
+                    /// The length is in UTF-16 code units like `maxlength`, not in characters (or grapheme
+                    /// clusters), so `max_length - length` is the number of units the browser still accepts.
+                    fn on_length_change<EH, OA>(self, handler: EH) -> events::OnLengthChange<Self, T, A, EH>
+                    where
+                        OA: OptionalAction<A>,
+                        EH: Fn(&mut T, usize) -> OA,
+                    {
+                        events::OnLengthChange::new(self, handler)
+                    }
+
+                    /// Set whether the browser should check the spelling of the value, the attribute is `"true"` or `"false"`.
+                    fn spellcheck(self, spellcheck: bool) -> Attr<Self, T, A> {
+                        self.attr("spellcheck", if spellcheck { "true" } else { "false" })
+                    }
+
+                    /// Set the `autocomplete` attribute, i.e. the kind of data the browser may fill in.
+                    fn autocomplete(self, token: AutocompleteToken) -> Attr<Self, T, A> {
+                        self.attr("autocomplete", token.as_str())
+                    }
+
+                    /// Set the `autocapitalize` attribute, i.e. which letters on-screen keyboards capitalize automatically.
+                    fn autocapitalize(self, mode: AutocapitalizeMode) -> Attr<Self, T, A> {
+                        self.attr("autocapitalize", mode.as_str())
+                    }
+
+                    /// Set the `placeholder` attribute, the hint shown while the value is empty, `None` removes it.
+                    fn placeholder(self, placeholder: Option<Cow<'static, str>>) -> Attr<Self, T, A> {
+                        self.attr("placeholder", placeholder)
+                    }
+
+                    /// Set the boolean `readonly` attribute, so that the user can't edit the value.
+                    ///
+                    /// Unlike a disabled control, a readonly control can still be focused (and its value selected),
+                    /// and its value is submitted with its form.
+                    fn readonly(self, readonly: bool) -> Attr<Self, T, A> {
+                        self.attr("readonly", readonly)
+                    }
+                },
+                child_interfaces: {}
+            },
             // HtmlTitleElement { methods: {}, child_interfaces: {} }, TODO include metadata?
             HtmlTrackElement { methods: {}, child_interfaces: {} },
             HtmlUListElement { methods: {}, child_interfaces: {} },
@@ -581,7 +1057,25 @@ dom_interface_macro_and_trait_definitions!(
                         },
                         child_interfaces: {}
                     },
-                    SvgsvgElement { methods: {}, child_interfaces: {} },
+                    SvgsvgElement {
+                        methods: {
+                            /// Set the `viewBox` attribute, i.e. the area of the user coordinate system that is mapped to the viewport.
+                            fn view_box(self, min_x: f64, min_y: f64, width: f64, height: f64) -> Attr<Self, T, A> {
+                                self.attr("viewBox", crate::svg::svg_element::view_box_value(min_x, min_y, width, height))
+                            }
+                            /// Set the `preserveAspectRatio` attribute, i.e. how the `viewBox` is fitted into the viewport.
+                            fn preserve_aspect_ratio(self, value: crate::svg::PreserveAspectRatio) -> Attr<Self, T, A> {
+                                self.attr("preserveAspectRatio", value.to_string())
+                            }
+                            fn width(self, value: f64) -> Attr<Self, T, A> {
+                                self.attr("width", value)
+                            }
+                            fn height(self, value: f64) -> Attr<Self, T, A> {
+                                self.attr("height", value)
+                            }
+                        },
+                        child_interfaces: {}
+                    },
                 }
             },
             SvgMarkerElement { methods: {}, child_interfaces: {} },
@@ -650,3 +1144,74 @@ macro_rules! impl_dom_traits_for_adapt_views {
     };
 }
 for_all_dom_interfaces!(impl_dom_traits_for_adapt_views, ());
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn placeholder_and_readonly_are_toggled() {
+        use wasm_bindgen::JsCast;
+
+        use super::{HtmlInputElement as _, HtmlTextAreaElement as _};
+        use crate::{elements::html, test_util::mount};
+
+        let view = |placeholder: Option<&'static str>, readonly: bool| {
+            html::div::<(), (), _>((
+                html::input(())
+                    .placeholder(placeholder.map(Into::into))
+                    .readonly(readonly),
+                html::textarea(())
+                    .placeholder(placeholder.map(Into::into))
+                    .readonly(readonly),
+            ))
+        };
+        let mut harness = mount((), view(Some("Name"), true));
+        let div = harness.root_element();
+        let input: web_sys::HtmlInputElement = div.first_element_child().unwrap().unchecked_into();
+        let textarea: web_sys::HtmlTextAreaElement =
+            div.last_element_child().unwrap().unchecked_into();
+        assert_eq!(input.placeholder(), "Name");
+        assert!(input.read_only());
+        // readonly doesn't disable the input, so its value is still submitted
+        assert!(!input.disabled());
+        assert_eq!(textarea.placeholder(), "Name");
+        assert!(textarea.read_only());
+        assert!(!textarea.disabled());
+
+        harness.rebuild(view(None, false));
+        assert!(!input.has_attribute("placeholder"));
+        assert!(!input.has_attribute("readonly"));
+        assert!(!input.read_only());
+        assert!(!textarea.has_attribute("placeholder"));
+        assert!(!textarea.read_only());
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn image_dimensions_are_set() {
+        use super::{
+            Element as _, HtmlElement as _, HtmlImageElement as _, HtmlSourceElement as _,
+        };
+        use crate::{elements::html, test_util::mount};
+
+        let harness = mount(
+            (),
+            html::picture::<(), (), _>((
+                html::source(())
+                    .attr("srcset", "wide.jpg")
+                    .dimensions(1600, 900),
+                html::img(())
+                    .attr("src", "narrow.jpg")
+                    .dimensions(800, 600)
+                    .style(crate::style("aspect-ratio", "auto 4 / 3")),
+            )),
+        );
+        let picture = harness.root_element();
+        let dimensions = |element: web_sys::Element| {
+            let parse = |name| element.get_attribute(name)?.parse::<u32>().ok();
+            (parse("width"), parse("height"))
+        };
+        let source = picture.first_element_child().unwrap();
+        assert_eq!(dimensions(source), (Some(1600), Some(900)));
+        let img = picture.last_element_child().unwrap();
+        assert_eq!(dimensions(img), (Some(800), Some(600)));
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Calling JS functions, e.g. to initialize an existing JS library on an element.
+
+use std::{any::Any, marker::PhantomData};
+
+use wasm_bindgen::JsValue;
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, Element},
+    view::DomNode,
+    ChangeFlags, Cx, View, ViewMarker,
+};
+
+/// Call `func` with `this` and `args`, when it throws, the exception is logged and `None` is returned.
+pub(crate) fn call_js(
+    func: &js_sys::Function,
+    this: &JsValue,
+    args: &[JsValue],
+) -> Option<JsValue> {
+    let args: js_sys::Array = args.iter().collect();
+    match func.apply(this, &args) {
+        Ok(result) => Some(result),
+        Err(exception) => {
+            tracing::error!("calling a JS function threw an exception: {exception:?}");
+            None
+        }
+    }
+}
+
+/// Calls a JS function with the element after it's mounted, see [`Element::with_js_init`].
+pub struct JsInit<E, T, A = ()> {
+    element: E,
+    init: js_sys::Function,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A> JsInit<E, T, A> {
+    pub(crate) fn new(element: E, init: js_sys::Function) -> Self {
+        JsInit {
+            element,
+            init,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Call `init` with `node` (as `this` and as the only argument), once the current build or rebuild
+/// is done, i.e. when the element has been inserted into the document.
+fn schedule_init(init: &js_sys::Function, node: &web_sys::Node) {
+    let (init, node) = (init.clone(), JsValue::from(node));
+    wasm_bindgen_futures::spawn_local(async move {
+        call_js(&init, &node, std::slice::from_ref(&node));
+    });
+}
+
+impl<E, T, A> ViewMarker for JsInit<E, T, A> {}
+impl<E, T, A> Sealed for JsInit<E, T, A> {}
+
+impl<E: Element<T, A>, T, A> View<T, A> for JsInit<E, T, A> {
+    type State = E::State;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.element.build(cx);
+        schedule_init(&self.init, element.as_node_ref());
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changed = self.element.rebuild(cx, &prev.element, id, state, element);
+        // The initializer only runs once per element
+        if changed.contains(ChangeFlags::STRUCTURE) {
+            schedule_init(&self.init, element.as_node_ref());
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.element.message(id_path, state, message, app_state)
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(Element, JsInit);
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use crate::{elements::html, interfaces::Element as _, Cx, View};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn js_init_receives_element() {
+        let received = Rc::new(RefCell::new(None));
+        let init = Closure::<dyn FnMut(JsValue)>::new({
+            let received = received.clone();
+            move |element| *received.borrow_mut() = Some(element)
+        });
+        let function: &js_sys::Function = init.as_ref().unchecked_ref();
+
+        let mut cx = Cx::new();
+        let view = html::div::<(), (), ()>(()).with_js_init(function.clone());
+        let (_, _, element) = View::build(&view, &mut cx);
+        assert!(received.borrow().is_none(), "init runs after the build");
+
+        // wait until the initializer has run
+        let resolved = js_sys::Promise::resolve(&JsValue::UNDEFINED);
+        wasm_bindgen_futures::JsFuture::from(resolved)
+            .await
+            .unwrap();
+        assert_eq!(received.borrow().as_ref(), Some(element.as_ref()));
+    }
+
+    #[wasm_bindgen_test]
+    fn throwing_function_is_caught() {
+        let cx = Cx::new();
+        let throws = js_sys::Function::new_no_args("throw new Error('boom')");
+        assert!(cx.call_js(&throws, &JsValue::NULL, &[]).is_none());
+
+        let adds = js_sys::Function::new_with_args("a, b", "return a + b");
+        let sum = cx.call_js(&adds, &JsValue::NULL, &[1.into(), 2.into()]);
+        assert_eq!(sum.and_then(|sum| sum.as_f64()), Some(3.0));
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A `<label>` that is associated with the form control it contains.
+
+use std::{borrow::Cow, marker::PhantomData};
+
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::{ChangeFlags, Cx, HtmlProps},
+    interfaces::{sealed::Sealed, Element, HtmlElement, HtmlLabelElement},
+    view::{DomNode, View, ViewMarker},
+    AttributeValue, HTML_NS,
+};
+
+type CowStr = Cow<'static, str>;
+
+pub struct Labeled<V, T, A = ()> {
+    label: CowStr,
+    control: V,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct LabeledState<S, E> {
+    control_id: CowStr,
+    props: HtmlProps,
+    text: web_sys::Text,
+    control_view_id: Id,
+    control_state: S,
+    control_element: E,
+    has_debug_id: bool,
+}
+
+/// Wrap `control` in a `<label>` with the text `label`.
+///
+/// A unique `id` is generated and set on the control, and the `for` attribute of the label
+/// is set to it, so the label stays associated with the control (e.g. for screen readers,
+/// or to focus the control when clicking on the label).
+/// The id is generated once, when the view is built, and kept across rebuilds.
+pub fn labeled<V, T, A>(label: impl Into<CowStr>, control: V) -> Labeled<V, T, A> {
+    Labeled {
+        label: label.into(),
+        control,
+        phantom: PhantomData,
+    }
+}
+
+/// The `id` attribute used for the control of the [`Labeled`] view with the given `id`,
+/// i.e. its [`Cx::unique_id`].
+fn control_id(id: Id) -> CowStr {
+    crate::context::unique_id("xilem-labeled", id)
+}
+
+impl<V, T, A> ViewMarker for Labeled<V, T, A> {}
+impl<V, T, A> Sealed for Labeled<V, T, A> {}
+
+impl<V: View<T, A>, T, A> View<T, A> for Labeled<V, T, A> {
+    type State = LabeledState<V::State, V::Element>;
+    type Element = web_sys::HtmlLabelElement;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let id = Id::next();
+        let control_id = control_id(id);
+
+        cx.add_attr_to_element(
+            &"for".into(),
+            &Some(AttributeValue::String(control_id.clone())),
+        );
+        let (el, props) = cx.build_element(HTML_NS, "label");
+
+        let text = web_sys::Text::new_with_data(&self.label).unwrap_throw();
+        el.append_child(&text).unwrap_throw();
+
+        cx.add_attr_to_element(
+            &"id".into(),
+            &Some(AttributeValue::String(control_id.clone())),
+        );
+        let (control_view_id, control_state, control_element) =
+            cx.with_id(id, |cx| self.control.build(cx));
+        el.append_child(control_element.as_node_ref())
+            .unwrap_throw();
+
+        let mut has_debug_id = false;
+        cx.update_debug_id(&el, id, &mut has_debug_id);
+
+        let state = LabeledState {
+            control_id,
+            props,
+            text,
+            control_view_id,
+            control_state,
+            control_element,
+            has_debug_id,
+        };
+        (id, state, el.dyn_into().unwrap_throw())
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = ChangeFlags::empty();
+
+        cx.add_attr_to_element(
+            &"for".into(),
+            &Some(AttributeValue::String(state.control_id.clone())),
+        );
+        changed |= cx.rebuild_element(element, &mut state.props);
+        cx.update_debug_id(element, *id, &mut state.has_debug_id);
+
+        if prev.label != self.label {
+            state.text.set_data(&self.label);
+            changed |= ChangeFlags::OTHER_CHANGE;
+        }
+
+        cx.add_attr_to_element(
+            &"id".into(),
+            &Some(AttributeValue::String(state.control_id.clone())),
+        );
+        let prev_node = state.control_element.as_node_ref().clone();
+        changed |= cx.with_id(*id, |cx| {
+            self.control.rebuild(
+                cx,
+                &prev.control,
+                &mut state.control_view_id,
+                &mut state.control_state,
+                &mut state.control_element,
+            )
+        });
+
+        // the control may have been replaced with a new element
+        let node = state.control_element.as_node_ref();
+        if *node != prev_node {
+            let label: &web_sys::Node = element;
+            if prev_node.parent_node().as_ref() == Some(label) {
+                element.replace_child(node, &prev_node).unwrap_throw();
+            } else {
+                element.append_child(node).unwrap_throw();
+            }
+        }
+        changed.remove(ChangeFlags::STRUCTURE);
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path.split_first() {
+            Some((first, rest_path)) if *first == state.control_view_id => {
+                self.control
+                    .message(rest_path, &mut state.control_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+impl<V: View<T, A>, T, A> Element<T, A> for Labeled<V, T, A> {}
+impl<V: View<T, A>, T, A> HtmlElement<T, A> for Labeled<V, T, A> {}
+impl<V: View<T, A>, T, A> HtmlLabelElement<T, A> for Labeled<V, T, A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::control_id;
+    use xilem_core::Id;
+
+    #[test]
+    fn labeled_controls_get_distinct_ids() {
+        let first = control_id(Id::next());
+        let second = control_id(Id::next());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn control_id_is_stable() {
+        let id = Id::next();
+        assert_eq!(control_id(id), control_id(id));
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deferring the build of a subtree until it's needed, e.g. for the content of a tab that isn't shown yet.
+
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult};
+
+use crate::{interfaces::sealed::Sealed, view::DomNode, ChangeFlags, Cx, Pod, View, ViewMarker};
+
+pub struct Lazy<F, T, A = ()> {
+    build_when: bool,
+    view_fn: F,
+    keep_state: bool,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct LazyState<V, S, E> {
+    placeholder: Pod,
+    child: Option<LazyChild<V, S, E>>,
+}
+
+/// The inner view of a [`Lazy`], which is kept while it's hidden with [`Lazy::keep_state`].
+struct LazyChild<V, S, E> {
+    view: V,
+    id: Id,
+    state: S,
+    element: E,
+    shown: bool,
+}
+
+/// Build the view returned by `view_fn` only when `build_when` is `true`, until then, it's rendered as a placeholder comment node.
+///
+/// `view_fn` isn't called as long as `build_when` is `false`. When it becomes `false` again,
+/// the inner view is torn down, use [`Lazy::keep_state`] to keep it around instead.
+pub fn lazy<F, V, T, A>(build_when: bool, view_fn: F) -> Lazy<F, T, A>
+where
+    F: Fn() -> V,
+    V: View<T, A>,
+{
+    Lazy {
+        build_when,
+        view_fn,
+        keep_state: false,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, T, A> Lazy<F, T, A> {
+    /// Keep the inner view (including its state and DOM element) while `build_when` is `false`,
+    /// after it was built once, instead of tearing it down.
+    ///
+    /// The inner view is then not rebuilt while it's hidden, but when it's shown again.
+    pub fn keep_state(mut self) -> Self {
+        self.keep_state = true;
+        self
+    }
+}
+
+impl<F, T, A> ViewMarker for Lazy<F, T, A> {}
+impl<F, T, A> Sealed for Lazy<F, T, A> {}
+
+impl<F, V, T, A> View<T, A> for Lazy<F, T, A>
+where
+    F: Fn() -> V,
+    V: View<T, A>,
+{
+    type State = LazyState<V, V::State, V::Element>;
+    type Element = web_sys::Node;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let placeholder = cx.create_marker();
+        let (id, child) = cx.with_new_id(|cx| self.build_when.then(|| self.build_child(cx)));
+        let element = match &child {
+            Some(child) => child.element.as_node_ref().clone(),
+            None => placeholder.0.as_node_ref().clone(),
+        };
+        (id, LazyState { placeholder, child }, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = cx.with_id(*id, |cx| match (&mut state.child, self.build_when) {
+            (Some(child), true) => {
+                let view = (self.view_fn)();
+                let changed = view.rebuild(
+                    cx,
+                    &child.view,
+                    &mut child.id,
+                    &mut child.state,
+                    &mut child.element,
+                );
+                child.view = view;
+                child.shown = true;
+                changed
+            }
+            (None, true) => {
+                state.child = Some(self.build_child(cx));
+                ChangeFlags::STRUCTURE
+            }
+            (Some(child), false) if self.keep_state => {
+                child.shown = false;
+                ChangeFlags::empty()
+            }
+            (Some(_), false) => {
+                state.child = None;
+                ChangeFlags::empty()
+            }
+            (None, false) => ChangeFlags::empty(),
+        });
+
+        let node = match &state.child {
+            Some(child) if child.shown => child.element.as_node_ref(),
+            _ => state.placeholder.0.as_node_ref(),
+        };
+        if node != element {
+            *element = node.clone();
+            changed |= ChangeFlags::STRUCTURE;
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match (id_path, &mut state.child) {
+            ([child_id, rest_path @ ..], Some(child)) if *child_id == child.id => child
+                .view
+                .message(rest_path, &mut child.state, message, app_state),
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+impl<F, V, T, A> Lazy<F, T, A>
+where
+    F: Fn() -> V,
+    V: View<T, A>,
+{
+    fn build_child(&self, cx: &mut Cx) -> LazyChild<V, V::State, V::Element> {
+        let view = (self.view_fn)();
+        let (id, state, element) = view.build(cx);
+        LazyChild {
+            view,
+            id,
+            state,
+            element,
+            shown: true,
+        }
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::cell::Cell;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::lazy;
+    use crate::{elements::html, Cx, View};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn inner_view_is_built_when_flag_flips() {
+        let built = Cell::new(0);
+        let tab = |shown| {
+            lazy(shown, || {
+                built.set(built.get() + 1);
+                html::div::<(), (), _>("heavy")
+            })
+        };
+
+        let mut cx = Cx::new();
+        let hidden = tab(false);
+        let (mut id, mut state, mut element) = View::build(&hidden, &mut cx);
+        assert_eq!(built.get(), 0);
+        assert_eq!(element.node_type(), web_sys::Node::COMMENT_NODE);
+
+        let shown = tab(true);
+        let changed = View::rebuild(&shown, &mut cx, &hidden, &mut id, &mut state, &mut element);
+        assert_eq!(built.get(), 1);
+        assert!(changed.contains(crate::ChangeFlags::STRUCTURE));
+        assert_eq!(element.text_content().as_deref(), Some("heavy"));
+
+        // the inner view is torn down when hidden again
+        View::rebuild(&hidden, &mut cx, &shown, &mut id, &mut state, &mut element);
+        assert_eq!(element.node_type(), web_sys::Node::COMMENT_NODE);
+        assert!(state.child.is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn kept_state_is_reused() {
+        let tab = |shown| lazy(shown, || html::div::<(), (), _>("tab")).keep_state();
+
+        let mut cx = Cx::new();
+        let shown = tab(true);
+        let (mut id, mut state, mut element) = View::build(&shown, &mut cx);
+        let inner = element.clone();
+
+        let hidden = tab(false);
+        View::rebuild(&hidden, &mut cx, &shown, &mut id, &mut state, &mut element);
+        assert_eq!(element.node_type(), web_sys::Node::COMMENT_NODE);
+
+        View::rebuild(&shown, &mut cx, &hidden, &mut id, &mut state, &mut element);
+        assert_eq!(element, inner);
+    }
+}
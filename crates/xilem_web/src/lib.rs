@@ -7,42 +7,118 @@
 
 use wasm_bindgen::JsCast;
 
+mod accordion;
+mod anchor;
+mod animation;
 mod app;
 mod attribute;
 mod attribute_value;
+mod autosize;
+mod before_unload;
+mod catch_errors;
 mod class;
+mod combobox;
+mod composition;
 mod context;
+mod crossfade;
+mod deferred;
 mod diff;
+mod dom_op_log;
 pub mod elements;
 pub mod events;
+mod form;
+mod head;
+mod image;
+mod input_hints;
 pub mod interfaces;
+mod js_init;
+mod labeled;
+mod lazy;
+mod location;
+mod media_query;
+#[cfg(debug_assertions)]
+mod message_trace;
+mod multi_select;
+mod node_pool;
 mod one_of;
 mod optional_action;
+mod perf_stats;
+mod picture;
 mod pointer;
+mod poll;
+mod scroll;
+mod shadow;
+mod show_if;
+mod slider;
+mod storage;
 mod style;
 pub mod svg;
+#[cfg(feature = "testing")]
+pub mod test_util;
+mod text_fmt;
+mod transition;
+mod validity;
 mod vecmap;
 mod view;
 mod view_ext;
+mod virtual_list;
+mod worker;
 
-pub use xilem_core::MessageResult;
+pub use xilem_core::{for_each_map, indexed, separated, MessageResult};
 
+pub use accordion::{accordion, Accordion, AccordionState};
+pub use anchor::TargetBlank;
+pub use animation::{animation, Animation, AnimationState};
 pub use app::App;
 pub use attribute::Attr;
 pub use attribute_value::{AttributeValue, IntoAttributeValue};
+pub use autosize::{Autosize, AutosizeState};
+pub use before_unload::{before_unload, BeforeUnload, BeforeUnloadState};
+pub use catch_errors::{catch_errors, CatchErrors, CatchErrorsState, CaughtError, ErrorReporter};
+pub use combobox::{combobox, Combobox, ComboboxInput, ComboboxState, ComboboxSuggestion};
+pub use composition::{OnCommittedInput, OnCommittedInputState};
 pub use context::{ChangeFlags, Cx};
+pub use crossfade::{crossfade, Crossfade, CrossfadeState};
+pub use diff::Diff;
+pub use dom_op_log::DomOp;
+pub use form::{OnFormReset, OnFormResetState, ResetGeneration};
+pub use head::HeadNode;
+pub use image::{ImageSettled, ImageSettledState};
+pub use input_hints::{AutocapitalizeMode, AutocompleteToken};
+pub use js_init::JsInit;
+pub use labeled::{labeled, Labeled, LabeledState};
+pub use lazy::{lazy, Lazy, LazyState};
+pub use location::{build_url, navigate, parse_query, LocationInfo};
+pub use media_query::{media_query, ColorScheme, MediaQuery, MediaQueryState, PREFERS_DARK};
+#[cfg(debug_assertions)]
+pub use message_trace::{MessageOutcome, RoutedMessage};
+pub use multi_select::{BindMultiSelected, BindMultiSelectedState};
 pub use one_of::{
     OneOf2, OneOf3, OneOf4, OneOf5, OneOf6, OneOf7, OneOf8, OneSeqOf2, OneSeqOf3, OneSeqOf4,
     OneSeqOf5, OneSeqOf6, OneSeqOf7, OneSeqOf8,
 };
 pub use optional_action::{Action, OptionalAction};
+pub use perf_stats::PerfStats;
+pub use picture::{picture, PictureSource, PictureSourceView, PictureView};
 pub use pointer::{Pointer, PointerDetails, PointerMsg};
+pub use poll::{poll, Poll, PollState};
+pub use scroll::{OnScroll, OnScrollState};
+pub use shadow::{Shadow, ShadowState};
+pub use show_if::{show_if, ShowIf};
+pub use slider::{Slider, SliderState};
+pub use storage::{StorageError, StorageKind, WebStorage};
 pub use style::style;
+pub use text_fmt::{text_fmt, TextFmt, TextFmtState};
+pub use transition::{transition, Transition};
+pub use validity::CustomValidity;
+pub use vecmap::VecMap;
 pub use view::{
     memoize, static_view, Adapt, AdaptState, AdaptThunk, AnyView, BoxedView, ElementsSplice,
     Memoize, MemoizeState, Pod, View, ViewMarker, ViewSequence,
 };
 pub use view_ext::ViewExt;
+pub use virtual_list::{virtual_list, visible_range, VirtualList, VirtualListState};
+pub use worker::{worker, Worker, WorkerError, WorkerState};
 
 xilem_core::message!();
 
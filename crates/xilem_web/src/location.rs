@@ -0,0 +1,171 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reading the URL of the document, and navigating to other URLs, e.g. to derive routing state from it.
+
+use wasm_bindgen::UnwrapThrowExt;
+
+/// The parts of the URL of the document, see [`Cx::location`](crate::Cx::location).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationInfo {
+    /// The path, e.g. `"/todos/active"`.
+    pub pathname: String,
+    /// The decoded key-value pairs of the query string, in the order in which they appear in the URL.
+    pub search: Vec<(String, String)>,
+    /// The fragment identifier without the leading `#`, e.g. `"top"`.
+    pub hash: String,
+}
+
+impl LocationInfo {
+    /// Parse the `pathname`, `search` and `hash` of a [`web_sys::Location`] (with their leading `?` and `#`).
+    pub fn parse(pathname: &str, search: &str, hash: &str) -> Self {
+        LocationInfo {
+            pathname: pathname.to_owned(),
+            search: parse_query(search),
+            hash: hash.strip_prefix('#').unwrap_or(hash).to_owned(),
+        }
+    }
+
+    pub(crate) fn from_location(location: &web_sys::Location) -> Self {
+        LocationInfo::parse(
+            &location.pathname().unwrap_throw(),
+            &location.search().unwrap_throw(),
+            &location.hash().unwrap_throw(),
+        )
+    }
+
+    /// The value of the first query parameter called `key`.
+    pub fn query(&self, key: &str) -> Option<&str> {
+        self.search
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse a query string (with or without the leading `?`) into its decoded key-value pairs.
+///
+/// A `+` is decoded as space, and a key without `=` has an empty value.
+pub fn parse_query(search: &str) -> Vec<(String, String)> {
+    let search = search.strip_prefix('?').unwrap_or(search);
+    search
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_component(key), decode_component(value))
+        })
+        .collect()
+}
+
+/// Build a URL from a path, query parameters (which are encoded) and an optional fragment identifier.
+pub fn build_url(pathname: &str, query: &[(&str, &str)], hash: Option<&str>) -> String {
+    let mut url = pathname.to_owned();
+    for (i, (key, value)) in query.iter().enumerate() {
+        url.push(if i == 0 { '?' } else { '&' });
+        url.push_str(&encode_component(key));
+        url.push('=');
+        url.push_str(&encode_component(value));
+    }
+    if let Some(hash) = hash {
+        url.push('#');
+        url.push_str(hash);
+    }
+    url
+}
+
+/// Navigate to `url` without reloading the page, by pushing it to the session history.
+///
+/// As with `history.pushState`, no `popstate` event is fired, the new [`Cx::location`](crate::Cx::location)
+/// is visible on the next rebuild.
+pub fn navigate(url: &str) {
+    web_sys::window()
+        .unwrap_throw()
+        .history()
+        .unwrap_throw()
+        .push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(url))
+        .unwrap_throw();
+}
+
+/// Percent-decodes `component`, invalid escapes and invalid UTF-8 are kept as is (or replaced, respectively).
+fn decode_component(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (_, Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', None) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, None) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+fn encode_component(component: &str) -> String {
+    let mut encoded = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_url, parse_query, LocationInfo};
+
+    #[test]
+    fn query_values_are_decoded() {
+        let location = LocationInfo::parse("/search", "?a=1&b=two%20words", "#results");
+        assert_eq!(location.query("a"), Some("1"));
+        assert_eq!(location.query("b"), Some("two words"));
+        assert_eq!(location.query("c"), None);
+        assert_eq!(location.hash, "results");
+
+        assert_eq!(
+            parse_query("q=a+b&flag&utf8=%C3%A4&bad=%zz"),
+            [
+                ("q".into(), "a b".into()),
+                ("flag".into(), "".into()),
+                ("utf8".into(), "ä".into()),
+                ("bad".into(), "%zz".into()),
+            ]
+        );
+        assert!(parse_query("").is_empty());
+    }
+
+    #[test]
+    fn built_urls_round_trip() {
+        let url = build_url(
+            "/search",
+            &[("q", "two words & more"), ("ä", "=")],
+            Some("top"),
+        );
+        assert_eq!(url, "/search?q=two%20words%20%26%20more&%C3%A4=%3D#top");
+
+        let (path, rest) = url.split_once('?').unwrap();
+        let (search, hash) = rest.split_once('#').unwrap();
+        let location = LocationInfo::parse(path, search, hash);
+        assert_eq!(location.query("q"), Some("two words & more"));
+        assert_eq!(location.query("ä"), Some("="));
+    }
+}
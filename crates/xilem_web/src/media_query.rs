@@ -0,0 +1,232 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reacting to CSS media queries, e.g. to follow the color scheme preferred by the user.
+
+use std::{any::Any, borrow::Cow, marker::PhantomData};
+
+use gloo::events::EventListener;
+use wasm_bindgen::UnwrapThrowExt;
+use xilem_core::{Id, MessageResult};
+
+use crate::{interfaces::sealed::Sealed, ChangeFlags, Cx, OptionalAction, View, ViewMarker};
+
+/// The media query that matches when the user prefers a dark color scheme.
+pub const PREFERS_DARK: &str = "(prefers-color-scheme: dark)";
+
+/// The color scheme preferred by the user (or the system), see [`Cx::color_scheme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorScheme {
+    /// Also used when there's no preference.
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    /// The color scheme for whether [`PREFERS_DARK`] matches, e.g. in the `on_change` handler of a [`media_query`].
+    pub fn from_prefers_dark(prefers_dark: bool) -> Self {
+        if prefers_dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        }
+    }
+}
+
+pub struct MediaQuery<F, T, A = ()> {
+    query: Cow<'static, str>,
+    on_change: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct MediaQueryState {
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// Call `on_change` with whether the CSS media `query` matches, whenever that changes.
+///
+/// `on_change` isn't called when the view is built, the initial state can be read with
+/// [`window.matchMedia`](web_sys::Window::match_media) (or [`Cx::color_scheme`] for [`PREFERS_DARK`]).
+/// When the query itself changes on rebuild, `on_change` is called with whether the new query matches.
+///
+/// The listener is removed when the view is removed.
+/// The view itself is rendered as an empty text node.
+pub fn media_query<F, T, A, OA>(
+    query: impl Into<Cow<'static, str>>,
+    on_change: F,
+) -> MediaQuery<F, T, A>
+where
+    F: Fn(&mut T, bool) -> OA,
+    OA: OptionalAction<A>,
+{
+    MediaQuery {
+        query: query.into(),
+        on_change,
+        phantom: PhantomData,
+    }
+}
+
+/// Sent when the media query has changed, with whether it matches now.
+struct MediaQueryChanged(bool);
+
+pub(crate) fn match_media(query: &str) -> web_sys::MediaQueryList {
+    web_sys::window()
+        .unwrap_throw()
+        .match_media(query)
+        .unwrap_throw()
+        .expect_throw("matchMedia didn't return a MediaQueryList")
+}
+
+/// Listen to changes of `list`, `matches` is read from the list, as the `change` event may be synthetic.
+fn create_change_listener(list: web_sys::MediaQueryList, cx: &Cx) -> EventListener {
+    let thunk = cx.message_thunk();
+    EventListener::new(&list.clone(), "change", move |_| {
+        thunk.push_message(MediaQueryChanged(list.matches()));
+    })
+}
+
+impl<F, T, A> ViewMarker for MediaQuery<F, T, A> {}
+impl<F, T, A> Sealed for MediaQuery<F, T, A> {}
+
+impl<F, T, A, OA> View<T, A> for MediaQuery<F, T, A>
+where
+    F: Fn(&mut T, bool) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = MediaQueryState;
+    type Element = web_sys::Text;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, listener) =
+            cx.with_new_id(|cx| create_change_listener(match_media(&self.query), cx));
+        let element = web_sys::Text::new().unwrap_throw();
+        (id, MediaQueryState { listener }, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        _element: &mut Self::Element,
+    ) -> ChangeFlags {
+        if prev.query != self.query {
+            cx.with_id(*id, |cx| {
+                let list = match_media(&self.query);
+                cx.message_self(Box::new(MediaQueryChanged(list.matches())));
+                // The listener of the previous query is removed when it's dropped
+                state.listener = create_change_listener(list, cx);
+            });
+        }
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        if !id_path.is_empty() {
+            return MessageResult::Stale(message);
+        }
+        match message.downcast::<MediaQueryChanged>() {
+            Ok(changed) => match (self.on_change)(app_state, changed.0).action() {
+                Some(action) => MessageResult::Action(action),
+                None => MessageResult::Nop,
+            },
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorScheme;
+
+    #[test]
+    fn color_scheme_from_prefers_dark() {
+        assert_eq!(ColorScheme::from_prefers_dark(true), ColorScheme::Dark);
+        assert_eq!(ColorScheme::from_prefers_dark(false), ColorScheme::Light);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn changed_color_scheme_calls_handler() {
+        use std::{cell::RefCell, rc::Rc};
+
+        use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+        use super::{media_query, PREFERS_DARK};
+        use crate::{test_util::mount, Cx};
+
+        /// Replaces `window.matchMedia` with lists whose `matches` can be set, until it's dropped.
+        struct MockMatchMedia {
+            lists: Rc<RefCell<Vec<web_sys::EventTarget>>>,
+            #[allow(unused)]
+            mock: Closure<dyn FnMut(String) -> JsValue>,
+        }
+
+        impl MockMatchMedia {
+            fn install(matches: bool) -> Self {
+                let lists = Rc::new(RefCell::new(Vec::new()));
+                let mock = Closure::<dyn FnMut(String) -> JsValue>::new({
+                    let lists = lists.clone();
+                    move |_query| {
+                        let list = web_sys::EventTarget::new().unwrap();
+                        js_sys::Reflect::set(&list, &"matches".into(), &matches.into()).unwrap();
+                        lists.borrow_mut().push(list.clone());
+                        list.into()
+                    }
+                });
+                let window = web_sys::window().unwrap();
+                js_sys::Reflect::set(&window, &"matchMedia".into(), mock.as_ref()).unwrap();
+                MockMatchMedia { lists, mock }
+            }
+
+            /// Set whether the `index`th created list matches and fire its `change` event.
+            fn flip(&self, index: usize, matches: bool) {
+                let list = self.lists.borrow()[index].clone();
+                js_sys::Reflect::set(&list, &"matches".into(), &matches.into()).unwrap();
+                list.dispatch_event(&web_sys::Event::new("change").unwrap())
+                    .unwrap();
+            }
+        }
+
+        impl Drop for MockMatchMedia {
+            fn drop(&mut self) {
+                let window: JsValue = web_sys::window().unwrap().into();
+                js_sys::Reflect::delete_property(window.unchecked_ref(), &"matchMedia".into())
+                    .unwrap();
+            }
+        }
+
+        let mock = MockMatchMedia::install(false);
+        assert_eq!(Cx::new().color_scheme(), ColorScheme::Light);
+
+        let view = |query: &'static str| {
+            media_query::<_, _, (), _>(query, |scheme: &mut ColorScheme, dark| {
+                *scheme = ColorScheme::from_prefers_dark(dark);
+            })
+        };
+        let mut harness = mount(ColorScheme::Light, view(PREFERS_DARK));
+        mock.flip(1, true);
+        harness.handle_messages();
+        assert_eq!(*harness.app_state(), ColorScheme::Dark);
+        mock.flip(1, false);
+        harness.handle_messages();
+        assert_eq!(*harness.app_state(), ColorScheme::Light);
+
+        // the listener of the previous query is removed
+        *harness.app_state_mut() = ColorScheme::Dark;
+        harness.rebuild(view("(prefers-contrast: more)"));
+        assert_eq!(*harness.app_state(), ColorScheme::Light);
+        mock.flip(1, true);
+        harness.handle_messages();
+        assert_eq!(*harness.app_state(), ColorScheme::Light);
+    }
+}
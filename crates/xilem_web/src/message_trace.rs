@@ -0,0 +1,221 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recording how messages were routed to the views, see [`Cx::set_message_trace_capacity`](crate::Cx::set_message_trace_capacity).
+
+use std::collections::{HashSet, VecDeque};
+
+use xilem_core::{Id, IdPath, MessageResult};
+
+/// How many unroutable ids are remembered to warn only once about each of them.
+const MAX_WARNED: usize = 256;
+
+/// What the views did with a message, see [`RoutedMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageOutcome {
+    /// A view handled the message, without an action.
+    Nop,
+    /// A view handled the message and returned an action.
+    Action,
+    /// A view handled the message and requested a rebuild.
+    RequestRebuild,
+    /// No view handled the message.
+    ///
+    /// `unknown_segment` is the index of the first id of the path that wasn't built or rebuilt
+    /// in the last pass, i.e. where the routing broke (e.g. the view was removed in the meantime).
+    /// `None` if all ids of the path are known, i.e. the target view didn't expect the message.
+    Stale { unknown_segment: Option<usize> },
+}
+
+/// A message that was dispatched to the views, with the id path of its target view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutedMessage {
+    pub id_path: IdPath,
+    pub outcome: MessageOutcome,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MessageTrace {
+    /// The ids of the views that were visited in the current (or last) build/rebuild pass.
+    ///
+    /// The children of memoized views aren't visited during a rebuild, so a stale message
+    /// to one of them is reported with the first of their ids as unknown segment.
+    known_ids: HashSet<Id>,
+    /// The ids a warning was logged for, so that e.g. pointer moves don't flood the console.
+    warned: HashSet<Id>,
+    capacity: usize,
+    messages: VecDeque<RoutedMessage>,
+}
+
+impl MessageTrace {
+    /// Record that the view with `id` was visited.
+    pub(crate) fn visit(&mut self, id: Id) {
+        self.known_ids.insert(id);
+    }
+
+    /// Forget all visited ids, this should be called before every build/rebuild pass.
+    pub(crate) fn start_pass(&mut self) {
+        self.known_ids.clear();
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.messages.len().saturating_sub(capacity);
+        self.messages.drain(..excess);
+    }
+
+    pub(crate) fn messages(&self) -> &VecDeque<RoutedMessage> {
+        &self.messages
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Record the `result` of the message to `id_path`, and warn (once per id) when it's stale.
+    pub(crate) fn record<A>(&mut self, id_path: &[Id], result: &MessageResult<A>) {
+        let outcome = match result {
+            MessageResult::Nop => MessageOutcome::Nop,
+            MessageResult::Action(_) => MessageOutcome::Action,
+            MessageResult::RequestRebuild => MessageOutcome::RequestRebuild,
+            MessageResult::Stale(_) => {
+                let unknown_segment = id_path.iter().position(|id| !self.known_ids.contains(id));
+                self.warn_stale(id_path, unknown_segment);
+                MessageOutcome::Stale { unknown_segment }
+            }
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(RoutedMessage {
+            id_path: id_path.to_vec(),
+            outcome,
+        });
+    }
+
+    fn warn_stale(&mut self, id_path: &[Id], unknown_segment: Option<usize>) {
+        let id = match unknown_segment {
+            Some(segment) => id_path.get(segment),
+            None => id_path.last(),
+        };
+        let Some(&id) = id else {
+            return;
+        };
+        if self.warned.len() >= MAX_WARNED {
+            self.warned.clear();
+        }
+        if !self.warned.insert(id) {
+            return;
+        }
+        let path = format_id_path(id_path);
+        match unknown_segment {
+            Some(segment) => tracing::warn!(
+                "the message to {path} wasn't handled, the routing broke at segment {segment} \
+                 (id {}), no view with that id was built in the last pass",
+                id.to_raw()
+            ),
+            None => tracing::warn!(
+                "the message to {path} wasn't handled by its target view (id {})",
+                id.to_raw()
+            ),
+        }
+    }
+}
+
+/// The raw ids of `id_path`, as used by the `data-debugid` attribute, e.g. `[1, 4, 7]`.
+fn format_id_path(id_path: &[Id]) -> String {
+    let ids: Vec<_> = id_path.iter().map(|id| id.to_raw().to_string()).collect();
+    format!("[{}]", ids.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{field::Field, span, Event, Metadata, Subscriber};
+    use xilem_core::{Id, MessageResult};
+
+    use super::{MessageOutcome, MessageTrace};
+
+    /// Collects the messages of all events.
+    #[derive(Default)]
+    struct WarningRecorder(Mutex<Vec<String>>);
+
+    impl tracing::field::Visit for &WarningRecorder {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0.lock().unwrap().push(format!("{value:?}"));
+            }
+        }
+    }
+
+    impl Subscriber for WarningRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut &*self);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn first_unknown_id_is_the_broken_segment() {
+        let mut trace = MessageTrace::default();
+        trace.set_capacity(4);
+        let (root, child, removed) = (Id::next(), Id::next(), Id::next());
+        trace.visit(root);
+        trace.visit(child);
+
+        trace.record(&[root, child], &MessageResult::<()>::Nop);
+        trace.record(
+            &[root, removed, child],
+            &MessageResult::<()>::Stale(Box::new(())),
+        );
+        trace.record(&[root, child], &MessageResult::<()>::Stale(Box::new(())));
+        let outcomes: Vec<_> = trace.messages().iter().map(|m| m.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [
+                MessageOutcome::Nop,
+                MessageOutcome::Stale {
+                    unknown_segment: Some(1)
+                },
+                MessageOutcome::Stale {
+                    unknown_segment: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stale_message_warns_once_with_broken_segment() {
+        let recorder = Arc::new(WarningRecorder::default());
+        let mut trace = MessageTrace::default();
+        let (root, removed) = (Id::next(), Id::next());
+        trace.visit(root);
+        tracing::subscriber::with_default(recorder.clone(), || {
+            // e.g. the pointer moves over a view that was removed
+            for _ in 0..3 {
+                trace.record(&[root, removed], &MessageResult::<()>::Stale(Box::new(())));
+            }
+        });
+
+        let warnings = recorder.0.lock().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains(&format!("segment 1 (id {})", removed.to_raw())),
+            "{}",
+            warnings[0]
+        );
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Binding the selected options of a `<select multiple>` to the app state.
+
+use std::{any::Any, marker::PhantomData};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, HtmlSelectElement},
+    view::DomNode,
+    AttributeValue, ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Binds the selected options of a `<select multiple>`, see [`HtmlSelectElement::bind_multi_selected`].
+pub struct BindMultiSelected<E, T, A, C> {
+    element: E,
+    selected: Vec<String>,
+    on_change: C,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, C> BindMultiSelected<E, T, A, C> {
+    pub(crate) fn new(element: E, selected: Vec<String>, on_change: C) -> Self {
+        BindMultiSelected {
+            element,
+            selected,
+            on_change,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct BindMultiSelectedState<S> {
+    child_id: Id,
+    child_state: S,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// The values that are in exactly one of `prev` and `next`, i.e. the options whose selection changed.
+fn changed_values<'a>(prev: &'a [String], next: &'a [String]) -> Vec<&'a str> {
+    let removed = prev.iter().filter(|value| !next.contains(value));
+    let added = next.iter().filter(|value| !prev.contains(value));
+    removed.chain(added).map(String::as_str).collect()
+}
+
+fn options(select: &web_sys::Node) -> impl Iterator<Item = web_sys::HtmlOptionElement> {
+    let options = select
+        .unchecked_ref::<web_sys::Element>()
+        .query_selector_all("option")
+        .unwrap_throw();
+    (0..options.length()).map(move |i| options.get(i).unwrap_throw().unchecked_into())
+}
+
+/// Set the `selected` property of the options of `select` with one of the values in `only`
+/// (or all of them, when it's `None`), to whether their value is in `selected`.
+fn sync_options(select: &web_sys::Node, selected: &[String], only: Option<&[&str]>) {
+    for option in options(select) {
+        let value = option.value();
+        if only.is_some_and(|only| !only.contains(&value.as_str())) {
+            continue;
+        }
+        let is_selected = selected.contains(&value);
+        if option.selected() != is_selected {
+            option.set_selected(is_selected);
+        }
+    }
+}
+
+fn selected_values(select: &web_sys::Node) -> Vec<String> {
+    options(select)
+        .filter(|option| option.selected())
+        .map(|option| option.value())
+        .collect()
+}
+
+fn create_change_listener(element: &web_sys::Node, cx: &Cx) -> EventListener {
+    let thunk = cx.message_thunk();
+    EventListener::new(element, "change", move |event| {
+        thunk.push_message(event.clone());
+    })
+}
+
+impl<E, T, A, C> ViewMarker for BindMultiSelected<E, T, A, C> {}
+impl<E, T, A, C> Sealed for BindMultiSelected<E, T, A, C> {}
+
+impl<E, T, A, C, OA> View<T, A> for BindMultiSelected<E, T, A, C>
+where
+    E: HtmlSelectElement<T, A>,
+    C: Fn(&mut T, Vec<String>) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = BindMultiSelectedState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            cx.add_attr_to_element(&"multiple".into(), &Some(AttributeValue::True));
+            let (child_id, child_state, element) = self.element.build(cx);
+            sync_options(element.as_node_ref(), &self.selected, None);
+            let listener = create_change_listener(element.as_node_ref(), cx);
+            let state = BindMultiSelectedState {
+                child_id,
+                child_state,
+                listener,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            cx.add_attr_to_element(&"multiple".into(), &Some(AttributeValue::True));
+            let mut changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            let node = element.as_node_ref();
+            if changed.contains(ChangeFlags::STRUCTURE) {
+                state.listener = create_change_listener(node, cx);
+            }
+            if !changed.is_empty() {
+                // The options may have changed, so check all of them
+                sync_options(node, &self.selected, None);
+            } else {
+                let changed_values = changed_values(&prev.selected, &self.selected);
+                if !changed_values.is_empty() {
+                    sync_options(node, &self.selected, Some(&changed_values));
+                    changed |= ChangeFlags::OTHER_CHANGE;
+                }
+            }
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.is::<web_sys::Event>() => {
+                let event = message.downcast::<web_sys::Event>().unwrap();
+                let Some(select) = event.target() else {
+                    return MessageResult::Nop;
+                };
+                let selected = selected_values(select.unchecked_ref());
+                match (self.on_change)(app_state, selected).action() {
+                    Some(action) => MessageResult::Action(action),
+                    None => MessageResult::Nop,
+                }
+            }
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlSelectElement,
+    BindMultiSelected,
+    vars: <C, OA,>,
+    vars_on_ty: <C,>,
+    bounds: {
+        C: Fn(&mut T, Vec<String>) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::changed_values;
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn only_changed_values_are_touched() {
+        let prev = values(&["a", "b"]);
+        let next = values(&["b", "c"]);
+        assert_eq!(changed_values(&prev, &next), ["a", "c"]);
+        assert!(changed_values(&next, &next).is_empty());
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn selecting_two_of_three_options() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{
+            elements::html,
+            interfaces::{Element, HtmlSelectElement as _},
+            test_util::mount,
+        };
+
+        fn picker(selected: &[String]) -> impl Element<Vec<String>> {
+            html::select(html::options([("a", "A"), ("b", "B"), ("c", "C")])).bind_multi_selected(
+                selected.to_vec(),
+                |state: &mut Vec<String>, selected| {
+                    *state = selected;
+                },
+            )
+        }
+
+        let mut harness = mount(vec![], picker(&[]));
+        let select: web_sys::HtmlSelectElement = harness.root_element().unchecked_into();
+        assert!(select.multiple());
+        for (option, selected) in super::options(&select).zip([true, false, true]) {
+            option.set_selected(selected);
+        }
+        harness.dispatch_event("select", &web_sys::Event::new("change").unwrap());
+        assert_eq!(*harness.app_state(), values(&["a", "c"]));
+
+        // the app state is reflected in the DOM on rebuild
+        harness.rebuild(picker(&values(&["b"])));
+        assert_eq!(super::selected_values(&select), values(&["b"]));
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reusing the elements of removed views, see [`Cx::set_node_pool_capacity`](crate::Cx::set_node_pool_capacity).
+
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+use crate::HTML_NS;
+
+/// HTML elements with state that isn't reflected in their attributes (e.g. the value of an `<input>`),
+/// which would leak into the view that reuses them.
+const NOT_POOLED: &[&str] = &[
+    "input", "textarea", "select", "option", "canvas", "audio", "video", "iframe",
+];
+
+/// Detached nodes by namespace and tag name, with at most `capacity` nodes per tag name.
+pub(crate) struct NodePool<N> {
+    capacity: usize,
+    entries: Vec<PoolEntry<N>>,
+}
+
+struct PoolEntry<N> {
+    ns: String,
+    name: String,
+    nodes: Vec<N>,
+}
+
+impl<N> Default for NodePool<N> {
+    fn default() -> Self {
+        NodePool {
+            capacity: 0,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<N> NodePool<N> {
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the number of nodes retained per tag name, nodes above it are dropped.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        for entry in &mut self.entries {
+            entry.nodes.truncate(capacity);
+        }
+        self.entries.retain(|entry| !entry.nodes.is_empty());
+    }
+
+    /// Retains `node` for reuse, returns `false` when the pool for its tag name is full.
+    pub(crate) fn release(&mut self, ns: &str, name: &str, node: N) -> bool {
+        let capacity = self.capacity;
+        if capacity == 0 {
+            return false;
+        }
+        match self.entry_mut(ns, name) {
+            Some(entry) if entry.nodes.len() >= capacity => false,
+            Some(entry) => {
+                entry.nodes.push(node);
+                true
+            }
+            None => {
+                self.entries.push(PoolEntry {
+                    ns: ns.to_owned(),
+                    name: name.to_owned(),
+                    nodes: vec![node],
+                });
+                true
+            }
+        }
+    }
+
+    /// Takes a retained node with the tag `name` in the namespace `ns` out of the pool.
+    pub(crate) fn take(&mut self, ns: &str, name: &str) -> Option<N> {
+        self.entry_mut(ns, name).and_then(|entry| entry.nodes.pop())
+    }
+
+    fn entry_mut(&mut self, ns: &str, name: &str) -> Option<&mut PoolEntry<N>> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.ns == ns && entry.name == name)
+    }
+}
+
+impl NodePool<web_sys::Element> {
+    /// Retains `node`, which was just removed from the DOM, if it's an element that can be reused.
+    pub(crate) fn release_node(&mut self, node: &web_sys::Node) {
+        let Some(element) = node.dyn_ref::<web_sys::Element>() else {
+            return;
+        };
+        let ns = element.namespace_uri().unwrap_or_default();
+        let name = element.local_name();
+        // custom elements may have internal state (e.g. a shadow root) as well
+        if name.contains('-') || (ns == HTML_NS && NOT_POOLED.contains(&name.as_str())) {
+            return;
+        }
+        self.release(&ns, &name, element.clone());
+    }
+
+    /// Takes an element out of the pool, with its attributes and children removed.
+    pub(crate) fn take_element(&mut self, ns: &str, name: &str) -> Option<web_sys::Element> {
+        let element = self.take(ns, name)?;
+        let attributes = element.attributes();
+        while let Some(attribute) = attributes.item(0) {
+            element.remove_attribute(&attribute.name()).unwrap_throw();
+        }
+        element.set_text_content(None);
+        Some(element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NodePool;
+    use crate::HTML_NS as NS;
+
+    #[test]
+    fn released_nodes_are_reused_by_tag_name() {
+        let mut pool = NodePool::default();
+        pool.set_capacity(4);
+        assert!(pool.release(NS, "li", 1));
+        assert!(pool.release(NS, "div", 2));
+        assert_eq!(pool.take(NS, "li"), Some(1));
+        assert_eq!(pool.take(NS, "li"), None);
+        assert_eq!(pool.take("http://www.w3.org/2000/svg", "div"), None);
+        assert_eq!(pool.take(NS, "div"), Some(2));
+    }
+
+    #[test]
+    fn pool_is_capped_per_tag_name() {
+        let mut pool = NodePool::default();
+        assert!(!pool.release(NS, "li", 0), "pooling is disabled by default");
+
+        pool.set_capacity(2);
+        assert!(pool.release(NS, "li", 1));
+        assert!(pool.release(NS, "li", 2));
+        assert!(!pool.release(NS, "li", 3));
+        assert!(pool.release(NS, "p", 4));
+
+        pool.set_capacity(1);
+        assert_eq!(pool.take(NS, "li"), Some(1));
+        assert_eq!(pool.take(NS, "li"), None);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Counting the work done by building and rebuilding the views, see [`Cx::perf_stats`](crate::Cx::perf_stats).
+
+use std::time::Duration;
+
+/// The work done by the last build or rebuild of the app (i.e. a frame), see [`Cx::perf_stats`](crate::Cx::perf_stats).
+///
+/// Only views with an element are counted, as that's where the cost of diffing is,
+/// e.g. the children of a memoized view that didn't change aren't rebuilt.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PerfStats {
+    /// The element views that were built, with a new element or one from the node pool.
+    pub views_built: usize,
+    /// The element views that were rebuilt.
+    pub views_rebuilt: usize,
+    /// The elements that were created, i.e. not taken from the node pool (text nodes aren't counted).
+    pub nodes_created: usize,
+    /// The nodes that were removed from their parent.
+    pub nodes_removed: usize,
+    /// The attribute, class and style writes to the DOM, including attribute writes that were
+    /// deferred to the next animation frame (see [`Cx::set_defer_attribute_writes`](crate::Cx::set_defer_attribute_writes)).
+    pub attribute_writes: usize,
+    /// How long the build or rebuild took.
+    pub frame_duration: Duration,
+}
+
+/// The counters of the current frame, and the stats of the last finished one.
+#[derive(Debug, Default)]
+pub(crate) struct PerfCounters {
+    pub(crate) current: PerfStats,
+    last_frame: PerfStats,
+    /// The `performance.now()` at the start of the current frame, in milliseconds.
+    frame_start: Option<f64>,
+}
+
+fn now() -> Option<f64> {
+    Some(web_sys::window()?.performance()?.now())
+}
+
+impl PerfCounters {
+    pub(crate) fn start_frame(&mut self) {
+        self.current = PerfStats::default();
+        self.frame_start = now();
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        if let (Some(start), Some(end)) = (self.frame_start.take(), now()) {
+            self.current.frame_duration = Duration::from_secs_f64((end - start).max(0.0) / 1000.0);
+        }
+        self.last_frame = self.current;
+    }
+
+    pub(crate) fn last_frame(&self) -> PerfStats {
+        self.last_frame
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn memoized_views_are_not_rebuilt() {
+        use crate::{elements::html, memoize, test_util::mount};
+
+        let view = |count: u32| {
+            html::div::<(), (), _>((
+                html::span(count.to_string()),
+                memoize((), |_| html::ul((html::li("a"), html::li("b")))),
+                (count > 0).then(|| html::p("more")),
+            ))
+        };
+        let mut harness = mount((), view(0));
+        let stats = harness.cx().perf_stats();
+        assert_eq!((stats.views_built, stats.views_rebuilt), (5, 0));
+
+        harness.rebuild(view(1));
+        let stats = harness.cx().perf_stats();
+        // The div and the span, the list is memoized
+        assert_eq!(stats.views_rebuilt, 2);
+        assert_eq!(stats.views_built, 1);
+        assert_eq!(stats.nodes_removed, 0);
+
+        harness.rebuild(view(0));
+        let stats = harness.cx().perf_stats();
+        assert_eq!((stats.views_built, stats.views_rebuilt), (0, 2));
+        assert_eq!(stats.nodes_removed, 1);
+    }
+}
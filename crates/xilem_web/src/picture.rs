@@ -0,0 +1,130 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A `<picture>` with a `<source>` for each art direction, see [`picture`].
+
+use std::borrow::Cow;
+
+use crate::{
+    elements::html,
+    interfaces::{Element, HtmlImageElement},
+    Attr,
+};
+
+type CowStr = Cow<'static, str>;
+
+/// A `<source>` of a [`picture`], i.e. the images in `srcset` that are used when `media` matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PictureSource {
+    srcset: CowStr,
+    media: Option<CowStr>,
+    sizes: Option<CowStr>,
+    mime_type: Option<CowStr>,
+}
+
+impl PictureSource {
+    /// A source with the image candidates `srcset` (e.g. `"wide.jpg 1x, wide@2x.jpg 2x"`)
+    /// that is used regardless of the media.
+    pub fn new(srcset: impl Into<CowStr>) -> Self {
+        PictureSource {
+            srcset: srcset.into(),
+            media: None,
+            sizes: None,
+            mime_type: None,
+        }
+    }
+
+    /// Use this source only when the media query `media` (e.g. `"(min-width: 800px)"`) matches.
+    pub fn media(mut self, media: impl Into<CowStr>) -> Self {
+        self.media = Some(media.into());
+        self
+    }
+
+    /// The `sizes` of the images, for width descriptors in `srcset` (e.g. `"100vw"`).
+    pub fn sizes(mut self, sizes: impl Into<CowStr>) -> Self {
+        self.sizes = Some(sizes.into());
+        self
+    }
+
+    /// The MIME type of the images (e.g. `"image/avif"`), the source is skipped by browsers
+    /// that don't support it.
+    pub fn mime_type(mut self, mime_type: impl Into<CowStr>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// The `<source>` view of a [`PictureSource`].
+pub type PictureSourceView<T, A = ()> =
+    Attr<Attr<Attr<Attr<html::Source<T, A, ()>, T, A>, T, A>, T, A>, T, A>;
+
+/// The view returned by [`picture`], with the `<img>` view `I`.
+pub type PictureView<I, T, A = ()> = html::Picture<T, A, (Vec<PictureSourceView<T, A>>, I)>;
+
+/// A `<picture>` with a `<source>` for each of `sources`, in their order, followed by the `<img>` `fallback`.
+///
+/// The browser uses the first source that matches, so sources with narrower media queries
+/// have to come first. The `<img>` is used when no source matches, and it actually displays
+/// the chosen image, so its `alt`, dimensions or load handlers apply to all sources.
+/// It's always the last child, as the spec requires.
+pub fn picture<T, A, I>(sources: &[PictureSource], fallback: I) -> PictureView<I, T, A>
+where
+    I: HtmlImageElement<T, A>,
+{
+    let sources = sources
+        .iter()
+        .map(|source| {
+            html::source(())
+                .attr("srcset", source.srcset.clone())
+                .attr("media", source.media.clone())
+                .attr("sizes", source.sizes.clone())
+                .attr("type", source.mime_type.clone())
+        })
+        .collect();
+    html::picture((sources, fallback))
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn sources_come_before_the_image() {
+        use super::{picture, PictureSource};
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        let sources = [
+            PictureSource::new("wide.jpg").media("(min-width: 800px)"),
+            PictureSource::new("medium.avif")
+                .media("(min-width: 400px)")
+                .mime_type("image/avif"),
+        ];
+        let fallback = html::img::<(), (), _>(()).attr("src", "narrow.jpg");
+        let harness = mount((), picture(&sources, fallback));
+
+        let picture = harness.root_element();
+        assert_eq!(picture.tag_name(), "PICTURE");
+        let children = picture.child_nodes();
+        let tag_names: Vec<_> = (0..children.length())
+            .map(|i| children.get(i).unwrap().node_name())
+            .collect();
+        assert_eq!(tag_names, ["SOURCE", "SOURCE", "IMG"]);
+
+        let source = |n: usize| {
+            picture
+                .query_selector(&format!("source:nth-child({n})"))
+                .unwrap()
+                .unwrap()
+        };
+        let first = source(1);
+        assert_eq!(
+            first.get_attribute("media").as_deref(),
+            Some("(min-width: 800px)")
+        );
+        assert!(!first.has_attribute("type"));
+        let second = source(2);
+        assert_eq!(
+            second.get_attribute("srcset").as_deref(),
+            Some("medium.avif")
+        );
+        assert_eq!(second.get_attribute("type").as_deref(), Some("image/avif"));
+    }
+}
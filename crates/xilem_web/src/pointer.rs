@@ -23,16 +23,29 @@ pub struct Pointer<V, T, A, F> {
 }
 
 pub struct PointerState<S> {
+    element: web_sys::Element,
     // Closures are retained so they can be called by environment
-    #[allow(unused)]
     down_closure: Closure<dyn FnMut(PointerEvent)>,
-    #[allow(unused)]
     move_closure: Closure<dyn FnMut(PointerEvent)>,
-    #[allow(unused)]
     up_closure: Closure<dyn FnMut(PointerEvent)>,
     child_state: S,
 }
 
+impl<S> Drop for PointerState<S> {
+    // The listeners are removed explicitly, as the element may outlive the view (e.g. when it's pooled)
+    fn drop(&mut self) {
+        for (event, closure) in [
+            ("pointerdown", &self.down_closure),
+            ("pointermove", &self.move_closure),
+            ("pointerup", &self.up_closure),
+        ] {
+            let _ = self
+                .element
+                .remove_event_listener_with_callback(event, closure.as_ref().unchecked_ref());
+        }
+    }
+}
+
 #[derive(Debug)]
 /// A message representing a pointer event.
 pub enum PointerMsg {
@@ -119,6 +132,7 @@ impl<T, A, F: Fn(&mut T, PointerMsg) -> A, V: View<T, A>> View<T, A> for Pointer
         el.add_event_listener_with_callback("pointerup", up_closure.as_ref().unchecked_ref())
             .unwrap();
         let state = PointerState {
+            element: el.clone(),
             down_closure,
             move_closure,
             up_closure,
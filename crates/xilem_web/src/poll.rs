@@ -0,0 +1,294 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Repeatedly running an async task on a timer, e.g. to poll an API.
+
+use std::{any::Any, cell::Cell, future::Future, marker::PhantomData, rc::Rc, time::Duration};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::MessageThunk, interfaces::sealed::Sealed, ChangeFlags, Cx, OptionalAction, View,
+    ViewMarker,
+};
+
+pub struct Poll<F, H, T, A = ()> {
+    interval: Duration,
+    fetch: F,
+    on_result: H,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct PollState {
+    schedule: PollSchedule,
+    thunk: Rc<MessageThunk>,
+    /// Set to `false` when the view is torn down, so that the results of in-flight fetches are discarded.
+    alive: Rc<Cell<bool>>,
+    /// The timer, which is `None` while the document is hidden.
+    timer: Option<Interval>,
+    #[allow(unused)]
+    visibility_listener: EventListener,
+}
+
+impl Drop for PollState {
+    fn drop(&mut self) {
+        self.alive.set(false);
+    }
+}
+
+/// Run the async `fetch` every `interval`, and pass its results to `on_result`.
+///
+/// The first fetch is started when the view is built. While the document is hidden
+/// (e.g. the tab is in the background), the timer is paused, and a fetch is started
+/// as soon as the document is visible again.
+///
+/// When a fetch takes longer than `interval`, the ticks of the timer in the meantime are dropped,
+/// i.e. there's at most one fetch in flight, and the next one starts with the first tick after it finished.
+///
+/// When the view is removed, the timer is cleared, and the result of an in-flight fetch is discarded.
+///
+/// The view itself is rendered as an empty text node.
+pub fn poll<F, Fut, H, T, A, OA>(interval: Duration, fetch: F, on_result: H) -> Poll<F, H, T, A>
+where
+    F: Fn() -> Fut,
+    Fut: Future + 'static,
+    H: Fn(&mut T, Fut::Output) -> OA,
+    OA: OptionalAction<A>,
+{
+    Poll {
+        interval,
+        fetch,
+        on_result,
+        phantom: PhantomData,
+    }
+}
+
+/// Sent by the timer of a [`Poll`].
+struct Tick;
+
+/// Sent when the visibility of the document changes.
+struct VisibilityChanged;
+
+/// The result of the fetch of a [`Poll`].
+struct Fetched<R>(R);
+
+/// Decides whether a [`Poll`] starts a fetch when its timer ticks.
+#[derive(Debug, Default)]
+struct PollSchedule {
+    in_flight: bool,
+    hidden: bool,
+}
+
+impl PollSchedule {
+    /// Whether to start a fetch, which is the case when the document is visible, and no fetch is in flight.
+    fn tick(&mut self) -> bool {
+        if self.in_flight || self.hidden {
+            return false;
+        }
+        self.in_flight = true;
+        true
+    }
+
+    /// The fetch in flight has finished.
+    fn finish(&mut self) {
+        self.in_flight = false;
+    }
+}
+
+/// A timer that calls `callback` repeatedly, until it's dropped.
+struct Interval {
+    handle: i32,
+    #[allow(unused)]
+    callback: Closure<dyn FnMut()>,
+}
+
+impl Interval {
+    fn new(period: Duration, callback: impl FnMut() + 'static) -> Self {
+        let callback = Closure::<dyn FnMut()>::new(callback);
+        let millis = i32::try_from(period.as_millis()).unwrap_or(i32::MAX);
+        let handle = web_sys::window()
+            .unwrap_throw()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                callback.as_ref().unchecked_ref(),
+                millis,
+            )
+            .unwrap_throw();
+        Interval { handle, callback }
+    }
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            window.clear_interval_with_handle(self.handle);
+        }
+    }
+}
+
+fn start_timer(interval: Duration, thunk: &Rc<MessageThunk>) -> Interval {
+    let thunk = thunk.clone();
+    Interval::new(interval, move || thunk.push_message(Tick))
+}
+
+impl<F, Fut, H, T, A, OA> Poll<F, H, T, A>
+where
+    F: Fn() -> Fut,
+    Fut: Future + 'static,
+    H: Fn(&mut T, Fut::Output) -> OA,
+    OA: OptionalAction<A>,
+{
+    fn start_fetch(&self, state: &mut PollState) {
+        if !state.schedule.tick() {
+            return;
+        }
+        let future = (self.fetch)();
+        let thunk = state.thunk.clone();
+        let alive = state.alive.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = future.await;
+            if alive.get() {
+                thunk.push_message(Fetched(result));
+            }
+        });
+    }
+}
+
+impl<F, H, T, A> ViewMarker for Poll<F, H, T, A> {}
+impl<F, H, T, A> Sealed for Poll<F, H, T, A> {}
+
+impl<F, Fut, H, T, A, OA> View<T, A> for Poll<F, H, T, A>
+where
+    F: Fn() -> Fut,
+    Fut: Future + 'static,
+    H: Fn(&mut T, Fut::Output) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = PollState;
+    type Element = web_sys::Text;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, thunk) = cx.with_new_id(|cx| {
+            // The first fetch is started via the first tick
+            cx.message_self(Box::new(Tick));
+            Rc::new(cx.message_thunk())
+        });
+        let hidden = cx.document().hidden();
+        let visibility_listener = {
+            let thunk = thunk.clone();
+            EventListener::new(cx.document(), "visibilitychange", move |_| {
+                thunk.push_message(VisibilityChanged);
+            })
+        };
+        let state = PollState {
+            schedule: PollSchedule {
+                in_flight: false,
+                hidden,
+            },
+            timer: (!hidden).then(|| start_timer(self.interval, &thunk)),
+            thunk,
+            alive: Rc::new(Cell::new(true)),
+            visibility_listener,
+        };
+        let element = web_sys::Text::new().unwrap_throw();
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        prev: &Self,
+        _id: &mut Id,
+        state: &mut Self::State,
+        _element: &mut Self::Element,
+    ) -> ChangeFlags {
+        if prev.interval != self.interval && state.timer.is_some() {
+            state.timer = Some(start_timer(self.interval, &state.thunk));
+        }
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        if !id_path.is_empty() {
+            return MessageResult::Stale(message);
+        }
+        if message.is::<Tick>() {
+            self.start_fetch(state);
+            MessageResult::Nop
+        } else if message.is::<VisibilityChanged>() {
+            let hidden = crate::document().hidden();
+            state.schedule.hidden = hidden;
+            if hidden {
+                state.timer = None;
+            } else if state.timer.is_none() {
+                state.timer = Some(start_timer(self.interval, &state.thunk));
+                self.start_fetch(state);
+            }
+            MessageResult::Nop
+        } else {
+            match message.downcast::<Fetched<Fut::Output>>() {
+                Ok(result) => {
+                    state.schedule.finish();
+                    match (self.on_result)(app_state, result.0).action() {
+                        Some(action) => MessageResult::Action(action),
+                        None => MessageResult::Nop,
+                    }
+                }
+                Err(message) => MessageResult::Stale(message),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PollSchedule;
+
+    /// Advances a timer by `ticks` ticks, where each fetch takes `fetch_duration` ticks,
+    /// returns the ticks at which fetches were started.
+    fn run_timer(schedule: &mut PollSchedule, ticks: u32, fetch_duration: f64) -> Vec<u32> {
+        let mut started = Vec::new();
+        let mut finishes_at = None;
+        for tick in 0..ticks {
+            if finishes_at.is_some_and(|finish| finish <= tick as f64) {
+                schedule.finish();
+                finishes_at = None;
+            }
+            if schedule.tick() {
+                started.push(tick);
+                finishes_at = Some(tick as f64 + fetch_duration);
+            }
+        }
+        started
+    }
+
+    #[test]
+    fn fetches_repeatedly() {
+        let mut schedule = PollSchedule::default();
+        assert_eq!(run_timer(&mut schedule, 5, 0.5), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn overlapping_fetches_are_dropped() {
+        let mut schedule = PollSchedule::default();
+        assert_eq!(run_timer(&mut schedule, 10, 2.5), [0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn hidden_document_pauses_fetches() {
+        let mut schedule = PollSchedule {
+            in_flight: false,
+            hidden: true,
+        };
+        assert!(run_timer(&mut schedule, 5, 0.5).is_empty());
+        schedule.hidden = false;
+        assert_eq!(run_timer(&mut schedule, 2, 0.5), [0, 1]);
+    }
+}
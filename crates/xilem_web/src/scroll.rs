@@ -0,0 +1,206 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scroll offsets that are throttled to animation frames, see [`Element::on_scroll`].
+
+use std::{any::Any, cell::Cell, marker::PhantomData, rc::Rc};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, Element},
+    view::DomNode,
+    ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Calls a handler with the scroll offsets of an element, at most once per animation frame,
+/// see [`Element::on_scroll`].
+pub struct OnScroll<E, T, A, F> {
+    element: E,
+    handler: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, F> OnScroll<E, T, A, F> {
+    pub(crate) fn new(element: E, handler: F) -> Self {
+        OnScroll {
+            element,
+            handler,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct OnScrollState<S> {
+    child_id: Id,
+    child_state: S,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// The `scrollTop` and `scrollLeft` of the element in the animation frame after it was scrolled.
+struct Scrolled {
+    top: f64,
+    left: f64,
+}
+
+/// The offsets are read in the animation frame, so all `scroll` events until then
+/// (e.g. of the momentum of a smooth scroll) result in one message with the latest offsets.
+fn create_scroll_listener(node: &web_sys::Node, cx: &Cx) -> EventListener {
+    let thunk = Rc::new(cx.message_thunk());
+    let frame_requested = Rc::new(Cell::new(false));
+    let element: web_sys::Element = node.clone().unchecked_into();
+    EventListener::new(node, "scroll", move |_| {
+        if frame_requested.replace(true) {
+            return;
+        }
+        let callback = Closure::once_into_js({
+            let thunk = thunk.clone();
+            let frame_requested = frame_requested.clone();
+            let element = element.clone();
+            move || {
+                frame_requested.set(false);
+                thunk.push_message(Scrolled {
+                    top: element.scroll_top().into(),
+                    left: element.scroll_left().into(),
+                });
+            }
+        });
+        web_sys::window()
+            .unwrap_throw()
+            .request_animation_frame(callback.unchecked_ref())
+            .unwrap_throw();
+    })
+}
+
+impl<E, T, A, F> ViewMarker for OnScroll<E, T, A, F> {}
+impl<E, T, A, F> Sealed for OnScroll<E, T, A, F> {}
+
+impl<E, T, A, F, OA> View<T, A> for OnScroll<E, T, A, F>
+where
+    E: Element<T, A>,
+    F: Fn(&mut T, f64, f64) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = OnScrollState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            let (child_id, child_state, element) = self.element.build(cx);
+            let listener = create_scroll_listener(element.as_node_ref(), cx);
+            let state = OnScrollState {
+                child_id,
+                child_state,
+                listener,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            if changed.contains(ChangeFlags::STRUCTURE) {
+                state.listener = create_scroll_listener(element.as_node_ref(), cx);
+            }
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] => match message.downcast::<Scrolled>() {
+                Ok(scrolled) => {
+                    match (self.handler)(app_state, scrolled.top, scrolled.left).action() {
+                        Some(action) => MessageResult::Action(action),
+                        None => MessageResult::Nop,
+                    }
+                }
+                Err(message) => MessageResult::Stale(message),
+            },
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    Element,
+    OnScroll,
+    vars: <F, OA,>,
+    vars_on_ty: <F,>,
+    bounds: {
+        F: Fn(&mut T, f64, f64) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn scroll_events_are_coalesced_per_frame() {
+        use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+        async fn next_frame() {
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                web_sys::window()
+                    .unwrap()
+                    .request_animation_frame(&resolve)
+                    .unwrap();
+            });
+            wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+        }
+
+        let view = html::div(html::div::<_, (), _>(()).attr("style", "height: 1000px"))
+            .attr("style", "height: 100px; overflow: auto")
+            .on_scroll(|offsets: &mut Vec<(f64, f64)>, top, left| offsets.push((top, left)));
+        let mut harness = mount(vec![], view);
+        let scroller = harness.root_element();
+        // the offsets can only be changed when the element is laid out
+        let container = scroller.parent_node().unwrap();
+        crate::document_body().append_child(&container).unwrap();
+
+        // e.g. the momentum of a smooth scroll
+        let scroll = || web_sys::Event::new("scroll").unwrap();
+        for top in [10, 20, 30] {
+            scroller.set_scroll_top(top);
+            harness.dispatch_event("div", &scroll());
+        }
+        assert!(harness.app_state().is_empty());
+
+        next_frame().await;
+        harness.handle_messages();
+        assert_eq!(*harness.app_state(), [(30.0, 0.0)]);
+        container
+            .parent_node()
+            .unwrap()
+            .remove_child(&container)
+            .unwrap();
+    }
+}
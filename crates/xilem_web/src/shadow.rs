@@ -0,0 +1,191 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The shadow DOM of a custom element, see [`CustomElement::shadow`](crate::elements::CustomElement::shadow).
+
+use std::{any::Any, marker::PhantomData};
+
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    elements::ChildrenSplice,
+    interfaces::{sealed::Sealed, HtmlElement},
+    view::DomNode,
+    ChangeFlags, Cx, Pod, View, ViewMarker, ViewSequence,
+};
+
+/// An element with the children of a shadow root, see [`CustomElement::shadow`](crate::elements::CustomElement::shadow).
+pub struct Shadow<E, T, A, Children> {
+    element: E,
+    mode: web_sys::ShadowRootMode,
+    children: Children,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, Children> Shadow<E, T, A, Children> {
+    pub(crate) fn new(element: E, mode: web_sys::ShadowRootMode, children: Children) -> Self {
+        Shadow {
+            element,
+            mode,
+            children,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct ShadowState<S, CS> {
+    child_id: Id,
+    child_state: S,
+    shadow_root: web_sys::ShadowRoot,
+    children_states: CS,
+    child_elements: Vec<Pod>,
+    scratch: Vec<Pod>,
+}
+
+fn attach_shadow(element: &web_sys::Node, mode: web_sys::ShadowRootMode) -> web_sys::ShadowRoot {
+    let element: &web_sys::Element = element.unchecked_ref();
+    element
+        .attach_shadow(&web_sys::ShadowRootInit::new(mode))
+        .expect_throw(
+            "only autonomous custom elements and some built-in elements can have a shadow root",
+        )
+}
+
+impl<E, T, A, Children> ViewMarker for Shadow<E, T, A, Children> {}
+impl<E, T, A, Children> Sealed for Shadow<E, T, A, Children> {}
+
+impl<E, T, A, Children> View<T, A> for Shadow<E, T, A, Children>
+where
+    E: HtmlElement<T, A>,
+    Children: ViewSequence<T, A>,
+{
+    type State = ShadowState<E::State, Children::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            let (child_id, child_state, element) = self.element.build(cx);
+            let shadow_root = attach_shadow(element.as_node_ref(), self.mode);
+            let mut child_elements = vec![];
+            let mut scratch = vec![];
+            let mut splice = ChildrenSplice::new(&mut child_elements, &mut scratch, &shadow_root);
+            let children_states = self.children.build(cx, &mut splice);
+            let state = ShadowState {
+                child_id,
+                child_state,
+                shadow_root,
+                children_states,
+                child_elements,
+                scratch,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            debug_assert!(
+                prev.mode == self.mode,
+                "the mode of a shadow root can't be changed after it was attached"
+            );
+            let changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            let host = state.shadow_root.host();
+            if AsRef::<web_sys::Node>::as_ref(&host) != element.as_node_ref() {
+                // The element was recreated (e.g. with another tag name), it needs a new shadow root
+                let shadow_root = attach_shadow(element.as_node_ref(), state.shadow_root.mode());
+                while let Some(child) = state.shadow_root.first_child() {
+                    shadow_root.append_child(&child).unwrap_throw();
+                }
+                state.shadow_root = shadow_root;
+            }
+            let mut splice = ChildrenSplice::new(
+                &mut state.child_elements,
+                &mut state.scratch,
+                &state.shadow_root,
+            );
+            changed
+                | self
+                    .children
+                    .rebuild(cx, &prev.children, &mut state.children_states, &mut splice)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => self
+                .children
+                .message(id_path, &mut state.children_states, message, app_state),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlElement,
+    Shadow,
+    vars: <Children,>,
+    vars_on_ty: <Children,>,
+    bounds: {
+        Children: ViewSequence<T, A>,
+    }
+);
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn children_are_mounted_in_the_shadow_root() {
+        use web_sys::ShadowRootMode;
+
+        use crate::{
+            elements::{custom_element, html, slotted},
+            interfaces::Element as _,
+            test_util::mount,
+        };
+
+        let view = |title: &'static str| {
+            custom_element::<(), (), _>("x-card", slotted("title", html::span(title))).shadow(
+                ShadowRootMode::Open,
+                (
+                    html::p("Shadow content"),
+                    html::h2(html::slot(()).attr("name", "title")),
+                ),
+            )
+        };
+        let mut harness = mount((), view("Hello"));
+        let host = harness.root_element();
+        let shadow_root = host.shadow_root().unwrap();
+        assert_eq!(shadow_root.child_element_count(), 2);
+        assert!(shadow_root.query_selector("h2 > slot").unwrap().is_some());
+        // the slotted child stays in the light DOM
+        assert_eq!(host.child_element_count(), 1);
+        assert_eq!(host.text_content().as_deref(), Some("Hello"));
+
+        harness.rebuild(view("World"));
+        assert_eq!(host.text_content().as_deref(), Some("World"));
+        assert_eq!(shadow_root.child_element_count(), 2);
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Showing or hiding a whole group of children without a wrapper element, see [`show_if`].
+
+use std::any::Any;
+
+use xilem_core::{Id, MessageResult};
+
+use crate::{ChangeFlags, Cx, ElementsSplice, ViewSequence};
+
+/// The view sequence returned by [`show_if`].
+pub struct ShowIf<VT> {
+    shown: bool,
+    seq: VT,
+}
+
+/// The elements of the sequence `seq` while `shown` is `true`, and nothing otherwise.
+///
+/// Unlike wrapping each child in an `Option`, the children appear and disappear together,
+/// and no wrapper element (which could affect the styling) is needed.
+/// While hidden, a marker (an empty comment node) keeps the position of the group among its siblings.
+///
+/// The state of the children is discarded when they're hidden, so e.g. the value of an
+/// uncontrolled input is lost, and they're built anew when they're shown again.
+/// Use [`lazy`](crate::lazy) with [`keep_state`](crate::Lazy::keep_state) to keep the state of a single view.
+pub fn show_if<VT>(shown: bool, seq: VT) -> ShowIf<VT> {
+    ShowIf { shown, seq }
+}
+
+impl<T, A, VT: ViewSequence<T, A>> ViewSequence<T, A> for ShowIf<VT> {
+    /// `None` while hidden, i.e. when the marker is the only element.
+    type State = Option<VT::State>;
+
+    fn build(&self, cx: &mut Cx, elements: &mut dyn ElementsSplice) -> Self::State {
+        if self.shown {
+            Some(self.seq.build(cx, elements))
+        } else {
+            elements.push(cx.create_marker(), cx);
+            None
+        }
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        state: &mut Self::State,
+        elements: &mut dyn ElementsSplice,
+    ) -> ChangeFlags {
+        match (self.shown, &mut *state) {
+            (true, Some(seq_state)) => self.seq.rebuild(cx, &prev.seq, seq_state, elements),
+            (true, None) => {
+                elements.delete(1, cx);
+                *state = Some(self.seq.build(cx, elements));
+                ChangeFlags::tree_structure()
+            }
+            (false, Some(seq_state)) => {
+                elements.delete(prev.seq.count(seq_state), cx);
+                elements.push(cx.create_marker(), cx);
+                *state = None;
+                ChangeFlags::tree_structure()
+            }
+            (false, None) => {
+                // The marker is left untouched
+                elements.mutate(cx);
+                elements.mark(ChangeFlags::empty(), cx)
+            }
+        }
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match state {
+            Some(seq_state) => self.seq.message(id_path, seq_state, message, app_state),
+            None => MessageResult::Stale(message),
+        }
+    }
+
+    fn count(&self, state: &Self::State) -> usize {
+        match state {
+            Some(seq_state) => self.seq.count(seq_state),
+            None => 1,
+        }
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn group_is_shown_and_hidden_together() {
+        use super::show_if;
+        use crate::{elements::html, test_util::mount};
+
+        let view = |shown: bool| {
+            html::div::<(), (), _>((
+                html::h2("Title"),
+                show_if(shown, (html::p("a"), html::p("b"), html::p("c"))),
+                html::footer("Footer"),
+            ))
+        };
+        let mut harness = mount((), view(false));
+        let div = harness.root_element();
+        let paragraphs = || div.query_selector_all("p").unwrap().length();
+        assert_eq!(paragraphs(), 0);
+        // the marker keeps the position of the group
+        assert_eq!(div.child_nodes().length(), 3);
+
+        harness.rebuild(view(true));
+        assert_eq!(paragraphs(), 3);
+        assert_eq!(div.text_content().as_deref(), Some("TitleabcFooter"));
+        assert_eq!(div.child_nodes().length(), 5);
+
+        harness.rebuild(view(true));
+        assert_eq!(paragraphs(), 3);
+
+        harness.rebuild(view(false));
+        assert_eq!(paragraphs(), 0);
+        assert_eq!(div.text_content().as_deref(), Some("TitleFooter"));
+        assert_eq!(div.last_element_child().unwrap().tag_name(), "FOOTER");
+
+        harness.rebuild(view(true));
+        assert_eq!(div.text_content().as_deref(), Some("TitleabcFooter"));
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Binding the value of an `<input type="range">` to the app state.
+
+use std::{any::Any, marker::PhantomData};
+
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, HtmlInputElement},
+    view::DomNode,
+    AttributeValue, ChangeFlags, Cx, OptionalAction, View, ViewMarker,
+};
+
+/// Binds the value of a range slider, see [`HtmlInputElement::slider`].
+pub struct Slider<E, T, A, C> {
+    element: E,
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    on_change: C,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A, C> Slider<E, T, A, C> {
+    pub(crate) fn new(element: E, value: f64, min: f64, max: f64, step: f64, on_change: C) -> Self {
+        Slider {
+            element,
+            value,
+            min,
+            max,
+            step,
+            on_change,
+            phantom: PhantomData,
+        }
+    }
+
+    fn add_range_attrs(&self, cx: &mut Cx) {
+        cx.add_attr_to_element(
+            &"type".into(),
+            &Some(AttributeValue::String("range".into())),
+        );
+        cx.add_attr_to_element(&"min".into(), &Some(AttributeValue::F64(self.min)));
+        cx.add_attr_to_element(&"max".into(), &Some(AttributeValue::F64(self.max)));
+        cx.add_attr_to_element(&"step".into(), &Some(AttributeValue::F64(self.step)));
+    }
+
+    fn clamped_value(&self) -> f64 {
+        clamp_to_range(self.value, self.min, self.max)
+    }
+}
+
+pub struct SliderState<S> {
+    child_id: Id,
+    child_state: S,
+    #[allow(unused)]
+    listener: EventListener,
+}
+
+/// Clamps `value` like the browser does, i.e. a `max` below `min` is treated as `min`,
+/// and `NaN` (e.g. an invalid value) becomes `min`.
+fn clamp_to_range(value: f64, min: f64, max: f64) -> f64 {
+    if value.is_nan() {
+        return min;
+    }
+    value.max(min).min(max.max(min))
+}
+
+/// Set the `valueAsNumber` of the `<input>` to `value`, unless it already has that value.
+fn sync_value(input: &web_sys::Node, value: f64) -> bool {
+    let input = input.unchecked_ref::<web_sys::HtmlInputElement>();
+    if input.value_as_number() == value {
+        return false;
+    }
+    input.set_value_as_number(value);
+    true
+}
+
+fn create_input_listener(element: &web_sys::Node, cx: &Cx) -> EventListener {
+    let thunk = cx.message_thunk();
+    EventListener::new(element, "input", move |event| {
+        thunk.push_message(event.clone());
+    })
+}
+
+impl<E, T, A, C> ViewMarker for Slider<E, T, A, C> {}
+impl<E, T, A, C> Sealed for Slider<E, T, A, C> {}
+
+impl<E, T, A, C, OA> View<T, A> for Slider<E, T, A, C>
+where
+    E: HtmlInputElement<T, A>,
+    C: Fn(&mut T, f64) -> OA,
+    OA: OptionalAction<A>,
+{
+    type State = SliderState<E::State>;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (element, state)) = cx.with_new_id(|cx| {
+            self.add_range_attrs(cx);
+            let (child_id, child_state, element) = self.element.build(cx);
+            sync_value(element.as_node_ref(), self.clamped_value());
+            let listener = create_input_listener(element.as_node_ref(), cx);
+            let state = SliderState {
+                child_id,
+                child_state,
+                listener,
+            };
+            (element, state)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            self.add_range_attrs(cx);
+            let mut changed = self.element.rebuild(
+                cx,
+                &prev.element,
+                &mut state.child_id,
+                &mut state.child_state,
+                element,
+            );
+            if changed.contains(ChangeFlags::STRUCTURE) {
+                state.listener = create_input_listener(element.as_node_ref(), cx);
+            }
+            // The bounds are applied before, so that the browser doesn't clamp the value to the old ones
+            if sync_value(element.as_node_ref(), self.clamped_value()) {
+                changed |= ChangeFlags::OTHER_CHANGE;
+            }
+            changed
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.is::<web_sys::Event>() => {
+                let event = message.downcast::<web_sys::Event>().unwrap();
+                let Some(input) = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                else {
+                    return MessageResult::Nop;
+                };
+                let value = clamp_to_range(input.value_as_number(), self.min, self.max);
+                match (self.on_change)(app_state, value).action() {
+                    Some(action) => MessageResult::Action(action),
+                    None => MessageResult::Nop,
+                }
+            }
+            [child_id, rest_path @ ..] if *child_id == state.child_id => {
+                self.element
+                    .message(rest_path, &mut state.child_state, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(
+    HtmlInputElement,
+    Slider,
+    vars: <C, OA,>,
+    vars_on_ty: <C,>,
+    bounds: {
+        C: Fn(&mut T, f64) -> OA,
+        OA: OptionalAction<A>,
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::clamp_to_range;
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        assert_eq!(clamp_to_range(150.0, 0.0, 100.0), 100.0);
+        assert_eq!(clamp_to_range(-5.0, 0.0, 100.0), 0.0);
+        assert_eq!(clamp_to_range(42.0, 0.0, 100.0), 42.0);
+        assert_eq!(clamp_to_range(f64::NAN, 10.0, 100.0), 10.0);
+        // a `max` below `min` is ignored
+        assert_eq!(clamp_to_range(5.0, 10.0, 0.0), 10.0);
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "testing"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn dragging_the_slider_updates_state() {
+        use wasm_bindgen::JsCast;
+
+        use crate::{
+            elements::html,
+            interfaces::{Element, HtmlInputElement as _},
+            test_util::mount,
+        };
+
+        fn volume(value: f64) -> impl Element<f64> {
+            html::input(()).slider(value, 0.0, 10.0, 0.5, |state: &mut f64, value| {
+                *state = value;
+            })
+        }
+
+        // the bound value is clamped to the bounds
+        let mut harness = mount(20.0, volume(20.0));
+        let input: web_sys::HtmlInputElement = harness.root_element().unchecked_into();
+        assert_eq!(input.type_(), "range");
+        assert_eq!(input.value_as_number(), 10.0);
+
+        input.set_value_as_number(2.5);
+        harness.dispatch_event("input", &web_sys::Event::new("input").unwrap());
+        assert_eq!(*harness.app_state(), 2.5);
+
+        // the browser clamps values above `max`
+        input.set_value("42");
+        harness.dispatch_event("input", &web_sys::Event::new("input").unwrap());
+        assert_eq!(*harness.app_state(), 10.0);
+
+        // the app state is reflected in the DOM on rebuild
+        harness.rebuild(volume(4.0));
+        assert_eq!(input.value_as_number(), 4.0);
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persisting small pieces of state (e.g. the theme or a draft) in the web storage, see [`Cx::storage`](crate::Cx::storage).
+
+use std::fmt;
+
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Which web storage to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    /// `localStorage`, which is kept across sessions.
+    Local,
+    /// `sessionStorage`, which is cleared when the page session ends (e.g. the tab is closed).
+    Session,
+}
+
+/// An error of the web storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    /// The storage can't be accessed, e.g. because it's disabled (in some private browsing modes), or not supported.
+    Unavailable,
+    /// The value couldn't be stored, as the storage is full.
+    QuotaExceeded,
+    /// Any other exception thrown by the storage, with its message.
+    Other(String),
+}
+
+impl StorageError {
+    /// The error for a `DOMException` with `name` (e.g. `"QuotaExceededError"`) and `message`.
+    fn from_exception(name: &str, message: String) -> Self {
+        match name {
+            // Older Firefox versions use their own name
+            "QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED" => StorageError::QuotaExceeded,
+            "SecurityError" => StorageError::Unavailable,
+            _ => StorageError::Other(message),
+        }
+    }
+
+    fn from_js(err: JsValue) -> Self {
+        match err.dyn_ref::<web_sys::DomException>() {
+            Some(exception) => StorageError::from_exception(&exception.name(), exception.message()),
+            None => StorageError::Other(err.as_string().unwrap_or_else(|| format!("{err:?}"))),
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Unavailable => f.write_str("the web storage is unavailable"),
+            StorageError::QuotaExceeded => f.write_str("the quota of the web storage is exceeded"),
+            StorageError::Other(message) => write!(f, "web storage error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// A handle to a web storage, see [`Cx::storage`](crate::Cx::storage).
+///
+/// Every operation returns an error when the storage itself is unavailable.
+#[derive(Debug, Clone)]
+pub struct WebStorage {
+    storage: Result<web_sys::Storage, StorageError>,
+}
+
+impl WebStorage {
+    pub(crate) fn new(window: &web_sys::Window, kind: StorageKind) -> Self {
+        let storage = match kind {
+            StorageKind::Local => window.local_storage(),
+            StorageKind::Session => window.session_storage(),
+        };
+        let storage = storage
+            .map_err(StorageError::from_js)
+            .and_then(|storage| storage.ok_or(StorageError::Unavailable));
+        WebStorage { storage }
+    }
+
+    fn storage(&self) -> Result<&web_sys::Storage, StorageError> {
+        self.storage.as_ref().map_err(Clone::clone)
+    }
+
+    /// The value stored for `key`, if any.
+    pub fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        self.storage()?.get_item(key).map_err(StorageError::from_js)
+    }
+
+    /// Store `value` for `key`, fails with [`StorageError::QuotaExceeded`] when the storage is full.
+    pub fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.storage()?
+            .set_item(key, value)
+            .map_err(StorageError::from_js)
+    }
+
+    /// Remove the value stored for `key`, removing a key that isn't stored is not an error.
+    pub fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.storage()?
+            .remove_item(key)
+            .map_err(StorageError::from_js)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageError;
+
+    #[test]
+    fn exceptions_are_classified() {
+        let error = |name| StorageError::from_exception(name, "message".into());
+        assert_eq!(error("QuotaExceededError"), StorageError::QuotaExceeded);
+        assert_eq!(
+            error("NS_ERROR_DOM_QUOTA_REACHED"),
+            StorageError::QuotaExceeded
+        );
+        assert_eq!(error("SecurityError"), StorageError::Unavailable);
+        assert_eq!(
+            error("InvalidStateError"),
+            StorageError::Other("message".into())
+        );
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn values_round_trip_until_the_quota_is_exceeded() {
+        use super::StorageKind;
+        use crate::Cx;
+
+        let storage = Cx::new().storage(StorageKind::Session);
+        storage.set("xilem-test-draft", "Hello").unwrap();
+        assert_eq!(
+            storage.get("xilem-test-draft").unwrap().as_deref(),
+            Some("Hello")
+        );
+        storage.remove("xilem-test-draft").unwrap();
+        assert_eq!(storage.get("xilem-test-draft").unwrap(), None);
+
+        // browsers allow about 5 MB per origin
+        let too_large = "x".repeat(16 * 1024 * 1024);
+        assert_eq!(
+            storage.set("xilem-test-draft", &too_large),
+            Err(StorageError::QuotaExceeded)
+        );
+        assert_eq!(storage.get("xilem-test-draft").unwrap(), None);
+    }
+}
@@ -3,7 +3,9 @@
 
 pub(crate) mod common_attrs;
 pub(crate) mod kurbo_shape;
+pub(crate) mod svg_element;
 
 pub use common_attrs::{fill, stroke, Fill, Stroke};
 pub use peniko;
 pub use peniko::kurbo;
+pub use svg_element::{AspectRatioAlign, MeetOrSlice, PreserveAspectRatio};
@@ -0,0 +1,104 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed attribute values for the root `<svg>` element.
+
+use std::fmt;
+
+/// The alignment part of the `preserveAspectRatio` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AspectRatioAlign {
+    /// Scale the content non-uniformly, so that it fills the viewport exactly.
+    None,
+    XMinYMin,
+    XMidYMin,
+    XMaxYMin,
+    XMinYMid,
+    #[default]
+    XMidYMid,
+    XMaxYMid,
+    XMinYMax,
+    XMidYMax,
+    XMaxYMax,
+}
+
+/// Whether the content should be scaled to fit (`meet`) or to cover (`slice`) the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MeetOrSlice {
+    #[default]
+    Meet,
+    Slice,
+}
+
+/// The value of the `preserveAspectRatio` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PreserveAspectRatio {
+    pub align: AspectRatioAlign,
+    pub meet_or_slice: MeetOrSlice,
+}
+
+impl PreserveAspectRatio {
+    pub fn new(align: AspectRatioAlign, meet_or_slice: MeetOrSlice) -> Self {
+        Self {
+            align,
+            meet_or_slice,
+        }
+    }
+}
+
+impl fmt::Display for PreserveAspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let align = match self.align {
+            // `meet`/`slice` is ignored when the content isn't scaled uniformly
+            AspectRatioAlign::None => return f.write_str("none"),
+            AspectRatioAlign::XMinYMin => "xMinYMin",
+            AspectRatioAlign::XMidYMin => "xMidYMin",
+            AspectRatioAlign::XMaxYMin => "xMaxYMin",
+            AspectRatioAlign::XMinYMid => "xMinYMid",
+            AspectRatioAlign::XMidYMid => "xMidYMid",
+            AspectRatioAlign::XMaxYMid => "xMaxYMid",
+            AspectRatioAlign::XMinYMax => "xMinYMax",
+            AspectRatioAlign::XMidYMax => "xMidYMax",
+            AspectRatioAlign::XMaxYMax => "xMaxYMax",
+        };
+        let meet_or_slice = match self.meet_or_slice {
+            MeetOrSlice::Meet => "meet",
+            MeetOrSlice::Slice => "slice",
+        };
+        write!(f, "{align} {meet_or_slice}")
+    }
+}
+
+/// Serializes the value of the `viewBox` attribute.
+///
+/// Numbers are formatted independently of the locale, with `.` as decimal separator.
+pub(crate) fn view_box_value(min_x: f64, min_y: f64, width: f64, height: f64) -> String {
+    format!("{min_x} {min_y} {width} {height}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn view_box_serialization() {
+        assert_eq!(view_box_value(0.0, 0.0, 100.0, 50.0), "0 0 100 50");
+        assert_eq!(
+            view_box_value(-0.5, 1.25, 10.5, 0.125),
+            "-0.5 1.25 10.5 0.125"
+        );
+    }
+
+    #[test]
+    fn preserve_aspect_ratio_serialization() {
+        assert_eq!(PreserveAspectRatio::default().to_string(), "xMidYMid meet");
+        assert_eq!(
+            PreserveAspectRatio::new(AspectRatioAlign::XMinYMax, MeetOrSlice::Slice).to_string(),
+            "xMinYMax slice"
+        );
+        assert_eq!(
+            PreserveAspectRatio::new(AspectRatioAlign::None, MeetOrSlice::Slice).to_string(),
+            "none"
+        );
+    }
+}
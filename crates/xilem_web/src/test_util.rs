@@ -0,0 +1,227 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for testing views in a browser (e.g. with `wasm-bindgen-test`).
+//!
+//! ```ignore
+//! use xilem_web::{elements::html, interfaces::Element, test_util::mount};
+//!
+//! fn counter(count: &u32) -> impl Element<u32> {
+//!     html::button(format!("clicked {count} times")).on_click(|count: &mut u32, _| *count += 1)
+//! }
+//!
+//! let mut harness = mount(0, counter(&0));
+//! harness.dispatch_event("button", &web_sys::MouseEvent::new("click").unwrap());
+//! harness.rebuild(counter(harness.app_state()));
+//! assert_eq!(harness.root_element().text_content().unwrap(), "clicked 1 times");
+//! ```
+
+use std::{cell::RefCell, rc::Rc};
+
+use wasm_bindgen::UnwrapThrowExt;
+use xilem_core::{Id, MessageResult};
+
+use crate::{app::AppRunner, view::DomNode, ChangeFlags, Cx, Message, View};
+
+/// Collects the messages sent by the views of a [`TestHarness`], until the harness handles them.
+#[derive(Clone, Default)]
+struct MessageQueue(Rc<RefCell<Vec<Message>>>);
+
+impl AppRunner for MessageQueue {
+    fn handle_message(&self, message: Message) {
+        self.0.borrow_mut().push(message);
+    }
+
+    fn clone_box(&self) -> Box<dyn AppRunner> {
+        Box::new(self.clone())
+    }
+}
+
+/// A view built into a container element, which is not attached to the document,
+/// unless [`TestHarness::attach_to_document`] is called.
+///
+/// While the container is detached, styles aren't computed. Nothing is left in the
+/// document when the harness is dropped, so tests don't interfere with each other.
+pub struct TestHarness<T, A, V: View<T, A>> {
+    app_state: T,
+    view: V,
+    id: Id,
+    state: V::State,
+    element: V::Element,
+    container: web_sys::Element,
+    messages: MessageQueue,
+    cx: Cx,
+}
+
+/// Build `view` with the initial `app_state` into a [`TestHarness`].
+pub fn mount<T, A, V: View<T, A>>(app_state: T, view: V) -> TestHarness<T, A, V> {
+    let messages = MessageQueue::default();
+    let mut cx = Cx::new();
+    cx.set_runner(messages.clone());
+    let container = cx.document().create_element("div").unwrap_throw();
+    cx.perf.start_frame();
+    let (id, state, element) = view.build(&mut cx);
+    cx.perf.end_frame();
+    container.append_child(element.as_node_ref()).unwrap_throw();
+    let mut harness = TestHarness {
+        app_state,
+        view,
+        id,
+        state,
+        element,
+        container,
+        messages,
+        cx,
+    };
+    harness.handle_messages();
+    harness
+}
+
+impl<T, A, V: View<T, A>> TestHarness<T, A, V> {
+    /// The DOM node built by the view.
+    pub fn root_node(&self) -> &web_sys::Node {
+        self.element.as_node_ref()
+    }
+
+    /// The element built by the view.
+    ///
+    /// # Panics
+    ///
+    /// If the view didn't build an element (e.g. a text node).
+    pub fn root_element(&self) -> web_sys::Element {
+        self.container
+            .first_element_child()
+            .expect_throw("the view didn't build an element")
+    }
+
+    pub fn app_state(&self) -> &T {
+        &self.app_state
+    }
+
+    pub fn app_state_mut(&mut self) -> &mut T {
+        &mut self.app_state
+    }
+
+    /// Rebuild the DOM with `new_view`, returns the resulting [`ChangeFlags`].
+    pub fn rebuild(&mut self, new_view: V) -> ChangeFlags {
+        #[cfg(debug_assertions)]
+        self.cx.message_trace.start_pass();
+        self.cx.perf.start_frame();
+        let changed = new_view.rebuild(
+            &mut self.cx,
+            &self.view,
+            &mut self.id,
+            &mut self.state,
+            &mut self.element,
+        );
+        self.cx.perf.end_frame();
+        self.view = new_view;
+        if changed.contains(ChangeFlags::STRUCTURE) {
+            self.container.set_text_content(None);
+            self.container
+                .append_child(self.element.as_node_ref())
+                .unwrap_throw();
+        }
+        self.handle_messages();
+        changed
+    }
+
+    /// Dispatch `event` on the first element matching the CSS `selector` and pass
+    /// the resulting messages to the view.
+    ///
+    /// Returns the actions of the handled messages.
+    ///
+    /// # Panics
+    ///
+    /// If no element matches `selector`.
+    pub fn dispatch_event(&mut self, selector: &str, event: &web_sys::Event) -> Vec<A> {
+        let target = self
+            .container
+            .query_selector(selector)
+            .unwrap_throw()
+            .unwrap_or_else(|| panic!("no element matches the selector {selector:?}"));
+        target.dispatch_event(event).unwrap_throw();
+        self.handle_messages()
+    }
+
+    /// Attach the container of the view to the body of the document, e.g. to test computed styles or focus.
+    ///
+    /// The container is removed from the document again when the harness is dropped.
+    pub fn attach_to_document(&mut self) {
+        self.cx
+            .document()
+            .body()
+            .unwrap_throw()
+            .append_child(&self.container)
+            .unwrap_throw();
+    }
+
+    /// The [`Cx`] used to build the view, e.g. to check [`Cx::take_dom_write_count`].
+    pub fn cx(&mut self) -> &mut Cx {
+        &mut self.cx
+    }
+
+    /// Pass the messages that were sent since the last rebuild or dispatched event to the view,
+    /// e.g. by listeners on targets outside of the view, like the window.
+    ///
+    /// Returns the actions of the handled messages.
+    pub fn handle_messages(&mut self) -> Vec<A> {
+        let mut actions = Vec::new();
+        loop {
+            let mut messages = std::mem::take(&mut *self.messages.0.borrow_mut());
+            messages.extend(self.cx.take_pending_messages());
+            if messages.is_empty() {
+                return actions;
+            }
+            for message in messages {
+                let result = self.view.message(
+                    &message.id_path[1..],
+                    &mut self.state,
+                    message.body,
+                    &mut self.app_state,
+                );
+                #[cfg(debug_assertions)]
+                self.cx.message_trace.record(&message.id_path, &result);
+                if let MessageResult::Action(action) = result {
+                    actions.push(action);
+                }
+            }
+        }
+    }
+}
+
+impl<T, A, V: View<T, A>> Drop for TestHarness<T, A, V> {
+    fn drop(&mut self) {
+        self.container.remove();
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::mount;
+    use crate::{elements::html, interfaces::Element};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn counter(count: &u32) -> impl Element<u32> {
+        html::button(format!("clicked {count} times")).on_click(|count: &mut u32, _| *count += 1)
+    }
+
+    #[wasm_bindgen_test]
+    fn click_counter() {
+        let mut harness = mount(0, counter(&0));
+        assert_eq!(harness.root_element().tag_name(), "BUTTON");
+
+        let click = web_sys::MouseEvent::new("click").unwrap();
+        harness.dispatch_event("button", &click);
+        assert_eq!(*harness.app_state(), 1);
+
+        harness.rebuild(counter(harness.app_state()));
+        assert_eq!(
+            harness.root_element().text_content().unwrap(),
+            "clicked 1 times"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A text node with a formatted value, see [`text_fmt`].
+
+use std::fmt::{self, Write as _};
+
+use wasm_bindgen::UnwrapThrowExt;
+use xilem_core::{Id, MessageResult};
+
+use crate::{ChangeFlags, Cx, View, ViewMarker};
+
+/// A text node with `value` formatted by a formatter, see [`text_fmt`].
+pub struct TextFmt<V, F> {
+    value: V,
+    formatter: F,
+}
+
+pub struct TextFmtState {
+    /// The current text of the node.
+    text: String,
+    /// The buffer the text is formatted into on rebuild, so that no string is allocated
+    /// when the text stays the same.
+    scratch: String,
+}
+
+/// A text node with `value` formatted by `formatter`, e.g. with a fixed number of decimals:
+///
+/// ```ignore
+/// text_fmt(price, |price, f| write!(f, "{price:.2} €"))
+/// ```
+///
+/// The formatter writes into [`fmt::Formatter`] like [`Display::fmt`](fmt::Display::fmt),
+/// so any formatting (e.g. with the thousands separators of a locale the closure captures)
+/// can be used without allocating an intermediate string. It's run on every rebuild,
+/// into a buffer that's reused, and the text node is only updated when the text changed.
+pub fn text_fmt<V, F>(value: V, formatter: F) -> TextFmt<V, F>
+where
+    F: Fn(&V, &mut fmt::Formatter) -> fmt::Result,
+{
+    TextFmt { value, formatter }
+}
+
+impl<V, F> fmt::Display for TextFmt<V, F>
+where
+    F: Fn(&V, &mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.formatter)(&self.value, f)
+    }
+}
+
+impl<V, F> ViewMarker for TextFmt<V, F> {}
+
+impl<T, A, V, F> View<T, A> for TextFmt<V, F>
+where
+    F: Fn(&V, &mut fmt::Formatter) -> fmt::Result,
+{
+    type State = TextFmtState;
+    type Element = web_sys::Text;
+
+    fn build(&self, _cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let text = self.to_string();
+        let element = web_sys::Text::new_with_data(&text).unwrap_throw();
+        let state = TextFmtState {
+            text,
+            scratch: String::new(),
+        };
+        (Id::next(), state, element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        state.scratch.clear();
+        write!(state.scratch, "{self}").unwrap_throw();
+        if state.scratch == state.text {
+            return ChangeFlags::empty();
+        }
+        std::mem::swap(&mut state.text, &mut state.scratch);
+        element.set_data(&state.text);
+        ChangeFlags::OTHER_CHANGE
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn text_is_only_updated_when_formatted_text_changes() {
+        use super::text_fmt;
+        use crate::{elements::html, test_util::mount, ChangeFlags};
+
+        let view = |value: f64| {
+            html::span::<(), (), _>(text_fmt(value, |value, f| write!(f, "{value:.2}")))
+        };
+        let mut harness = mount((), view(1.234));
+        let text = harness.root_node().first_child().unwrap();
+        assert_eq!(text.text_content().as_deref(), Some("1.23"));
+
+        // rounds to the same text
+        assert_eq!(harness.rebuild(view(1.2341)), ChangeFlags::empty());
+        assert_eq!(harness.root_node().first_child(), Some(text.clone()));
+
+        assert_eq!(harness.rebuild(view(5.0)), ChangeFlags::OTHER_CHANGE);
+        assert_eq!(harness.root_node().first_child(), Some(text.clone()));
+        assert_eq!(text.text_content().as_deref(), Some("5.00"));
+    }
+}
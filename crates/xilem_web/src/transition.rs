@@ -0,0 +1,297 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Enter and leave transitions for elements that are added to or removed from the DOM.
+
+use std::{any::Any, borrow::Cow, cell::RefCell, marker::PhantomData, rc::Rc};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, Element},
+    view::DomNode,
+    ChangeFlags, Cx, View, ViewMarker,
+};
+
+type CowStr = Cow<'static, str>;
+
+/// The attribute that marks an element to be removed only after its leave transition has finished.
+///
+/// Its value is the class that is added to start that transition.
+pub(crate) const LEAVE_CLASS_ATTR: &str = "data-xilem-leave-class";
+
+/// The attribute that marks an element that is currently in its leave transition.
+///
+/// Such an element is not part of the children of its parent anymore, it's removed from the DOM at the end of the transition.
+pub(crate) const LEAVING_ATTR: &str = "data-xilem-leaving";
+
+pub struct Transition<E, T, A = ()> {
+    element: E,
+    enter_class: CowStr,
+    leave_class: CowStr,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+/// Animate the mounting and removal of `element`.
+///
+/// `enter_class` is added to the element when it's added to the DOM (e.g. to start a CSS animation).
+/// When the element is removed from its parent, `leave_class` is added instead,
+/// and the element stays in the DOM until its `transitionend` or `transitioncancel` event fires
+/// (or immediately, when the `leave_class` doesn't define a transition).
+/// In case neither fires (e.g. when the element is hidden), it's removed after the
+/// computed `transition-duration` and `transition-delay` have passed.
+/// This also applies when the element is replaced, e.g. by switching the branch of a [`OneOf2`](crate::OneOf2),
+/// then the new element is inserted before it.
+///
+/// If the element is added again while it's leaving (e.g. because the item of a list was re-added),
+/// a new element is created, and the leaving element is still removed after its transition.
+pub fn transition<E, T, A>(
+    element: E,
+    enter_class: impl Into<CowStr>,
+    leave_class: impl Into<CowStr>,
+) -> Transition<E, T, A> {
+    Transition {
+        element,
+        enter_class: enter_class.into(),
+        leave_class: leave_class.into(),
+        phantom: PhantomData,
+    }
+}
+
+impl<E, T, A> ViewMarker for Transition<E, T, A> {}
+impl<E, T, A> Sealed for Transition<E, T, A> {}
+
+impl<E: Element<T, A>, T, A> View<T, A> for Transition<E, T, A> {
+    type State = E::State;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.element.build(cx);
+        let el = element.as_node_ref().unchecked_ref::<web_sys::Element>();
+        el.class_list().add_1(&self.enter_class).unwrap_throw();
+        el.set_attribute(LEAVE_CLASS_ATTR, &self.leave_class)
+            .unwrap_throw();
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = self.element.rebuild(cx, &prev.element, id, state, element);
+        let el = element.as_node_ref().unchecked_ref::<web_sys::Element>();
+        if changed.contains(ChangeFlags::STRUCTURE) {
+            // the element was recreated, so it's mounted again
+            el.class_list().add_1(&self.enter_class).unwrap_throw();
+            el.set_attribute(LEAVE_CLASS_ATTR, &self.leave_class)
+                .unwrap_throw();
+        } else {
+            if prev.enter_class != self.enter_class {
+                el.class_list().remove_1(&prev.enter_class).unwrap_throw();
+                el.class_list().add_1(&self.enter_class).unwrap_throw();
+                changed |= ChangeFlags::OTHER_CHANGE;
+            }
+            if prev.leave_class != self.leave_class {
+                el.set_attribute(LEAVE_CLASS_ATTR, &self.leave_class)
+                    .unwrap_throw();
+                changed |= ChangeFlags::OTHER_CHANGE;
+            }
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.element.message(id_path, state, message, app_state)
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(Element, Transition);
+
+/// Whether `node` is an element that is currently in its leave transition.
+pub(crate) fn is_leaving(node: &web_sys::Node) -> bool {
+    node.dyn_ref::<web_sys::Element>()
+        .is_some_and(|el| el.has_attribute(LEAVING_ATTR))
+}
+
+/// Remove `node` from `parent`, after the leave transition of `node` has finished, if it has one.
+pub(crate) fn remove_child(parent: &web_sys::Node, node: &web_sys::Node) {
+    let Some(el) = node.dyn_ref::<web_sys::Element>() else {
+        parent.remove_child(node).unwrap_throw();
+        return;
+    };
+    let Some(leave_class) = el.get_attribute(LEAVE_CLASS_ATTR) else {
+        parent.remove_child(node).unwrap_throw();
+        return;
+    };
+    el.set_attribute(LEAVING_ATTR, "").unwrap_throw();
+    el.class_list().add_1(&leave_class).unwrap_throw();
+
+    let style = web_sys::window().and_then(|window| window.get_computed_style(el).ok().flatten());
+    let property = |name| {
+        style
+            .as_ref()
+            .and_then(|style| style.get_property_value(name).ok())
+            .unwrap_or_default()
+    };
+    let Some(end_ms) = transition_end(
+        &property("transition-duration"),
+        &property("transition-delay"),
+    ) else {
+        parent.remove_child(node).unwrap_throw();
+        return;
+    };
+
+    // The listeners are dropped when the element is removed, which also frees their closures
+    let listeners = Rc::new(RefCell::new(Vec::new()));
+    let finish = {
+        let el = el.clone();
+        let listeners = listeners.clone();
+        move || {
+            if listeners.borrow_mut().drain(..).count() > 0 {
+                el.remove();
+            }
+        }
+    };
+    for event_type in ["transitionend", "transitioncancel"] {
+        let el_ref = el.clone();
+        let finish = finish.clone();
+        let listener = EventListener::new(el, event_type, move |event| {
+            // ignore transitions of children, they bubble up to this element
+            if event.target().as_ref() == Some(el_ref.as_ref()) {
+                finish();
+            }
+        });
+        listeners.borrow_mut().push(listener);
+    }
+
+    // Neither event fires when e.g. the element or its parent is hidden with `display: none`
+    let callback = Closure::once_into_js(finish);
+    web_sys::window()
+        .unwrap_throw()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            callback.unchecked_ref(),
+            (end_ms + FALLBACK_SLACK_MS).ceil() as i32,
+        )
+        .unwrap_throw();
+}
+
+/// How long after the expected end of a leave transition the element is removed, when no
+/// `transitionend` or `transitioncancel` event fired.
+const FALLBACK_SLACK_MS: f64 = 100.0;
+
+/// The time in milliseconds until the transitions with the computed `transition-duration` and
+/// `transition-delay` (comma separated lists) have ended, or `None` if no duration is non-zero.
+///
+/// Like CSS, the shorter list is repeated to match the length of the other.
+fn transition_end(transition_duration: &str, transition_delay: &str) -> Option<f64> {
+    let durations: Vec<_> = transition_duration.split(',').map(parse_time).collect();
+    let delays: Vec<_> = transition_delay.split(',').map(parse_time).collect();
+    (0..durations.len().max(delays.len()))
+        .filter_map(|i| {
+            let duration = durations[i % durations.len()]?;
+            let delay = delays[i % delays.len()].unwrap_or(0.0);
+            (duration > 0.0).then_some(duration + delay.max(0.0))
+        })
+        .reduce(f64::max)
+}
+
+/// Parse a CSS time (e.g. `0.3s` or `150ms`) into milliseconds.
+fn parse_time(time: &str) -> Option<f64> {
+    let time = time.trim();
+    if let Some(ms) = time.strip_suffix("ms") {
+        ms.parse().ok()
+    } else {
+        time.strip_suffix('s')
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(|s| s * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::transition_end;
+
+    #[test]
+    fn zero_durations_have_no_transition() {
+        assert_eq!(transition_end("", ""), None);
+        assert_eq!(transition_end("0s", "0s"), None);
+        assert_eq!(transition_end("0s, 0ms", "1s"), None);
+    }
+
+    #[test]
+    fn non_zero_durations_have_a_transition() {
+        assert_eq!(transition_end("0.3s", "0s"), Some(300.0));
+        assert_eq!(transition_end("0s, 150ms", "0s"), Some(150.0));
+    }
+
+    #[test]
+    fn delays_are_added_to_their_duration() {
+        assert_eq!(transition_end("0.3s", "0.2s"), Some(500.0));
+        // the delays are repeated for each duration
+        assert_eq!(transition_end("100ms, 0s, 200ms", "1s, 5s"), Some(1200.0));
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod browser_tests {
+    use super::transition;
+    use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn removed_node_persists_until_transitionend() {
+        let view = |items: &[&'static str]| {
+            html::ul::<(), (), _>(
+                items
+                    .iter()
+                    .map(|item| {
+                        transition(
+                            html::li(*item)
+                                .attr("id", *item)
+                                .attr("style", "transition: opacity 10s"),
+                            "enter",
+                            "leave",
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let mut harness = mount((), view(&["a", "b"]));
+        harness.attach_to_document();
+        harness.rebuild(view(&["a"]));
+
+        let root = harness.root_element();
+        let leaving = root.query_selector("#b").unwrap().unwrap();
+        assert!(leaving.is_connected());
+        assert!(leaving.class_list().contains("leave"));
+
+        let mut init = web_sys::TransitionEventInit::new();
+        init.bubbles(true);
+        let event =
+            web_sys::TransitionEvent::new_with_event_init_dict("transitionend", &init).unwrap();
+        // bubbling up from a child, which doesn't end the transition of the element
+        leaving
+            .first_child()
+            .unwrap()
+            .dispatch_event(&event)
+            .unwrap();
+        assert!(leaving.is_connected());
+
+        leaving.dispatch_event(&event).unwrap();
+        assert!(!leaving.is_connected());
+        assert_eq!(root.child_element_count(), 1);
+    }
+}
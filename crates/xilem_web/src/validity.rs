@@ -0,0 +1,108 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Custom validation messages for form controls.
+
+use std::{any::Any, borrow::Cow, marker::PhantomData};
+
+use wasm_bindgen::JsCast;
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    interfaces::{sealed::Sealed, HtmlInputElement},
+    view::DomNode,
+    ChangeFlags, Cx, View, ViewMarker,
+};
+
+type CowStr = Cow<'static, str>;
+
+/// Sets the custom validation message of an `<input>` element, see [`HtmlInputElement::custom_validity`].
+pub struct CustomValidity<E, T, A = ()> {
+    pub(crate) element: E,
+    pub(crate) message: Option<CowStr>,
+    pub(crate) phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<E, T, A> CustomValidity<E, T, A> {
+    pub(crate) fn new(element: E, message: Option<&str>) -> Self {
+        CustomValidity {
+            element,
+            message: message.map(|message| message.to_owned().into()),
+            phantom: PhantomData,
+        }
+    }
+}
+
+fn set_custom_validity(element: &impl DomNode, message: &Option<CowStr>) {
+    let input = element
+        .as_node_ref()
+        .unchecked_ref::<web_sys::HtmlInputElement>();
+    input.set_custom_validity(message.as_deref().unwrap_or(""));
+}
+
+impl<E, T, A> ViewMarker for CustomValidity<E, T, A> {}
+impl<E, T, A> Sealed for CustomValidity<E, T, A> {}
+
+impl<E: HtmlInputElement<T, A>, T, A> View<T, A> for CustomValidity<E, T, A> {
+    type State = E::State;
+    type Element = E::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.element.build(cx);
+        if self.message.is_some() {
+            set_custom_validity(&element, &self.message);
+        }
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = self.element.rebuild(cx, &prev.element, id, state, element);
+        if changed.contains(ChangeFlags::STRUCTURE) || prev.message != self.message {
+            // An empty message clears the custom validity, i.e. the field becomes valid again
+            set_custom_validity(element, &self.message);
+            changed |= ChangeFlags::OTHER_CHANGE;
+        }
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.element.message(id_path, state, message, app_state)
+    }
+}
+
+crate::interfaces::impl_dom_interfaces_for_ty!(HtmlInputElement, CustomValidity);
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use crate::{elements::html, interfaces::HtmlInputElement as _, Cx, View};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn custom_validity_makes_input_invalid() {
+        let mut cx = Cx::new();
+        let invalid = html::input::<(), (), _>(()).custom_validity(Some("Name is taken"));
+        let (mut id, mut state, mut element) = View::build(&invalid, &mut cx);
+        assert!(!element.check_validity());
+        assert_eq!(element.validation_message().unwrap(), "Name is taken");
+
+        let valid = html::input::<(), (), _>(()).custom_validity(None);
+        View::rebuild(&valid, &mut cx, &invalid, &mut id, &mut state, &mut element);
+        assert!(element.check_validity());
+    }
+}
@@ -3,9 +3,14 @@
 
 use std::{borrow::Borrow, fmt, ops::Index};
 
+use crate::diff::{diff_kv_iterables, Diff};
+
 /// Basically an ordered Map (similar as BTreeMap) with a Vec as backend for very few elements
 /// As it uses linear search instead of a tree traversal,
 /// which seems to be faster for small `n` (currently roughly `n < ~20` for the use case of diffing html attributes)
+///
+/// The entries are ordered by key (not by insertion), so two maps can be compared with [`VecMap::diff_against`],
+/// e.g. to apply only the changed props in custom views.
 pub struct VecMap<K, V>(Vec<(K, V)>);
 
 impl<K, V> Default for VecMap<K, V> {
@@ -30,8 +35,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut map = VecMap::default();
     /// map.insert(1, "a");
     /// assert_eq!(map.get(&1), Some(&"a"));
@@ -56,8 +61,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut map = VecMap::default();
     /// map.insert(1, "a");
     /// assert!(map.contains_key(&1));
@@ -80,8 +85,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut map = VecMap::default();
     /// map.insert(1, "a");
     /// if let Some(x) = map.get_mut(&1) {
@@ -106,8 +111,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut a = VecMap::default();
     /// a.insert(2, "b");
     /// a.insert(1, "a");
@@ -125,8 +130,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut map = VecMap::default();
     /// map.insert(3, "c");
     /// map.insert(2, "b");
@@ -156,8 +161,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut map = VecMap::default();
     /// assert_eq!(map.insert(37, "a"), None);
     /// assert_eq!(map.is_empty(), false);
@@ -193,8 +198,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut map = VecMap::default();
     /// map.insert(1, "a");
     /// assert_eq!(map.remove(&1), Some("a"));
@@ -212,6 +217,34 @@ impl<K, V> VecMap<K, V> {
         }
     }
 
+    /// Compares the map with the `prev` version of it,
+    /// i.e. what has to be added, changed or removed to get from `prev` to `self`, in the order of the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use xilem_web::{Diff, VecMap};
+    /// let mut prev = VecMap::default();
+    /// prev.insert("a", 1);
+    /// prev.insert("b", 2);
+    /// let mut next = VecMap::default();
+    /// next.insert("a", 3);
+    /// next.insert("c", 4);
+    ///
+    /// let mut diff = next.diff_against(&prev);
+    /// assert!(matches!(diff.next(), Some(Diff::Change(&"a", &3))));
+    /// assert!(matches!(diff.next(), Some(Diff::Remove(&"b"))));
+    /// assert!(matches!(diff.next(), Some(Diff::Add(&"c", &4))));
+    /// assert!(diff.next().is_none());
+    /// ```
+    pub fn diff_against<'a>(&'a self, prev: &'a Self) -> impl Iterator<Item = Diff<&'a K, &'a V>>
+    where
+        K: Ord,
+        V: PartialEq,
+    {
+        diff_kv_iterables(prev, self)
+    }
+
     pub fn clear(&mut self) {
         self.0.clear();
     }
@@ -222,8 +255,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut a = VecMap::default();
     /// assert!(a.is_empty());
     /// a.insert(1, "a");
@@ -239,8 +272,8 @@ impl<K, V> VecMap<K, V> {
     ///
     /// Basic usage:
     ///
-    /// ```ignore
-    /// # use crate::vecmap::VecMap;
+    /// ```
+    /// # use xilem_web::VecMap;
     /// let mut a = VecMap::default();
     /// assert_eq!(a.len(), 0);
     /// a.insert(1, "a");
@@ -279,7 +312,7 @@ impl<'a, K, V> IntoIterator for &'a VecMap<K, V> {
     }
 }
 
-// Basically all the doc tests from the rustdoc examples above, to avoid having to expose this module (pub)
+// Basically all the doc tests from the rustdoc examples above
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,6 +377,37 @@ mod tests {
         assert_eq!(map[&37], "c");
     }
 
+    #[test]
+    fn insert_existing_key_updates_in_place() {
+        let mut map = VecMap::default();
+        map.insert(2, "b");
+        map.insert(1, "a");
+        map.insert(2, "c");
+        assert_eq!(map.iter().collect::<Vec<_>>(), [(&1, &"a"), (&2, &"c")]);
+    }
+
+    #[test]
+    fn diff_against_reports_added_changed_and_removed_keys() {
+        let mut prev = VecMap::default();
+        prev.insert("class", "a");
+        prev.insert("href", "/");
+        prev.insert("title", "Home");
+        let mut next = VecMap::default();
+        next.insert("href", "/about");
+        next.insert("id", "nav");
+        next.insert("title", "Home");
+
+        let mut diff = next.diff_against(&prev);
+        assert!(matches!(diff.next(), Some(Diff::Remove(&"class"))));
+        assert!(matches!(
+            diff.next(),
+            Some(Diff::Change(&"href", &"/about"))
+        ));
+        assert!(matches!(diff.next(), Some(Diff::Add(&"id", &"nav"))));
+        assert!(diff.next().is_none());
+        assert!(next.diff_against(&next).next().is_none());
+    }
+
     #[test]
     fn remove() {
         let mut map = VecMap::default();
@@ -4,7 +4,7 @@
 //! Integration with xilem_core. This instantiates the View and related
 //! traits for DOM node generation.
 
-use std::{any::Any, borrow::Cow, ops::Deref};
+use std::{any::Any, borrow::Cow, ops::Deref, rc::Rc, sync::Arc};
 
 use xilem_core::{Id, MessageResult};
 
@@ -112,7 +112,9 @@ macro_rules! impl_string_view {
                 _state: &mut Self::State,
                 element: &mut Self::Element,
             ) -> ChangeFlags {
-                if prev != self {
+                // The DOM may already contain the new text, when it was edited by the user
+                // (`contenteditable`), writing it again would reset the caret.
+                if prev != self && element.data() != **self {
                     element.set_data(self);
                     ChangeFlags::OTHER_CHANGE
                 } else {
@@ -136,6 +138,10 @@ macro_rules! impl_string_view {
 impl_string_view!(String);
 impl_string_view!(&'static str);
 impl_string_view!(Cow<'static, str>);
+// Large strings shown in many places (e.g. translations) can be shared instead of copied.
+// Comparing a shared string to itself only compares the pointers, so rebuilding is cheap.
+impl_string_view!(Rc<str>);
+impl_string_view!(Arc<str>);
 
 // Specialization would probably avoid manual implementation,
 // but it's probably a good idea to have more control than via a blanket impl
@@ -197,3 +203,41 @@ impl_to_string_view!(usize);
 fn new_text(text: &str) -> web_sys::Text {
     web_sys::Text::new_with_data(text).unwrap()
 }
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{elements::html, interfaces::Element as _, test_util::mount, ChangeFlags};
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn shared_strings_are_not_copied() {
+        const NODES: usize = 100;
+        let shared: Arc<str> = "0123456789".repeat(1024).into();
+        let view = |text: &Arc<str>| {
+            html::div::<(), (), _>(
+                (0..NODES)
+                    .map(|_| html::span(text.clone()).attr("title", text.clone()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let mut harness = mount((), view(&shared));
+        let root = harness.root_element();
+        assert_eq!(root.child_element_count() as usize, NODES);
+        let span = root.first_element_child().unwrap();
+        assert_eq!(span.text_content().as_deref(), Some(&*shared));
+        assert_eq!(span.get_attribute("title").as_deref(), Some(&*shared));
+        // the text, the attribute and the applied attribute of every node refer to the one allocation
+        assert_eq!(Arc::strong_count(&shared), 3 * NODES + 1);
+
+        // the same allocation is unchanged, without comparing the 10KB
+        assert_eq!(harness.rebuild(view(&shared)), ChangeFlags::empty());
+        assert_eq!(Arc::strong_count(&shared), 3 * NODES + 1);
+
+        // a new allocation with the same contents is also unchanged, after comparing them
+        let copy: Arc<str> = (*shared).into();
+        assert_eq!(harness.rebuild(view(&copy)), ChangeFlags::empty());
+        assert_eq!(Arc::strong_count(&shared), 1);
+    }
+}
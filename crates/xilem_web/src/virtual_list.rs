@@ -0,0 +1,418 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A scrollable list that only builds the items which are currently visible.
+
+use std::{any::Any, cell::Cell, marker::PhantomData, ops::Range, rc::Rc};
+
+use gloo::events::EventListener;
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::{ChangeFlags, Cx, HtmlProps, MessageThunk},
+    elements::ChildrenSplice,
+    interfaces::{sealed::Sealed, Element, HtmlDivElement, HtmlElement},
+    Pod, View, ViewMarker, ViewSequence, HTML_NS,
+};
+
+/// The number of items that are built above and below the visible items by default.
+const DEFAULT_OVERSCAN: usize = 3;
+
+pub struct VirtualList<F, T, A = ()> {
+    total: usize,
+    item_height: f64,
+    overscan: usize,
+    render_item: F,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct VirtualListState<V, S> {
+    range: Range<usize>,
+    items: Vec<V>,
+    items_state: S,
+    child_elements: Vec<Pod>,
+    scratch: Vec<Pod>,
+    props: HtmlProps,
+    spacer: web_sys::HtmlElement,
+    window: web_sys::HtmlElement,
+    #[allow(unused)]
+    scroll_listener: EventListener,
+    has_debug_id: bool,
+}
+
+/// The message sent by a [`VirtualList`] to itself when it was scrolled (or mounted).
+struct Scrolled;
+
+/// A vertically scrolling list of `total` items, of which only the visible ones are built.
+///
+/// Every item has to be `item_height` pixels high, `render_item` is called with the index
+/// of each item that is (or is about to be) visible.
+///
+/// The list needs a fixed height (e.g. via `style`), its content is scrolled vertically.
+/// The visible items are determined from the scroll position whenever the list is rebuilt,
+/// so the list sends a message when it's scrolled, which causes the app to rebuild
+/// (at most once per animation frame).
+///
+/// The built items are identified by their slot in the visible window, not by their index:
+/// when scrolling by one item, each slot is rebuilt with the view of the next index. So the
+/// state of an item view (e.g. of a `memoize`d child) and of its elements (e.g. the text typed
+/// into an `<input>` or its focus) moves to a different index when the list is scrolled,
+/// items should be built from the app state alone.
+pub fn virtual_list<F, V, T, A>(
+    total: usize,
+    item_height: f64,
+    render_item: F,
+) -> VirtualList<F, T, A>
+where
+    F: Fn(usize) -> V,
+{
+    VirtualList {
+        total,
+        item_height,
+        overscan: DEFAULT_OVERSCAN,
+        render_item,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, T, A> VirtualList<F, T, A> {
+    /// Set the number of items that are built above and below the visible items (default = `3`).
+    ///
+    /// More items make it less likely to see empty space when scrolling fast.
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+}
+
+/// Computes the indices of the items that should be built.
+///
+/// These are the items that are visible in a viewport of `viewport_height` pixels scrolled by
+/// `scroll_top` pixels, plus `overscan` items above and below.
+pub fn visible_range(
+    scroll_top: f64,
+    viewport_height: f64,
+    item_height: f64,
+    total: usize,
+    overscan: usize,
+) -> Range<usize> {
+    if total == 0 || item_height <= 0.0 {
+        return 0..0;
+    }
+    let first_visible = (scroll_top.max(0.0) / item_height).floor() as usize;
+    let last_visible =
+        ((scroll_top.max(0.0) + viewport_height.max(0.0)) / item_height).ceil() as usize;
+    let start = first_visible.saturating_sub(overscan).min(total);
+    let end = last_visible.saturating_add(overscan).min(total);
+    start..end
+}
+
+/// Sends [`Scrolled`] in the animation frame after the list was scrolled, so that fast scrolling
+/// (with many `scroll` events per frame) only rebuilds the app once per frame.
+fn create_scroll_listener(element: &web_sys::Element, thunk: MessageThunk) -> EventListener {
+    let thunk = Rc::new(thunk);
+    let frame_requested = Rc::new(Cell::new(false));
+    EventListener::new(element, "scroll", move |_| {
+        if frame_requested.replace(true) {
+            return;
+        }
+        let callback = Closure::once_into_js({
+            let thunk = thunk.clone();
+            let frame_requested = frame_requested.clone();
+            move || {
+                frame_requested.set(false);
+                thunk.push_message(Scrolled);
+            }
+        });
+        web_sys::window()
+            .unwrap_throw()
+            .request_animation_frame(callback.unchecked_ref())
+            .unwrap_throw();
+    })
+}
+
+/// Makes `viewport` scroll its content, this has to be set again after the `style` attribute changed.
+fn set_viewport_style(viewport: &web_sys::HtmlElement) {
+    viewport
+        .style()
+        .set_property("overflow-y", "auto")
+        .unwrap_throw();
+}
+
+fn px(value: f64) -> String {
+    format!("{value}px")
+}
+
+impl<F, T, A> VirtualList<F, T, A> {
+    fn range(&self, viewport: &web_sys::Element) -> Range<usize> {
+        visible_range(
+            viewport.scroll_top() as f64,
+            viewport.client_height() as f64,
+            self.item_height,
+            self.total,
+            self.overscan,
+        )
+    }
+}
+
+impl<F, T, A> ViewMarker for VirtualList<F, T, A> {}
+impl<F, T, A> Sealed for VirtualList<F, T, A> {}
+
+impl<F, V, T, A> View<T, A> for VirtualList<F, T, A>
+where
+    F: Fn(usize) -> V,
+    V: View<T, A> + ViewMarker,
+{
+    type State = VirtualListState<V, <Vec<V> as ViewSequence<T, A>>::State>;
+    type Element = web_sys::HtmlDivElement;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (el, props) = cx.build_element(HTML_NS, "div");
+        set_viewport_style(el.unchecked_ref());
+
+        // The spacer has the height of all items, so that the scrollbar has the correct size
+        let spacer: web_sys::HtmlElement = cx
+            .document()
+            .create_element("div")
+            .unwrap_throw()
+            .unchecked_into();
+        spacer
+            .style()
+            .set_property("position", "relative")
+            .unwrap_throw();
+        let height = px(self.total as f64 * self.item_height);
+        spacer
+            .style()
+            .set_property("height", &height)
+            .unwrap_throw();
+        el.append_child(&spacer).unwrap_throw();
+
+        // The window contains the built items and is moved to where they are visible
+        let window: web_sys::HtmlElement = cx
+            .document()
+            .create_element("div")
+            .unwrap_throw()
+            .unchecked_into();
+        for (name, value) in [("position", "absolute"), ("left", "0"), ("right", "0")] {
+            window.style().set_property(name, value).unwrap_throw();
+        }
+        spacer.append_child(&window).unwrap_throw();
+
+        let range = self.range(&el);
+        let top = px(range.start as f64 * self.item_height);
+        window.style().set_property("top", &top).unwrap_throw();
+        let items: Vec<V> = range.clone().map(&self.render_item).collect();
+
+        let mut child_elements = vec![];
+        let mut scratch = vec![];
+        let mut splice = ChildrenSplice::new(&mut child_elements, &mut scratch, &window);
+        let id = cx.alloc_id();
+        let (items_state, scroll_listener) = cx.run_in(id, |cx| {
+            let items_state = items.build(cx, &mut splice);
+            let scroll_listener = create_scroll_listener(&el, cx.message_thunk());
+            // The size of the viewport is only known once it's in the document
+            cx.message_self(Box::new(Scrolled));
+            (items_state, scroll_listener)
+        });
+        debug_assert!(scratch.is_empty());
+
+        let mut has_debug_id = false;
+        cx.update_debug_id(&el, id, &mut has_debug_id);
+
+        let state = VirtualListState {
+            range,
+            items,
+            items_state,
+            child_elements,
+            scratch,
+            props,
+            spacer,
+            window,
+            scroll_listener,
+            has_debug_id,
+        };
+        (id, state, el.unchecked_into())
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changed = cx.rebuild_element(element, &mut state.props);
+        if !changed.is_empty() {
+            // e.g. a new `style` attribute replaced the whole inline style
+            set_viewport_style(element);
+        }
+        cx.update_debug_id(element, *id, &mut state.has_debug_id);
+
+        if prev.total != self.total || prev.item_height != self.item_height {
+            let height = px(self.total as f64 * self.item_height);
+            state
+                .spacer
+                .style()
+                .set_property("height", &height)
+                .unwrap_throw();
+            changed |= ChangeFlags::OTHER_CHANGE;
+        }
+
+        let range = self.range(element);
+        if range.start != state.range.start || prev.item_height != self.item_height {
+            let top = px(range.start as f64 * self.item_height);
+            state
+                .window
+                .style()
+                .set_property("top", &top)
+                .unwrap_throw();
+            changed |= ChangeFlags::OTHER_CHANGE;
+        }
+
+        // The items are rebuilt by their position in the window, when scrolling,
+        // the elements are reused for the items that are scrolled into view.
+        let items: Vec<V> = range.clone().map(&self.render_item).collect();
+        let mut splice =
+            ChildrenSplice::new(&mut state.child_elements, &mut state.scratch, &state.window);
+        changed |= cx.run_in(*id, |cx| {
+            items.rebuild(cx, &state.items, &mut state.items_state, &mut splice)
+        });
+        debug_assert!(state.scratch.is_empty());
+        state.items = items;
+        state.range = range;
+
+        changed.remove(ChangeFlags::STRUCTURE);
+        changed
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.is::<Scrolled>() => MessageResult::RequestRebuild,
+            _ => state
+                .items
+                .message(id_path, &mut state.items_state, message, app_state),
+        }
+    }
+}
+
+impl<F, V, T, A> Element<T, A> for VirtualList<F, T, A>
+where
+    F: Fn(usize) -> V,
+    V: View<T, A> + ViewMarker,
+{
+}
+impl<F, V, T, A> HtmlElement<T, A> for VirtualList<F, T, A>
+where
+    F: Fn(usize) -> V,
+    V: View<T, A> + ViewMarker,
+{
+}
+impl<F, V, T, A> HtmlDivElement<T, A> for VirtualList<F, T, A>
+where
+    F: Fn(usize) -> V,
+    V: View<T, A> + ViewMarker,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visible_range;
+
+    #[test]
+    fn only_visible_window_is_built() {
+        // 10000 items of 20px in a 200px high viewport
+        assert_eq!(visible_range(0.0, 200.0, 20.0, 10_000, 3), 0..13);
+        assert_eq!(visible_range(50_000.0, 200.0, 20.0, 10_000, 3), 2497..2513);
+        assert_eq!(visible_range(50_010.0, 200.0, 20.0, 10_000, 0), 2500..2511);
+    }
+
+    #[test]
+    fn range_is_clamped_to_items() {
+        assert_eq!(
+            visible_range(199_800.0, 200.0, 20.0, 10_000, 3),
+            9987..10_000
+        );
+        // e.g. after the number of items shrunk, while being scrolled to the end
+        assert_eq!(
+            visible_range(1_000_000.0, 200.0, 20.0, 10_000, 3),
+            10_000..10_000
+        );
+        assert_eq!(visible_range(0.0, 200.0, 20.0, 5, 3), 0..5);
+        assert_eq!(visible_range(0.0, 200.0, 20.0, 0, 3), 0..0);
+    }
+
+    #[test]
+    fn unmounted_list_builds_overscan_only() {
+        assert_eq!(visible_range(0.0, 0.0, 20.0, 10_000, 3), 0..3);
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod browser_tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::virtual_list;
+    use crate::{elements::html, interfaces::Element as _, test_util::mount};
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn only_visible_window_is_in_the_dom() {
+        let view = || {
+            virtual_list::<_, _, (), ()>(10_000, 20.0, |index| html::div(index.to_string()))
+                .attr("style", "height: 200px")
+        };
+        let mut harness = mount((), view());
+        harness.attach_to_document();
+        // the viewport has its size now
+        harness.rebuild(view());
+
+        let list = harness.root_element();
+        let window = list
+            .first_element_child()
+            .unwrap()
+            .first_element_child()
+            .unwrap();
+        let texts = || {
+            let first = window
+                .first_element_child()
+                .unwrap()
+                .text_content()
+                .unwrap();
+            let last = window.last_element_child().unwrap().text_content().unwrap();
+            (window.child_element_count(), first, last)
+        };
+        // 10 visible items and 3 below them
+        assert_eq!(texts(), (13, "0".into(), "12".into()));
+
+        list.set_scroll_top(50_000);
+        harness.rebuild(view());
+        // 10 visible items and 3 above and below them
+        assert_eq!(texts(), (16, "2497".into(), "2512".into()));
+    }
+
+    #[wasm_bindgen_test]
+    fn changed_style_attribute_keeps_the_list_scrollable() {
+        use wasm_bindgen::JsCast;
+
+        let view = |height: &str| {
+            virtual_list::<_, _, (), ()>(100, 20.0, |index| html::div(index.to_string()))
+                .attr("style", format!("height: {height}"))
+        };
+        let mut harness = mount((), view("200px"));
+        harness.rebuild(view("300px"));
+
+        let list: web_sys::HtmlElement = harness.root_element().unchecked_into();
+        let style = list.style();
+        assert_eq!(style.get_property_value("height").unwrap(), "300px");
+        assert_eq!(style.get_property_value("overflow-y").unwrap(), "auto");
+    }
+}
@@ -0,0 +1,379 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offloading work to a web worker, see [`worker`].
+
+use std::{
+    any::Any, borrow::Cow, cell::Cell, fmt, future::poll_fn, marker::PhantomData, pin::pin, rc::Rc,
+};
+
+use futures_core::Stream;
+use gloo::events::EventListener;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use xilem_core::{Id, MessageResult};
+
+use crate::{
+    context::MessageThunk, interfaces::sealed::Sealed, ChangeFlags, Cx, OptionalAction, View,
+    ViewMarker,
+};
+
+type CowStr = Cow<'static, str>;
+
+/// An error of a web worker, see [`Worker::on_error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerError {
+    /// The worker couldn't be created, e.g. because the script URL is invalid, with the message of the exception.
+    Spawn(String),
+    /// An uncaught exception in the worker (e.g. when its script failed to load), with its message.
+    Error(String),
+    /// A message couldn't be sent to the worker, e.g. because it can't be cloned, with the message of the exception.
+    Post(String),
+    /// A message of the worker couldn't be deserialized.
+    MessageError,
+}
+
+impl WorkerError {
+    fn from_js(err: JsValue, variant: fn(String) -> Self) -> Self {
+        let message = match err.dyn_ref::<js_sys::Error>() {
+            Some(error) => error.message().into(),
+            None => err.as_string().unwrap_or_else(|| format!("{err:?}")),
+        };
+        variant(message)
+    }
+}
+
+impl fmt::Display for WorkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkerError::Spawn(message) => {
+                write!(f, "the web worker couldn't be created: {message}")
+            }
+            WorkerError::Error(message) => write!(f, "error in the web worker: {message}"),
+            WorkerError::Post(message) => {
+                write!(
+                    f,
+                    "the message couldn't be sent to the web worker: {message}"
+                )
+            }
+            WorkerError::MessageError => {
+                f.write_str("a message of the web worker couldn't be deserialized")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorkerError {}
+
+pub struct Worker<F, H, E, T, A = ()> {
+    script_url: CowStr,
+    to_worker: F,
+    on_message: H,
+    on_error: E,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+/// The type of the default of [`Worker::on_error`], which logs the errors.
+type LogError<T> = fn(&mut T, WorkerError);
+
+fn log_error<T>(_: &mut T, error: WorkerError) {
+    log::error!("{error}");
+}
+
+/// Run the script at `script_url` in a web worker, post the messages of the stream returned by
+/// `to_worker` to it, and pass the messages it posts back to `on_message`.
+///
+/// The worker is spawned when the view is built, and the stream is created then, i.e. the
+/// stream is the channel to the worker, e.g. the receiver of a channel whose sender is kept
+/// in the app state. When `script_url` changes, the worker is terminated and a new one is spawned,
+/// with a new stream. When the view is removed, the worker is terminated.
+///
+/// Errors are logged, use [`Worker::on_error`] to handle them.
+///
+/// The view itself is rendered as an empty text node.
+pub fn worker<F, S, H, T, A, OA>(
+    script_url: impl Into<CowStr>,
+    to_worker: F,
+    on_message: H,
+) -> Worker<F, H, LogError<T>, T, A>
+where
+    F: Fn() -> S,
+    S: Stream + 'static,
+    S::Item: Into<JsValue>,
+    H: Fn(&mut T, JsValue) -> OA,
+    OA: OptionalAction<A>,
+{
+    Worker {
+        script_url: script_url.into(),
+        to_worker,
+        on_message,
+        on_error: log_error::<T>,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, H, E, T, A> Worker<F, H, E, T, A> {
+    /// Handle the errors of the worker with `on_error` instead of logging them.
+    ///
+    /// The worker isn't terminated on errors, e.g. it still handles later messages after
+    /// an uncaught exception in one of its handlers.
+    pub fn on_error<E2, OE>(self, on_error: E2) -> Worker<F, H, E2, T, A>
+    where
+        E2: Fn(&mut T, WorkerError) -> OE,
+        OE: OptionalAction<A>,
+    {
+        Worker {
+            script_url: self.script_url,
+            to_worker: self.to_worker,
+            on_message: self.on_message,
+            on_error,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct WorkerState {
+    /// `None` when the worker couldn't be spawned.
+    worker: Option<web_sys::Worker>,
+    /// Set to `false` when the worker is terminated, to stop forwarding the stream.
+    alive: Rc<Cell<bool>>,
+    #[allow(unused)]
+    listeners: Vec<EventListener>,
+}
+
+impl Drop for WorkerState {
+    fn drop(&mut self) {
+        self.alive.set(false);
+        if let Some(worker) = &self.worker {
+            worker.terminate();
+        }
+    }
+}
+
+/// A message posted by the worker.
+struct Received(JsValue);
+
+struct Failed(WorkerError);
+
+impl<F, S, H, E, T, A> Worker<F, H, E, T, A>
+where
+    F: Fn() -> S,
+    S: Stream + 'static,
+    S::Item: Into<JsValue>,
+{
+    /// Spawn the worker and start forwarding the stream to it.
+    ///
+    /// This has to be called within the id of the view.
+    fn spawn(&self, cx: &mut Cx) -> WorkerState {
+        let alive = Rc::new(Cell::new(true));
+        let worker = match web_sys::Worker::new(&self.script_url) {
+            Ok(worker) => worker,
+            Err(err) => {
+                cx.message_self(Box::new(Failed(WorkerError::from_js(
+                    err,
+                    WorkerError::Spawn,
+                ))));
+                return WorkerState {
+                    worker: None,
+                    alive,
+                    listeners: Vec::new(),
+                };
+            }
+        };
+        let thunk = Rc::new(cx.message_thunk());
+        let listeners = create_listeners(&worker, &thunk);
+        forward(worker.clone(), (self.to_worker)(), alive.clone(), thunk);
+        WorkerState {
+            worker: Some(worker),
+            alive,
+            listeners,
+        }
+    }
+}
+
+fn create_listeners(worker: &web_sys::Worker, thunk: &Rc<MessageThunk>) -> Vec<EventListener> {
+    let message = EventListener::new(worker, "message", {
+        let thunk = thunk.clone();
+        move |event| {
+            let event: &web_sys::MessageEvent = event.unchecked_ref();
+            thunk.push_message(Received(event.data()));
+        }
+    });
+    let error = EventListener::new(worker, "error", {
+        let thunk = thunk.clone();
+        move |event| {
+            let message = event
+                .dyn_ref::<web_sys::ErrorEvent>()
+                .map(web_sys::ErrorEvent::message)
+                .unwrap_or_default();
+            thunk.push_message(Failed(WorkerError::Error(message)));
+        }
+    });
+    let message_error = EventListener::new(worker, "messageerror", {
+        let thunk = thunk.clone();
+        move |_| thunk.push_message(Failed(WorkerError::MessageError))
+    });
+    vec![message, error, message_error]
+}
+
+/// Post the items of `stream` to `worker`, until the stream ends or the worker is terminated.
+fn forward<S>(worker: web_sys::Worker, stream: S, alive: Rc<Cell<bool>>, thunk: Rc<MessageThunk>)
+where
+    S: Stream + 'static,
+    S::Item: Into<JsValue>,
+{
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut stream = pin!(stream);
+        while let Some(message) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            if !alive.get() {
+                break;
+            }
+            if let Err(err) = worker.post_message(&message.into()) {
+                thunk.push_message(Failed(WorkerError::from_js(err, WorkerError::Post)));
+            }
+        }
+    });
+}
+
+impl<F, H, E, T, A> ViewMarker for Worker<F, H, E, T, A> {}
+impl<F, H, E, T, A> Sealed for Worker<F, H, E, T, A> {}
+
+impl<F, S, H, E, T, A, OA, OE> View<T, A> for Worker<F, H, E, T, A>
+where
+    F: Fn() -> S,
+    S: Stream + 'static,
+    S::Item: Into<JsValue>,
+    H: Fn(&mut T, JsValue) -> OA,
+    OA: OptionalAction<A>,
+    E: Fn(&mut T, WorkerError) -> OE,
+    OE: OptionalAction<A>,
+{
+    type State = WorkerState;
+    type Element = web_sys::Text;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state) = cx.with_new_id(|cx| self.spawn(cx));
+        let element = web_sys::Text::new().unwrap_throw();
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        _element: &mut Self::Element,
+    ) -> ChangeFlags {
+        if prev.script_url != self.script_url {
+            // The old worker is terminated when its state is dropped
+            *state = cx.with_id(*id, |cx| self.spawn(cx));
+        }
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        if !id_path.is_empty() {
+            return MessageResult::Stale(message);
+        }
+        let action = match message.downcast::<Received>() {
+            Ok(received) => (self.on_message)(app_state, received.0).action(),
+            Err(message) => match message.downcast::<Failed>() {
+                Ok(failed) => (self.on_error)(app_state, failed.0).action(),
+                Err(message) => return MessageResult::Stale(message),
+            },
+        };
+        match action {
+            Some(action) => MessageResult::Action(action),
+            None => MessageResult::Nop,
+        }
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32", feature = "testing"))]
+mod tests {
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use futures_core::Stream;
+    use wasm_bindgen::JsValue;
+
+    use super::{worker, WorkerError};
+    use crate::test_util::mount;
+
+    /// A stream of the given messages.
+    struct Messages(Vec<JsValue>);
+
+    impl Stream for Messages {
+        type Item = JsValue;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<JsValue>> {
+            Poll::Ready((!self.0.is_empty()).then(|| self.0.remove(0)))
+        }
+    }
+
+    /// The URL of a worker running `script`.
+    fn script_url(script: &str) -> String {
+        let parts = js_sys::Array::of1(&script.into());
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_("text/javascript");
+        let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options).unwrap();
+        web_sys::Url::create_object_url_with_blob(&blob).unwrap()
+    }
+
+    async fn after_timeout(millis: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            web_sys::window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+                .unwrap();
+        });
+        wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn response_reaches_handler() {
+        let url = script_url("onmessage = (event) => postMessage(event.data * 2);");
+        let view = worker::<_, _, _, _, (), _>(
+            url,
+            || Messages(vec![21.into()]),
+            |responses: &mut Vec<f64>, response| responses.push(response.as_f64().unwrap()),
+        );
+        let mut harness = mount(vec![], view);
+        for _ in 0..50 {
+            harness.handle_messages();
+            if !harness.app_state().is_empty() {
+                break;
+            }
+            after_timeout(10).await;
+        }
+        assert_eq!(*harness.app_state(), [42.0]);
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn errors_reach_error_handler() {
+        let url = script_url("throw new Error('boom');");
+        let view =
+            worker::<_, _, _, _, (), _>(url, || Messages(vec![]), |_: &mut Vec<WorkerError>, _| ())
+                .on_error(|errors: &mut Vec<WorkerError>, error| errors.push(error));
+        let mut harness = mount(vec![], view);
+        for _ in 0..50 {
+            harness.handle_messages();
+            if !harness.app_state().is_empty() {
+                break;
+            }
+            after_timeout(10).await;
+        }
+        assert!(matches!(
+            harness.app_state()[..],
+            [WorkerError::Error(ref message)] if message.contains("boom")
+        ));
+    }
+}
@@ -176,7 +176,10 @@ impl<'w, 'a, 'b> TaffyLayoutCtx<'w, 'a, 'b> {
 }
 
 impl<'w, 'a, 'b> taffy::TraversePartialTree for TaffyLayoutCtx<'w, 'a, 'b> {
-    type ChildIter<'c> = ChildIter where Self: 'c;
+    type ChildIter<'c>
+        = ChildIter
+    where
+        Self: 'c;
 
     fn child_ids(&self, _parent_node_id: taffy::NodeId) -> Self::ChildIter<'_> {
         ChildIter(0..self.widget.children.len())